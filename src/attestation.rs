@@ -3,6 +3,8 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashSet;
 use std::fmt;
 
+use crate::kinds::KindRegistry;
+
 // ─── Span types ─────────────────────────────────────────────────────────────
 
 /// A position within an artifact (1-indexed).
@@ -42,6 +44,21 @@ impl Span {
 }
 
 /// Parse a span from CLI syntax: "42", "42:58", "42.5:58.80"
+/// Normalized line hashes of a span and the lines around it, used to find
+/// the span again after the file changes (see [`crate::pin`]). Field order
+/// is alphabetical (MCF canonical form).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// Hashes of up to two lines just after the span.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub after: Vec<String>,
+    /// Hashes of up to two lines just before the span.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub before: Vec<String>,
+    /// Hashes of the spanned lines, in order.
+    pub lines: Vec<String>,
+}
+
 pub fn parse_span(s: &str) -> Result<Span, String> {
     let parts: Vec<&str> = s.split(':').collect();
     match parts.len() {
@@ -199,6 +216,15 @@ impl std::str::FromStr for IssuerType {
 pub struct AttestationBody {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub detail: Option<String>,
+    /// BLAKE3 hex digest of the content reviewed (see [`crate::pin`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// When the signal stops counting toward scores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Fingerprint of the spanned text, for re-anchoring the span.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<Fingerprint>,
     pub kind: Kind,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub r#ref: Option<String>,
@@ -210,10 +236,37 @@ pub struct AttestationBody {
     pub summary: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub supersedes: Option<String>,
+    /// Rust item the attestation addresses, e.g. `parse_span` or
+    /// `impl Record::subject` (see [`crate::symbol`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 }
 
+/// A `pass` body with score 0, an empty summary, and no optional fields, so
+/// tests can spell out only the fields they care about.
+#[cfg(test)]
+impl Default for AttestationBody {
+    fn default() -> Self {
+        AttestationBody {
+            detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
+            kind: Kind::Pass,
+            r#ref: None,
+            score: 0,
+            span: None,
+            suggested_fix: None,
+            summary: String::new(),
+            supersedes: None,
+            symbol: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
 /// Epoch body fields. Field order is alphabetical (MCF canonical form).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct EpochBody {
@@ -230,6 +283,54 @@ pub struct DependencyBody {
     pub depends_on: Vec<String>,
 }
 
+/// Policy body fields. Field order is alphabetical (MCF canonical form).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PolicyBody {
+    pub rules: Vec<PolicyRule>,
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersedes: Option<String>,
+}
+
+/// Revocation body fields. Field order is alphabetical (MCF canonical form).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevocationBody {
+    /// IDs of the records being withdrawn.
+    pub revokes: Vec<String>,
+    pub summary: String,
+}
+
+/// A single gate rule within a policy. Field order is alphabetical (MCF).
+///
+/// A rule applies to every subject matching the `subjects` glob and may
+/// combine any of its checks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PolicyRule {
+    /// Kinds that must not appear among the subject's active attestations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbid: Vec<Kind>,
+    /// Minimum effective score.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<i32>,
+    /// Kinds that must appear among the subject's active attestations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub require: Vec<Requirement>,
+    /// Glob over subject names (`*` stays within a path segment, `**` spans).
+    pub subjects: String,
+}
+
+/// A required-kind check within a policy rule. Field order is alphabetical (MCF).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Requirement {
+    /// Only count attestations from this issuer type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_type: Option<IssuerType>,
+    pub kind: Kind,
+    /// Minimum number of matching attestations (defaults to 1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_count: Option<u32>,
+}
+
 // ─── Attestation struct ─────────────────────────────────────────────────────
 
 fn default_attestation_type() -> String {
@@ -311,6 +412,42 @@ pub struct DependencyRecord {
     pub body: DependencyBody,
 }
 
+// ─── PolicyRecord struct ────────────────────────────────────────────────────
+
+/// A policy record declaring gate rules enforced by `qualifier check`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PolicyRecord {
+    #[serde(default = "default_metabox")]
+    pub metabox: String,
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub subject: String,
+    pub issuer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_type: Option<IssuerType>,
+    pub created_at: DateTime<Utc>,
+    pub id: String,
+    pub body: PolicyBody,
+}
+
+// ─── RevocationRecord struct ────────────────────────────────────────────────
+
+/// A revocation record withdrawing earlier records about the same subject.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevocationRecord {
+    #[serde(default = "default_metabox")]
+    pub metabox: String,
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub subject: String,
+    pub issuer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_type: Option<IssuerType>,
+    pub created_at: DateTime<Utc>,
+    pub id: String,
+    pub body: RevocationBody,
+}
+
 // ─── Record enum ────────────────────────────────────────────────────────────
 
 /// A typed qualifier record. Dispatches on the `type` field in JSON.
@@ -319,6 +456,8 @@ pub enum Record {
     Attestation(Box<Attestation>),
     Epoch(Epoch),
     Dependency(DependencyRecord),
+    Policy(PolicyRecord),
+    Revocation(RevocationRecord),
     Unknown(serde_json::Value),
 }
 
@@ -328,6 +467,8 @@ impl Serialize for Record {
             Record::Attestation(a) => a.serialize(serializer),
             Record::Epoch(e) => e.serialize(serializer),
             Record::Dependency(d) => d.serialize(serializer),
+            Record::Policy(p) => p.serialize(serializer),
+            Record::Revocation(r) => r.serialize(serializer),
             Record::Unknown(v) => v.serialize(serializer),
         }
     }
@@ -357,6 +498,16 @@ impl<'de> Deserialize<'de> for Record {
                     serde_json::from_value(value).map_err(serde::de::Error::custom)?;
                 Ok(Record::Dependency(dep))
             }
+            "policy" => {
+                let policy: PolicyRecord =
+                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
+                Ok(Record::Policy(policy))
+            }
+            "revocation" => {
+                let revocation: RevocationRecord =
+                    serde_json::from_value(value).map_err(serde::de::Error::custom)?;
+                Ok(Record::Revocation(revocation))
+            }
             _ => Ok(Record::Unknown(value)),
         }
     }
@@ -369,6 +520,8 @@ impl Record {
             Record::Attestation(a) => &a.subject,
             Record::Epoch(e) => &e.subject,
             Record::Dependency(d) => &d.subject,
+            Record::Policy(p) => &p.subject,
+            Record::Revocation(r) => &r.subject,
             Record::Unknown(v) => v.get("subject").and_then(|v| v.as_str()).unwrap_or(""),
         }
     }
@@ -379,6 +532,8 @@ impl Record {
             Record::Attestation(a) => &a.id,
             Record::Epoch(e) => &e.id,
             Record::Dependency(d) => &d.id,
+            Record::Policy(p) => &p.id,
+            Record::Revocation(r) => &r.id,
             Record::Unknown(v) => v.get("id").and_then(|v| v.as_str()).unwrap_or(""),
         }
     }
@@ -392,10 +547,11 @@ impl Record {
         }
     }
 
-    /// Get the supersedes ID (attestations only).
+    /// Get the supersedes ID (attestations and policies).
     pub fn supersedes(&self) -> Option<&str> {
         match self {
             Record::Attestation(a) => a.body.supersedes.as_deref(),
+            Record::Policy(p) => p.body.supersedes.as_deref(),
             _ => None,
         }
     }
@@ -408,6 +564,35 @@ impl Record {
         }
     }
 
+    /// Get the tags (attestations only; empty otherwise).
+    pub fn tags(&self) -> &[String] {
+        match self {
+            Record::Attestation(a) => &a.body.tags,
+            _ => &[],
+        }
+    }
+
+    /// Get the expiry time (attestations only).
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Record::Attestation(a) => a.body.expires_at,
+            _ => None,
+        }
+    }
+
+    /// Returns true if the record has expired as of `at`.
+    pub fn is_expired(&self, at: DateTime<Utc>) -> bool {
+        self.expires_at().is_some_and(|expires_at| expires_at <= at)
+    }
+
+    /// Get the span (attestations only).
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            Record::Attestation(a) => a.body.span.as_ref(),
+            _ => None,
+        }
+    }
+
     /// Try to get this as an attestation.
     pub fn as_attestation(&self) -> Option<&Attestation> {
         match self {
@@ -424,12 +609,62 @@ impl Record {
         }
     }
 
+    /// Try to get this as a policy.
+    pub fn as_policy(&self) -> Option<&PolicyRecord> {
+        match self {
+            Record::Policy(p) => Some(p),
+            _ => None,
+        }
+    }
+
+    /// Try to get this as a revocation.
+    pub fn as_revocation(&self) -> Option<&RevocationRecord> {
+        match self {
+            Record::Revocation(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// IDs withdrawn by this record (revocations only; empty otherwise).
+    pub fn revokes(&self) -> &[String] {
+        match self {
+            Record::Revocation(r) => &r.body.revokes,
+            _ => &[],
+        }
+    }
+
+    /// Get the creation timestamp (`None` for unknown record types).
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Record::Attestation(a) => Some(a.created_at),
+            Record::Epoch(e) => Some(e.created_at),
+            Record::Dependency(d) => Some(d.created_at),
+            Record::Policy(p) => Some(p.created_at),
+            Record::Revocation(r) => Some(r.created_at),
+            Record::Unknown(_) => None,
+        }
+    }
+
+    /// Get the issuer URI (`None` for unknown record types).
+    pub fn issuer(&self) -> Option<&str> {
+        match self {
+            Record::Attestation(a) => Some(&a.issuer),
+            Record::Epoch(e) => Some(&e.issuer),
+            Record::Dependency(d) => Some(&d.issuer),
+            Record::Policy(p) => Some(&p.issuer),
+            Record::Revocation(r) => Some(&r.issuer),
+            Record::Unknown(v) => v.get("issuer").and_then(|v| v.as_str()),
+        }
+    }
+
     /// Get the issuer type classification.
     pub fn issuer_type(&self) -> Option<&IssuerType> {
         match self {
             Record::Attestation(a) => a.issuer_type.as_ref(),
             Record::Epoch(e) => e.issuer_type.as_ref(),
             Record::Dependency(d) => d.issuer_type.as_ref(),
+            Record::Policy(p) => p.issuer_type.as_ref(),
+            Record::Revocation(r) => r.issuer_type.as_ref(),
             Record::Unknown(_) => None,
         }
     }
@@ -485,11 +720,44 @@ struct DependencyCanonicalView<'a> {
     body: &'a DependencyBody,
 }
 
+/// Zero-copy canonical view for policy records (MCF).
+#[derive(Serialize)]
+struct PolicyCanonicalView<'a> {
+    metabox: &'a str,
+    r#type: &'a str,
+    subject: &'a str,
+    issuer: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issuer_type: Option<&'a IssuerType>,
+    created_at: &'a DateTime<Utc>,
+    id: &'a str,
+    body: &'a PolicyBody,
+}
+
+/// Zero-copy canonical view for revocation records (MCF).
+#[derive(Serialize)]
+struct RevocationCanonicalView<'a> {
+    metabox: &'a str,
+    r#type: &'a str,
+    subject: &'a str,
+    issuer: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issuer_type: Option<&'a IssuerType>,
+    created_at: &'a DateTime<Utc>,
+    id: &'a str,
+    body: &'a RevocationBody,
+}
+
 // ─── ID generation ──────────────────────────────────────────────────────────
 
 /// Generate a deterministic attestation ID by BLAKE3-hashing the canonical
 /// serialization with the `id` field set to the empty string.
 pub fn generate_id(attestation: &Attestation) -> String {
+    hash_canonical(&canonical_attestation(attestation))
+}
+
+/// Serialize an attestation record in Metabox Canonical Form (`id` set to `""`).
+fn canonical_attestation(attestation: &Attestation) -> String {
     let view = AttestationCanonicalView {
         metabox: &attestation.metabox,
         r#type: "attestation",
@@ -500,12 +768,16 @@ pub fn generate_id(attestation: &Attestation) -> String {
         id: "",
         body: &attestation.body,
     };
-    let canonical = serde_json::to_string(&view).expect("attestation must serialize");
-    blake3::hash(canonical.as_bytes()).to_hex().to_string()
+    serde_json::to_string(&view).expect("attestation must serialize")
 }
 
 /// Generate a deterministic epoch ID.
 pub fn generate_epoch_id(epoch: &Epoch) -> String {
+    hash_canonical(&canonical_epoch(epoch))
+}
+
+/// Serialize an epoch record in Metabox Canonical Form (`id` set to `""`).
+fn canonical_epoch(epoch: &Epoch) -> String {
     let view = EpochCanonicalView {
         metabox: &epoch.metabox,
         r#type: "epoch",
@@ -516,12 +788,16 @@ pub fn generate_epoch_id(epoch: &Epoch) -> String {
         id: "",
         body: &epoch.body,
     };
-    let canonical = serde_json::to_string(&view).expect("epoch must serialize");
-    blake3::hash(canonical.as_bytes()).to_hex().to_string()
+    serde_json::to_string(&view).expect("epoch must serialize")
 }
 
 /// Generate a deterministic dependency record ID.
 pub fn generate_dependency_id(dep: &DependencyRecord) -> String {
+    hash_canonical(&canonical_dependency(dep))
+}
+
+/// Serialize a dependency record in Metabox Canonical Form (`id` set to `""`).
+fn canonical_dependency(dep: &DependencyRecord) -> String {
     let view = DependencyCanonicalView {
         metabox: &dep.metabox,
         r#type: "dependency",
@@ -532,16 +808,76 @@ pub fn generate_dependency_id(dep: &DependencyRecord) -> String {
         id: "",
         body: &dep.body,
     };
-    let canonical = serde_json::to_string(&view).expect("dependency must serialize");
+    serde_json::to_string(&view).expect("dependency must serialize")
+}
+
+/// Generate a deterministic policy record ID.
+pub fn generate_policy_id(policy: &PolicyRecord) -> String {
+    hash_canonical(&canonical_policy(policy))
+}
+
+/// Serialize a policy record in Metabox Canonical Form (`id` set to `""`).
+fn canonical_policy(policy: &PolicyRecord) -> String {
+    let view = PolicyCanonicalView {
+        metabox: &policy.metabox,
+        r#type: "policy",
+        subject: &policy.subject,
+        issuer: &policy.issuer,
+        issuer_type: policy.issuer_type.as_ref(),
+        created_at: &policy.created_at,
+        id: "",
+        body: &policy.body,
+    };
+    serde_json::to_string(&view).expect("policy must serialize")
+}
+
+/// Generate a deterministic revocation record ID.
+pub fn generate_revocation_id(revocation: &RevocationRecord) -> String {
+    hash_canonical(&canonical_revocation(revocation))
+}
+
+/// Serialize a revocation record in Metabox Canonical Form (`id` set to `""`).
+fn canonical_revocation(revocation: &RevocationRecord) -> String {
+    let view = RevocationCanonicalView {
+        metabox: &revocation.metabox,
+        r#type: "revocation",
+        subject: &revocation.subject,
+        issuer: &revocation.issuer,
+        issuer_type: revocation.issuer_type.as_ref(),
+        created_at: &revocation.created_at,
+        id: "",
+        body: &revocation.body,
+    };
+    serde_json::to_string(&view).expect("revocation must serialize")
+}
+
+fn hash_canonical(canonical: &str) -> String {
     blake3::hash(canonical.as_bytes()).to_hex().to_string()
 }
 
+/// Serialize any known record type in Metabox Canonical Form, with `id` set
+/// to `""`. This is the exact byte string hashed to produce the record ID.
+///
+/// Returns `None` for unknown record types, which have no defined MCF.
+pub fn canonical_form(record: &Record) -> Option<String> {
+    match record {
+        Record::Attestation(a) => Some(canonical_attestation(a)),
+        Record::Epoch(e) => Some(canonical_epoch(e)),
+        Record::Dependency(d) => Some(canonical_dependency(d)),
+        Record::Policy(p) => Some(canonical_policy(p)),
+        Record::Revocation(r) => Some(canonical_revocation(r)),
+        Record::Unknown(_) => None,
+    }
+}
+
 /// Generate a deterministic ID for any record type.
 pub fn generate_record_id(record: &Record) -> String {
     match record {
         Record::Attestation(a) => generate_id(a),
         Record::Epoch(e) => generate_epoch_id(e),
         Record::Dependency(d) => generate_dependency_id(d),
+        Record::Policy(p) => generate_policy_id(p),
+        Record::Revocation(r) => generate_revocation_id(r),
         Record::Unknown(_) => String::new(),
     }
 }
@@ -550,6 +886,13 @@ pub fn generate_record_id(record: &Record) -> String {
 
 /// Validate an attestation, returning all validation errors found.
 pub fn validate(attestation: &Attestation) -> Vec<String> {
+    validate_with(attestation, &KindRegistry::default())
+}
+
+/// Validate an attestation against the project's declared `kinds`: scores
+/// must respect a declared kind's range and sign, and kind typos are
+/// checked against declared kinds as well as the built-ins.
+pub fn validate_with(attestation: &Attestation, kinds: &KindRegistry) -> Vec<String> {
     let mut errors = Vec::new();
 
     if attestation.metabox != "1" {
@@ -575,6 +918,30 @@ pub fn validate(attestation: &Attestation) -> Vec<String> {
             attestation.body.score
         ));
     }
+    if let Some(expires_at) = attestation.body.expires_at
+        && expires_at <= attestation.created_at
+    {
+        errors.push(format!(
+            "expires_at {} must be after created_at {}",
+            expires_at.to_rfc3339(),
+            attestation.created_at.to_rfc3339()
+        ));
+    }
+    if let Some(fingerprint) = &attestation.body.fingerprint {
+        match &attestation.body.span {
+            None => errors.push("fingerprint requires a span".into()),
+            Some(span) => {
+                let spanned = span.end_or_start().line.saturating_sub(span.start.line) + 1;
+                if fingerprint.lines.len() != spanned as usize {
+                    errors.push(format!(
+                        "fingerprint covers {} line(s) but span covers {}",
+                        fingerprint.lines.len(),
+                        spanned
+                    ));
+                }
+            }
+        }
+    }
     if attestation.id.is_empty() {
         errors.push("id must not be empty".into());
     }
@@ -606,13 +973,16 @@ pub fn validate(attestation: &Attestation) -> Vec<String> {
             "suggestion",
             "waiver",
         ];
-        for k in &known {
-            if is_likely_typo(custom, k) {
-                errors.push(format!("unknown kind '{}', did you mean '{}'?", custom, k));
-                break;
+        if !kinds.is_known(&attestation.body.kind) {
+            for k in known.into_iter().chain(kinds.names()) {
+                if is_likely_typo(custom, k) {
+                    errors.push(format!("unknown kind '{}', did you mean '{}'?", custom, k));
+                    break;
+                }
             }
         }
     }
+    errors.extend(kinds.check(attestation));
 
     // Validate span
     if let Some(ref span) = attestation.body.span {
@@ -731,6 +1101,47 @@ pub fn validate_supersession_targets(records: &[Record]) -> crate::Result<()> {
     Ok(())
 }
 
+/// Validate that revocations target records of the same subject.
+///
+/// Returns an error for a revocation with no targets, one that targets a
+/// record about another subject, or one that targets another revocation.
+/// Targets not present in `records` are allowed.
+pub fn validate_revocation_targets(records: &[Record]) -> crate::Result<()> {
+    let by_id: std::collections::HashMap<&str, &Record> =
+        records.iter().map(|r| (r.id(), r)).collect();
+
+    for revocation in records.iter().filter_map(|r| r.as_revocation()) {
+        let short = &revocation.id[..revocation.id.len().min(8)];
+        if revocation.body.revokes.is_empty() {
+            return Err(crate::Error::Validation(format!(
+                "revocation {short} does not revoke any records"
+            )));
+        }
+        for target_id in &revocation.body.revokes {
+            let Some(target) = by_id.get(target_id.as_str()) else {
+                continue;
+            };
+            if target.subject() != revocation.subject {
+                return Err(crate::Error::Validation(format!(
+                    "revocation {} (subject '{}') revokes {} (subject '{}') \
+                     — cross-subject revocation is not allowed",
+                    short,
+                    revocation.subject,
+                    &target_id[..target_id.len().min(8)],
+                    target.subject()
+                )));
+            }
+            if matches!(target, Record::Revocation(_)) {
+                return Err(crate::Error::Validation(format!(
+                    "revocation {short} revokes another revocation \
+                     — revocations cannot be revoked"
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
 // ─── Finalize ───────────────────────────────────────────────────────────────
 
 /// Clamp a score to the valid range [-100, 100].
@@ -778,12 +1189,52 @@ pub fn finalize_record(record: Record) -> Record {
             d.id = generate_dependency_id(&d);
             Record::Dependency(d)
         }
+        Record::Policy(mut p) => {
+            p.metabox = "1".into();
+            p.record_type = "policy".to_string();
+            p.id = String::new();
+            p.id = generate_policy_id(&p);
+            Record::Policy(p)
+        }
+        Record::Revocation(mut r) => {
+            r.metabox = "1".into();
+            r.record_type = "revocation".to_string();
+            r.id = String::new();
+            r.id = generate_revocation_id(&r);
+            Record::Revocation(r)
+        }
         other => other,
     }
 }
 
 // ─── Tests ──────────────────────────────────────────────────────────────────
 
+/// Fixtures shared by unit tests across the crate.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+
+    /// An unfinalized attestation about `subject` from `mailto:test@test.com`,
+    /// created at 2026-02-24T10:00:00Z.
+    pub(crate) fn attestation(subject: &str, body: AttestationBody) -> Attestation {
+        Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: "2026-02-24T10:00:00Z".parse().unwrap(),
+            id: String::new(),
+            body,
+        }
+    }
+
+    /// [`attestation`], finalized and wrapped in a [`Record`].
+    pub(crate) fn record(subject: &str, body: AttestationBody) -> Record {
+        Record::Attestation(Box::new(finalize(attestation(subject, body))))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -802,6 +1253,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Concern,
                 r#ref: None,
                 score: -30,
@@ -809,6 +1263,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "Panics on malformed input".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         };
@@ -835,6 +1290,32 @@ mod tests {
         assert_ne!(att1.id, att2.id);
     }
 
+    #[test]
+    fn test_expires_at_is_hashed_and_validated() {
+        let att = sample_attestation();
+        let canonical = canonical_attestation(&att);
+        assert!(!canonical.contains("expires_at"));
+
+        let mut expiring = att.clone();
+        expiring.body.expires_at = Some(att.created_at + chrono::Duration::days(90));
+        expiring.id = generate_id(&expiring);
+        assert_ne!(att.id, expiring.id);
+        assert!(canonical_attestation(&expiring).contains("\"expires_at\":"));
+        assert!(validate(&expiring).is_empty());
+
+        let record = Record::Attestation(Box::new(expiring.clone()));
+        assert!(!record.is_expired(att.created_at));
+        assert!(record.is_expired(att.created_at + chrono::Duration::days(90)));
+
+        expiring.body.expires_at = Some(att.created_at);
+        expiring.id = generate_id(&expiring);
+        let errors = validate(&expiring);
+        assert!(
+            errors.iter().any(|e| e.contains("expires_at")),
+            "{errors:?}"
+        );
+    }
+
     #[test]
     fn test_validate_valid() {
         let att = sample_attestation();
@@ -854,6 +1335,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 0,
@@ -861,6 +1345,7 @@ mod tests {
                 suggested_fix: None,
                 summary: String::new(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         };
@@ -909,6 +1394,9 @@ mod tests {
             id: "will be replaced".into(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 200, // over max
@@ -916,6 +1404,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "good".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         };
@@ -937,6 +1426,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Concern,
                 r#ref: None,
                 score: -10,
@@ -950,6 +1442,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "issue".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         };
@@ -980,6 +1473,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Concern,
                 r#ref: None,
                 score: -10,
@@ -987,6 +1483,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "issue".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         });
@@ -1001,6 +1498,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Concern,
                 r#ref: None,
                 score: -10,
@@ -1014,6 +1514,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "issue".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         });
@@ -1034,6 +1535,9 @@ mod tests {
             id: "aaa".into(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 10,
@@ -1041,6 +1545,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "a".into(),
                 supersedes: Some("bbb".into()),
+                symbol: None,
                 tags: vec![],
             },
         }));
@@ -1054,6 +1559,9 @@ mod tests {
             id: "bbb".into(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 10,
@@ -1061,6 +1569,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "b".into(),
                 supersedes: Some("aaa".into()),
+                symbol: None,
                 tags: vec![],
             },
         }));
@@ -1151,6 +1660,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 10,
@@ -1158,6 +1670,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "ok".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         })));
@@ -1172,6 +1685,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 20,
@@ -1179,6 +1695,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "updated".into(),
                 supersedes: Some(a_id),
+                symbol: None,
                 tags: vec![],
             },
         })));
@@ -1199,6 +1716,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Concern,
                 r#ref: None,
                 score: -10,
@@ -1206,6 +1726,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "bad".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         })));
@@ -1220,6 +1741,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 20,
@@ -1227,6 +1751,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "fixed".into(),
                 supersedes: Some(a_id),
+                symbol: None,
                 tags: vec![],
             },
         })));
@@ -1255,6 +1780,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 10,
@@ -1262,6 +1790,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "ok".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         });
@@ -1285,6 +1814,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 10,
@@ -1292,6 +1824,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "ok".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         });
@@ -1306,6 +1839,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 10,
@@ -1313,6 +1849,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "ok".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         });
@@ -1327,6 +1864,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: Some("git:abc123".into()),
                 score: 10,
@@ -1334,6 +1874,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "ok".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         });
@@ -1356,6 +1897,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 10,
@@ -1363,6 +1907,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "ok".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         };
@@ -1391,6 +1936,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Praise,
                 r#ref: Some("git:3aba500".into()),
                 score: 30,
@@ -1398,6 +1946,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "great".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec!["quality".into()],
             },
         });
@@ -1427,6 +1976,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score: 10,
@@ -1434,6 +1986,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "ok".into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         });
@@ -1481,6 +2034,59 @@ mod tests {
         assert_eq!(parsed.as_epoch().unwrap().body.score, 30);
     }
 
+    fn sample_revocation(subject: &str, revokes: Vec<String>) -> Record {
+        finalize_record(Record::Revocation(RevocationRecord {
+            metabox: "1".into(),
+            record_type: "revocation".into(),
+            subject: subject.into(),
+            issuer: "mailto:alice@example.com".into(),
+            issuer_type: None,
+            created_at: DateTime::parse_from_rfc3339("2026-02-25T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            id: String::new(),
+            body: RevocationBody {
+                revokes,
+                summary: "Filed against the wrong function".into(),
+            },
+        }))
+    }
+
+    #[test]
+    fn test_revocation_roundtrip() {
+        let att = sample_attestation();
+        let record = sample_revocation("src/parser.rs", vec![att.id.clone()]);
+
+        let json = serde_json::to_string(&record).unwrap();
+        assert!(json.contains("\"type\":\"revocation\""));
+        assert!(json.contains("\"body\":{\"revokes\":["));
+
+        let parsed: Record = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, record);
+        assert_eq!(parsed.revokes(), std::slice::from_ref(&att.id));
+        assert_eq!(parsed.id(), generate_record_id(&parsed));
+        assert!(!parsed.is_scored());
+    }
+
+    #[test]
+    fn test_validate_revocation_targets() {
+        let att = sample_attestation();
+        let ok = sample_revocation("src/parser.rs", vec![att.id.clone()]);
+        let records = vec![Record::Attestation(Box::new(att.clone())), ok.clone()];
+        assert!(validate_revocation_targets(&records).is_ok());
+
+        let cross = sample_revocation("src/other.rs", vec![att.id.clone()]);
+        let records = vec![Record::Attestation(Box::new(att)), cross];
+        let err = validate_revocation_targets(&records).unwrap_err();
+        assert!(err.to_string().contains("cross-subject"));
+
+        let nested = sample_revocation("src/parser.rs", vec![ok.id().to_string()]);
+        assert!(validate_revocation_targets(&[ok, nested]).is_err());
+
+        let empty = sample_revocation("src/parser.rs", vec![]);
+        assert!(validate_revocation_targets(&[empty]).is_err());
+    }
+
     #[test]
     fn test_unknown_record_type_preserved() {
         let json = r#"{"metabox":"1","type":"custom-thing","subject":"x.rs","issuer":"mailto:test@test.com","created_at":"2026-02-24T10:00:00Z","id":"abc","body":{"foo":"bar"}}"#;
@@ -4,6 +4,9 @@ use std::io::{self, BufRead};
 use std::path::Path;
 
 use crate::attestation::{self, Attestation, AttestationBody, IssuerType, Kind, Record};
+use crate::cli::{config, output};
+use crate::kinds::KindRegistry;
+use crate::pin;
 use crate::qual_file;
 
 #[derive(ClapArgs)]
@@ -47,6 +50,10 @@ pub struct Args {
     #[arg(long)]
     pub span: Option<String>,
 
+    /// Rust item the attestation is about (e.g., "parse_span", "impl Record::subject")
+    #[arg(long)]
+    pub symbol: Option<String>,
+
     /// VCS ref to pin (e.g., "git:3aba500")
     #[arg(long, name = "ref")]
     pub r#ref: Option<String>,
@@ -55,6 +62,19 @@ pub struct Args {
     #[arg(long)]
     pub supersedes: Option<String>,
 
+    /// Record a BLAKE3 digest of the subject's content (or the span's lines)
+    #[arg(long)]
+    pub pin: bool,
+
+    /// Fingerprint the spanned lines so `qualifier reanchor` can follow them
+    /// (needs --span or --symbol)
+    #[arg(long)]
+    pub fingerprint: bool,
+
+    /// Stop counting this attestation after this RFC 3339 timestamp or date
+    #[arg(long)]
+    pub expires_at: Option<String>,
+
     /// Explicit .qual file to write to (overrides layout resolution)
     #[arg(long)]
     pub file: Option<String>,
@@ -80,8 +100,6 @@ pub fn run(args: Args) -> crate::Result<()> {
 
     let kind: Kind = args.kind.as_deref().unwrap_or("concern").parse().unwrap();
 
-    let score = args.score.unwrap_or_else(|| kind.default_score());
-
     let summary = match args.summary {
         Some(s) => s,
         None => {
@@ -91,8 +109,21 @@ pub fn run(args: Args) -> crate::Result<()> {
         }
     };
 
+    let root = qual_file::find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            issuer: args.issuer,
+            ..Default::default()
+        },
+    )?;
+    let kinds = config::kinds(&config)?.clone();
+    check_declared(&kind, &kinds, config.strict_kinds)?;
+    let score = args.score.unwrap_or_else(|| kinds.default_score(&kind));
+
     let issuer = normalize_issuer_uri(
-        args.issuer
+        config
+            .issuer
             .or_else(detect_issuer)
             .unwrap_or_else(|| "mailto:unknown@localhost".into()),
     );
@@ -107,6 +138,69 @@ pub fn run(args: Args) -> crate::Result<()> {
         None => None,
     };
 
+    // A symbol must resolve now; its current range becomes the stored
+    // span unless one was given explicitly.
+    let span = match &args.symbol {
+        Some(symbol) => {
+            let content_root = root.as_deref().unwrap_or(Path::new("."));
+            let live = crate::symbol::resolve_file(&content_root.join(&subject), symbol)?
+                .ok_or_else(|| {
+                    crate::Error::Validation(format!("symbol '{symbol}' not found in '{subject}'"))
+                })?;
+            span.or(Some(live))
+        }
+        None => span,
+    };
+
+    let fingerprint = match (&span, args.fingerprint) {
+        (Some(span), true) => {
+            let content_root = root.as_deref().unwrap_or(Path::new("."));
+            let content = std::fs::read_to_string(content_root.join(&subject)).map_err(|e| {
+                crate::Error::Validation(format!(
+                    "--fingerprint requires '{}' to be a readable file under {}: {e}",
+                    subject,
+                    content_root.display()
+                ))
+            })?;
+            Some(pin::fingerprint(&content, span).ok_or_else(|| {
+                crate::Error::Validation(format!(
+                    "span {} runs past the end of '{}'",
+                    output::format_span(span),
+                    subject
+                ))
+            })?)
+        }
+        (None, true) => {
+            return Err(crate::Error::Validation(
+                "--fingerprint requires --span or --symbol".into(),
+            ));
+        }
+        (_, false) => None,
+    };
+
+    let expires_at = match &args.expires_at {
+        Some(s) => Some(
+            config::parse_timestamp(s)
+                .map_err(|e| crate::Error::Validation(format!("invalid --expires-at: {e}")))?,
+        ),
+        None => None,
+    };
+
+    let digest = if args.pin {
+        let content_root = root.as_deref().unwrap_or(Path::new("."));
+        let digest =
+            pin::current_digest(content_root, &subject, span.as_ref()).ok_or_else(|| {
+                crate::Error::Validation(format!(
+                    "--pin requires '{}' to be a readable file under {}",
+                    subject,
+                    content_root.display()
+                ))
+            })?;
+        Some(digest)
+    } else {
+        None
+    };
+
     let qual_path = qual_file::resolve_qual_path(&subject, args.file.as_deref().map(Path::new))?;
 
     let att = attestation::finalize(Attestation {
@@ -119,6 +213,9 @@ pub fn run(args: Args) -> crate::Result<()> {
         id: String::new(),
         body: AttestationBody {
             detail: args.detail,
+            digest,
+            expires_at,
+            fingerprint,
             kind,
             r#ref: args.r#ref,
             score,
@@ -126,31 +223,27 @@ pub fn run(args: Args) -> crate::Result<()> {
             suggested_fix: args.suggested_fix,
             summary,
             supersedes: args.supersedes,
+            symbol: args.symbol,
             tags: args.tags,
         },
     });
 
-    let errors = attestation::validate(&att);
+    let errors = attestation::validate_with(&att, &kinds);
     if !errors.is_empty() {
         return Err(crate::Error::Validation(errors.join("; ")));
     }
 
+    // Hold the lock from validation through the append, so a concurrent
+    // compaction cannot drop the superseded record in between.
+    let mut lock = qual_file::lock(&qual_path)?;
     if att.body.supersedes.is_some() {
-        let existing = if qual_path.exists() {
-            qual_file::parse(&qual_path)?.records
-        } else {
-            Vec::new()
-        };
-        let mut all = existing;
+        let mut all = lock.parse()?.records;
         all.push(Record::Attestation(Box::new(att.clone())));
         attestation::check_supersession_cycles(&all)?;
         attestation::validate_supersession_targets(&all)?;
     }
 
-    qual_file::append(
-        qual_path.as_ref(),
-        &Record::Attestation(Box::new(att.clone())),
-    )?;
+    lock.append(&Record::Attestation(Box::new(att.clone())))?;
     println!(
         "Attested {} [{}] {}",
         att.subject, att.body.score, att.body.kind
@@ -161,6 +254,9 @@ pub fn run(args: Args) -> crate::Result<()> {
 }
 
 fn run_batch() -> crate::Result<()> {
+    let root = qual_file::find_project_root(Path::new("."));
+    let config = config::load(root.as_deref(), &config::Overrides::default())?;
+    let kinds = config::kinds(&config)?;
     let stdin = io::stdin();
     let mut count = 0;
 
@@ -176,27 +272,29 @@ fn run_batch() -> crate::Result<()> {
 
         // Validate attestation records
         if let Some(att) = record.as_attestation() {
-            let errors = attestation::validate(att);
+            check_declared(&att.body.kind, kinds, config.strict_kinds)?;
+            let errors = attestation::validate_with(att, kinds);
             if !errors.is_empty() {
                 return Err(crate::Error::Validation(errors.join("; ")));
             }
         }
 
         let qual_path = qual_file::resolve_qual_path(record.subject(), None)?;
+        let mut lock = qual_file::lock(&qual_path)?;
 
         if record.supersedes().is_some() {
-            let existing = if qual_path.exists() {
-                qual_file::parse(&qual_path)?.records
-            } else {
-                Vec::new()
-            };
-            let mut all = existing;
+            let mut all = lock.parse()?.records;
             all.push(record.clone());
             attestation::check_supersession_cycles(&all)?;
             attestation::validate_supersession_targets(&all)?;
         }
+        if record.as_revocation().is_some() {
+            let mut all = lock.parse()?.records;
+            all.push(record.clone());
+            attestation::validate_revocation_targets(&all)?;
+        }
 
-        qual_file::append(&qual_path, &record)?;
+        lock.append(&record)?;
         count += 1;
     }
 
@@ -204,7 +302,23 @@ fn run_batch() -> crate::Result<()> {
     Ok(())
 }
 
-fn detect_issuer() -> Option<String> {
+/// Warn about a kind that is neither built in nor declared in `[kinds]`, or
+/// reject it under `strict_kinds`. Nothing is reported while no kinds are
+/// declared and strict mode is off.
+fn check_declared(kind: &Kind, kinds: &KindRegistry, strict: bool) -> crate::Result<()> {
+    if kinds.is_known(kind) || (kinds.0.is_empty() && !strict) {
+        return Ok(());
+    }
+    if strict {
+        return Err(crate::Error::Validation(format!(
+            "kind '{kind}' is not declared in [kinds] (strict_kinds is on)"
+        )));
+    }
+    eprintln!("warning: kind '{kind}' is not declared in [kinds]");
+    Ok(())
+}
+
+pub(crate) fn detect_issuer() -> Option<String> {
     // Try git first
     std::process::Command::new("git")
         .args(["config", "user.email"])
@@ -234,7 +348,7 @@ fn detect_issuer() -> Option<String> {
 
 /// Normalize an issuer value to a URI. Bare emails get `mailto:` prefix;
 /// values already containing `:` are assumed to be valid URIs.
-fn normalize_issuer_uri(issuer: String) -> String {
+pub(crate) fn normalize_issuer_uri(issuer: String) -> String {
     if issuer.contains(':') {
         issuer
     } else {
@@ -1,19 +1,42 @@
 use clap::Args as ClapArgs;
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::attestation::Record;
+use crate::cli::{config, output};
+use crate::policy;
 use crate::qual_file::{self, find_project_root};
 use crate::scoring;
 
+/// Subjects below a threshold, with their reports.
+type Failures = Vec<(String, scoring::ScoreReport)>;
+
 #[derive(ClapArgs)]
 pub struct Args {
     /// Minimum acceptable effective score (default: 0)
-    #[arg(long, default_value = "0", allow_hyphen_values = true)]
-    pub min_score: i32,
+    #[arg(long, allow_hyphen_values = true)]
+    pub min_score: Option<i32>,
 
     /// Path to the dependency graph file
     #[arg(long)]
     pub graph: Option<String>,
 
+    /// Minimum effective score in a dimension, as TAG=MIN (repeatable)
+    #[arg(long = "dimension", value_name = "TAG=MIN", allow_hyphen_values = true)]
+    pub dimensions: Vec<String>,
+
+    /// Fail if any active attestation has passed its expires_at
+    #[arg(long)]
+    pub fail_on_expired: bool,
+
+    /// Evaluate scores at this RFC 3339 timestamp or date instead of now
+    #[arg(long)]
+    pub at: Option<String>,
+
+    /// Skip malformed .qual lines with a warning instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+
     /// Disable .gitignore and .qualignore filtering
     #[arg(long)]
     pub no_ignore: bool,
@@ -21,38 +44,184 @@ pub struct Args {
 
 pub fn run(args: Args) -> crate::Result<()> {
     let root = find_project_root(Path::new("."));
-    let graph = crate::cli::config::load_graph(args.graph.as_deref(), root.as_deref());
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            graph: args.graph.clone(),
+            min_score: args.min_score,
+            ..Default::default()
+        },
+    )?;
     let discover_root = root.as_deref().unwrap_or(Path::new("."));
-    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+    // Parse every file before failing, so one run reports every bad line.
+    let (qual_files, diagnostics) = qual_file::discover_lenient(discover_root, !args.no_ignore)?;
+    if args.lenient {
+        for diagnostic in &diagnostics {
+            eprintln!("warning: {diagnostic}");
+        }
+    } else if !diagnostics.is_empty() {
+        for diagnostic in &diagnostics {
+            eprintln!("MALFORMED: {diagnostic}");
+        }
+        return Err(crate::Error::CheckFailed(format!(
+            "{} malformed line(s) in .qual files",
+            diagnostics.len()
+        )));
+    }
+    let graph = config::load_graph(&config.graph, root.as_deref(), &qual_files)?;
 
-    let scores = scoring::effective_scores(&graph, &qual_files);
+    let eval = config::evaluation(&config, args.at.as_deref())?.with_staleness(config::staleness(
+        &config,
+        discover_root,
+        &qual_files,
+    )?);
+    let scores = scoring::effective_scores(&graph, &qual_files, &eval);
+    // Policy rules also cover files nobody has attested yet.
+    let files = if policy::active_policies(&qual_files).is_empty() {
+        Vec::new()
+    } else {
+        let managed = config::managed_paths(&config, discover_root);
+        qual_file::discover_subjects(discover_root, !args.no_ignore, &managed)?
+    };
+    let violations = policy::evaluate(&qual_files, &files, &scores, &eval)?;
+    let all_records: Vec<Record> = qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter().cloned())
+        .collect();
+    let expired = if args.fail_on_expired {
+        scoring::expiring(&all_records, eval.at)
+    } else {
+        Vec::new()
+    };
 
-    let mut failures: Vec<(String, scoring::ScoreReport)> = scores
+    let mut failures: Failures = scores
         .into_iter()
-        .filter(|(_, report)| report.effective < args.min_score)
+        .filter(|(_, report)| report.effective < config.min_score)
         .collect();
 
     failures.sort_by_key(|(_, r)| r.effective);
 
-    if failures.is_empty() {
-        println!("All artifacts meet minimum score of {}", args.min_score);
-        Ok(())
-    } else {
-        for (artifact, report) in &failures {
+    let thresholds = dimension_thresholds(&config, &args.dimensions)?;
+    let mut dimension_failures: Vec<(&str, i32, Failures)> = Vec::new();
+    for (dimension, &min_score) in &thresholds {
+        let eval = eval.clone().with_dimension(dimension.as_str());
+        let mut below: Failures = scoring::dimension_scores(&graph, &qual_files, &eval)
+            .into_iter()
+            .filter(|(_, report)| report.effective < min_score)
+            .collect();
+        below.sort_by(|(a, ra), (b, rb)| ra.effective.cmp(&rb.effective).then(a.cmp(b)));
+        if !below.is_empty() {
+            dimension_failures.push((dimension, min_score, below));
+        }
+    }
+
+    if failures.is_empty()
+        && dimension_failures.is_empty()
+        && violations.is_empty()
+        && expired.is_empty()
+    {
+        let dimensions: Vec<String> = thresholds
+            .iter()
+            .map(|(dimension, min)| format!("{dimension} >= {min}"))
+            .collect();
+        if dimensions.is_empty() {
+            println!("All artifacts meet minimum score of {}", config.min_score);
+        } else {
+            println!(
+                "All artifacts meet minimum score of {} ({})",
+                config.min_score,
+                dimensions.join(", ")
+            );
+        }
+        return Ok(());
+    }
+
+    for (artifact, report) in &failures {
+        let detail = if let Some(ref path) = report.limiting_path {
+            format!(" (limited by {})", path.join(" -> "))
+        } else {
+            String::new()
+        };
+        eprintln!(
+            "FAIL: {} effective={} raw={}{}",
+            artifact, report.effective, report.raw, detail
+        );
+    }
+    for (dimension, _, below) in &dimension_failures {
+        for (artifact, report) in below {
             let detail = if let Some(ref path) = report.limiting_path {
                 format!(" (limited by {})", path.join(" -> "))
             } else {
                 String::new()
             };
             eprintln!(
-                "FAIL: {} effective={} raw={}{}",
-                artifact, report.effective, report.raw, detail
+                "FAIL: {} [{}] effective={} raw={}{}",
+                artifact, dimension, report.effective, report.raw, detail
             );
         }
-        Err(crate::Error::CheckFailed(format!(
+    }
+    for violation in &violations {
+        eprintln!(
+            "POLICY: {} [policy {}]",
+            violation,
+            &violation.policy_id[..violation.policy_id.len().min(8)]
+        );
+    }
+    for record in &expired {
+        eprintln!(
+            "EXPIRED:{} [{}]",
+            output::format_expiry(record, eval.at),
+            &record.id()[..record.id().len().min(8)]
+        );
+    }
+
+    let mut summary = Vec::new();
+    if !failures.is_empty() {
+        summary.push(format!(
             "{} artifact(s) below minimum score of {}",
             failures.len(),
-            args.min_score
-        )))
+            config.min_score
+        ));
+    }
+    for (dimension, min_score, below) in &dimension_failures {
+        summary.push(format!(
+            "{} artifact(s) below minimum {} score of {}",
+            below.len(),
+            dimension,
+            min_score
+        ));
+    }
+    if !violations.is_empty() {
+        summary.push(format!("{} policy violation(s)", violations.len()));
+    }
+    if !expired.is_empty() {
+        summary.push(format!("{} expired attestation(s)", expired.len()));
+    }
+    Err(crate::Error::CheckFailed(summary.join("; ")))
+}
+
+/// Per-dimension minimum scores from `[dimensions.<tag>] min_score`, with
+/// `--dimension TAG=MIN` flags taking precedence.
+fn dimension_thresholds(
+    config: &config::Config,
+    flags: &[String],
+) -> crate::Result<BTreeMap<String, i32>> {
+    let mut thresholds: BTreeMap<String, i32> = config
+        .dimensions
+        .iter()
+        .filter_map(|(dimension, settings)| Some((dimension.clone(), settings.min_score?)))
+        .collect();
+    for flag in flags {
+        let parsed = flag
+            .split_once('=')
+            .and_then(|(dimension, min)| Some((dimension.trim(), min.trim().parse().ok()?)))
+            .filter(|(dimension, _)| !dimension.is_empty());
+        let Some((dimension, min)) = parsed else {
+            return Err(crate::Error::Validation(format!(
+                "invalid --dimension '{flag}' (expected TAG=MIN, e.g. security=50)"
+            )));
+        };
+        thresholds.insert(dimension.to_string(), min);
     }
+    Ok(thresholds)
 }
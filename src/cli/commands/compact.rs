@@ -1,6 +1,7 @@
 use clap::Args as ClapArgs;
 use std::path::Path;
 
+use crate::cli::config;
 use crate::compact as compact_lib;
 use crate::qual_file::{self, find_project_root};
 use crate::scoring;
@@ -22,14 +23,21 @@ pub struct Args {
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Keep the pre-compaction file as <file>.bak
+    #[arg(long)]
+    pub backup: bool,
+
     /// Disable .gitignore and .qualignore filtering
     #[arg(long)]
     pub no_ignore: bool,
 }
 
 pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(root.as_deref(), &config::Overrides::default())?;
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
     if args.all {
-        return run_all(&args);
+        return run_all(&args, &config, discover_root);
     }
 
     let artifact = args
@@ -43,15 +51,12 @@ pub fn run(args: Args) -> crate::Result<()> {
         ))
     })?;
 
-    let qf = qual_file::parse(&qual_path)?;
-    compact_one(&qf, args.snapshot, args.dry_run)?;
+    compact_one(&qual_path, &args, &config, discover_root)?;
 
     Ok(())
 }
 
-fn run_all(args: &Args) -> crate::Result<()> {
-    let root = find_project_root(Path::new("."));
-    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+fn run_all(args: &Args, config: &config::Config, discover_root: &Path) -> crate::Result<()> {
     let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
 
     if qual_files.is_empty() {
@@ -60,31 +65,40 @@ fn run_all(args: &Args) -> crate::Result<()> {
     }
 
     for qf in &qual_files {
-        compact_one(qf, args.snapshot, args.dry_run)?;
+        compact_one(&qf.path, args, config, discover_root)?;
     }
 
     Ok(())
 }
 
-fn compact_one(qf: &qual_file::QualFile, snapshot: bool, dry_run: bool) -> crate::Result<()> {
-    let score_before = scoring::raw_score(&qf.records);
-
-    let (compacted, result) = if snapshot {
-        compact_lib::snapshot(qf)
+fn compact_one(
+    path: &Path,
+    args: &Args,
+    config: &config::Config,
+    root: &Path,
+) -> crate::Result<()> {
+    // Hold the lock from parse to rewrite, so a concurrent append is either
+    // compacted with everything else or waits for the rewrite to finish.
+    let lock = qual_file::lock(path)?;
+    let qf = &lock.parse()?;
+
+    // Compaction preserves scores as configured, so decay, trust, and
+    // staleness apply before and after, as they do in `score`.
+    let eval = config::evaluation(config, None)?.with_staleness(config::staleness(
+        config,
+        root,
+        std::slice::from_ref(qf),
+    )?);
+    let score_before = scoring::raw_score(&qf.records, &eval);
+
+    let (compacted, result) = if args.snapshot {
+        compact_lib::snapshot(qf, &eval)
     } else {
         compact_lib::prune(qf)
     };
 
     // Verify the invariant
-    let score_after = scoring::raw_score(&compacted.records);
-    if score_before != score_after {
-        return Err(crate::Error::Validation(format!(
-            "BUG: compaction changed raw score from {} to {} for {}",
-            score_before,
-            score_after,
-            qf.path.display()
-        )));
-    }
+    let score_after = verify_raw_score(score_before, &compacted, &eval)?;
 
     if result.pruned == 0 {
         println!(
@@ -95,7 +109,7 @@ fn compact_one(qf: &qual_file::QualFile, snapshot: bool, dry_run: bool) -> crate
         return Ok(());
     }
 
-    if snapshot {
+    if args.snapshot {
         println!(
             "  {}: {} -> {} record (epoch, raw score: {})",
             qf.path.display(),
@@ -105,7 +119,7 @@ fn compact_one(qf: &qual_file::QualFile, snapshot: bool, dry_run: bool) -> crate
         );
     } else {
         println!(
-            "  {}: {} -> {} records ({} superseded, pruned)",
+            "  {}: {} -> {} records ({} superseded or revoked, pruned)",
             qf.path.display(),
             result.before,
             result.after,
@@ -113,11 +127,38 @@ fn compact_one(qf: &qual_file::QualFile, snapshot: bool, dry_run: bool) -> crate
         );
     }
 
-    if !dry_run {
-        qual_file::write_all(&qf.path, &compacted.records)?;
+    if !args.dry_run {
+        // Check the file as written too, before it replaces the original.
+        lock.replace(&compacted.records, args.backup, |written| {
+            verify_raw_score(score_before, written, &eval).map(|_| ())
+        })?;
+        if args.backup {
+            println!(
+                "  (previous contents kept in {})",
+                qual_file::backup_path(path).display()
+            );
+        }
     } else {
         println!("  (dry run — no changes written)");
     }
 
     Ok(())
 }
+
+/// Check that `compacted` has the raw score `before`, returning it.
+fn verify_raw_score(
+    before: i32,
+    compacted: &qual_file::QualFile,
+    eval: &scoring::Evaluation,
+) -> crate::Result<i32> {
+    let after = scoring::raw_score(&compacted.records, eval);
+    if before != after {
+        return Err(crate::Error::Validation(format!(
+            "BUG: compaction changed raw score from {} to {} for {}",
+            before,
+            after,
+            compacted.path.display()
+        )));
+    }
+    Ok(after)
+}
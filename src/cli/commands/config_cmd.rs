@@ -0,0 +1,97 @@
+use clap::{Args as ClapArgs, Subcommand};
+use std::path::Path;
+
+use crate::cli::config;
+use crate::qual_file::find_project_root;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print each effective setting and the layer it came from
+    Show(ShowArgs),
+}
+
+#[derive(ClapArgs)]
+pub struct ShowArgs {
+    /// Output format (human, json)
+    #[arg(long)]
+    pub format: Option<String>,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    match args.command {
+        ConfigCommand::Show(args) => run_show(args),
+    }
+}
+
+fn run_show(args: ShowArgs) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let overrides = config::Overrides {
+        format: args.format,
+        ..Default::default()
+    };
+    let config = config::load(root.as_deref(), &overrides)?;
+    let sources = config::sources(root.as_deref(), &overrides);
+
+    let values: Vec<(&str, serde_json::Value, config::Layer)> = sources
+        .into_iter()
+        .map(|(key, layer)| {
+            let value = match key {
+                "graph" => serde_json::json!(config.graph.display().to_string()),
+                "issuer" => serde_json::json!(config.issuer),
+                "format" => serde_json::json!(config.format),
+                "min_score" => serde_json::json!(config.min_score),
+                "keyring" => serde_json::json!(config.keyring.display().to_string()),
+                "signing_key" => {
+                    serde_json::json!(config.signing_key.as_ref().map(|p| p.display().to_string()))
+                }
+                "decay" => serde_json::to_value(&config.decay).unwrap_or_default(),
+                "sarif.levels.error" => serde_json::json!(config.sarif.levels.error),
+                "sarif.levels.warning" => serde_json::json!(config.sarif.levels.warning),
+                "sarif.levels.note" => serde_json::json!(config.sarif.levels.note),
+                "sarif.levels.none" => serde_json::json!(config.sarif.levels.none),
+                "stale.weight" => serde_json::json!(config.stale.weight),
+                "trust" => serde_json::to_value(&config.trust).unwrap_or_default(),
+                "kinds" => serde_json::to_value(&config.kinds).unwrap_or_default(),
+                "strict_kinds" => serde_json::json!(config.strict_kinds),
+                "dimensions" => serde_json::to_value(&config.dimensions).unwrap_or_default(),
+                _ => serde_json::Value::Null,
+            };
+            (key, value, layer)
+        })
+        .collect();
+
+    if config.format == "json" {
+        let entries: Vec<serde_json::Value> = values
+            .iter()
+            .map(|(key, value, layer)| {
+                serde_json::json!({
+                    "key": key,
+                    "value": value,
+                    "source": layer.to_string(),
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    for (key, value, layer) in &values {
+        let shown = match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Null => "(unset)".into(),
+            other => other.to_string(),
+        };
+        println!("  {:<20} {:<30} {}", key, shown, layer);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,332 @@
+use chrono::Utc;
+use clap::{Args as ClapArgs, Subcommand};
+use std::path::Path;
+
+use crate::attestation::{
+    self, DependencyBody, DependencyRecord, Record, RevocationBody, RevocationRecord,
+};
+use crate::cli::commands::attest::{detect_issuer, normalize_issuer_uri};
+use crate::cli::config;
+use crate::qual_file::{self, find_project_root};
+use crate::scoring;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: DepCommand,
+}
+
+#[derive(Subcommand)]
+pub enum DepCommand {
+    /// Declare that a subject depends on other subjects
+    Add(AddArgs),
+    /// Remove dependency edges declared in .qual files
+    Rm(RmArgs),
+    /// List dependency records
+    Ls(LsArgs),
+}
+
+#[derive(ClapArgs)]
+pub struct AddArgs {
+    /// The dependent subject
+    pub subject: String,
+
+    /// Subject this one depends on (repeatable)
+    #[arg(long = "on", required = true)]
+    pub on: Vec<String>,
+
+    /// Issuer identity URI (defaults to VCS user email with mailto:)
+    #[arg(long)]
+    pub issuer: Option<String>,
+
+    /// Explicit .qual file to write to (overrides layout resolution)
+    #[arg(long)]
+    pub file: Option<String>,
+
+    /// Path to the dependency graph file
+    #[arg(long)]
+    pub graph: Option<String>,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+#[derive(ClapArgs)]
+pub struct RmArgs {
+    /// The dependent subject
+    pub subject: String,
+
+    /// Dependency to remove (repeatable; all of the subject's edges if omitted)
+    #[arg(long = "on")]
+    pub on: Vec<String>,
+
+    /// Issuer identity URI (defaults to VCS user email with mailto:)
+    #[arg(long)]
+    pub issuer: Option<String>,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+#[derive(ClapArgs)]
+pub struct LsArgs {
+    /// Only list records for this subject
+    pub subject: Option<String>,
+
+    /// Output format (human, json)
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    match args.command {
+        DepCommand::Add(args) => run_add(args),
+        DepCommand::Rm(args) => run_rm(args),
+        DepCommand::Ls(args) => run_ls(args),
+    }
+}
+
+fn run_add(args: AddArgs) -> crate::Result<()> {
+    if args.subject.is_empty() {
+        return Err(crate::Error::Validation("subject must not be empty".into()));
+    }
+
+    let root = find_project_root(Path::new("."));
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            graph: args.graph.clone(),
+            issuer: args.issuer.clone(),
+            ..Default::default()
+        },
+    )?;
+    let issuer = normalize_issuer_uri(
+        config
+            .issuer
+            .or_else(detect_issuer)
+            .unwrap_or_else(|| "mailto:unknown@localhost".into()),
+    );
+
+    let record = attestation::finalize_record(Record::Dependency(DependencyRecord {
+        metabox: "1".into(),
+        record_type: "dependency".into(),
+        subject: args.subject.clone(),
+        issuer,
+        issuer_type: None,
+        created_at: Utc::now(),
+        id: String::new(),
+        body: DependencyBody {
+            depends_on: args.on,
+        },
+    }));
+
+    // Refuse edges that would close a cycle in the merged graph.
+    let mut g = config::load_graph(&config.graph, root.as_deref(), &qual_files)?;
+    g.add_dependency_records([&record]);
+    g.toposort()?;
+
+    let qual_path =
+        qual_file::resolve_qual_path(&args.subject, args.file.as_deref().map(Path::new))?;
+    qual_file::append(&qual_path, &record)?;
+
+    if let Record::Dependency(ref dep) = record {
+        println!(
+            "Added {} -> {}",
+            dep.subject,
+            dep.body.depends_on.join(", ")
+        );
+        println!("  id: {}", dep.id);
+    }
+
+    Ok(())
+}
+
+fn run_rm(args: RmArgs) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            issuer: args.issuer.clone(),
+            ..Default::default()
+        },
+    )?;
+    let issuer = normalize_issuer_uri(
+        config
+            .issuer
+            .or_else(detect_issuer)
+            .unwrap_or_else(|| "mailto:unknown@localhost".into()),
+    );
+    let now = Utc::now();
+
+    // Records are append-only: revoke each record that loses an edge and
+    // re-declare the edges it keeps, rather than rewriting it in place.
+    let mut removed = 0;
+    for qf in &qual_files {
+        // Re-read under the lock so the revoked records cannot be compacted
+        // away before the revocation lands next to them.
+        let mut lock = qual_file::lock(&qf.path)?;
+        let mut all: Vec<Record> = qual_files
+            .iter()
+            .filter(|other| other.path != qf.path)
+            .flat_map(|other| other.records.iter().cloned())
+            .collect();
+        let here = all.len();
+        all.extend(lock.parse()?.records);
+        let inactive = scoring::inactive_ids(&all);
+
+        let mut revokes = Vec::new();
+        let mut dropped: Vec<String> = Vec::new();
+        let mut kept: Vec<String> = Vec::new();
+        for dep in all[here..].iter().filter_map(|r| match r {
+            Record::Dependency(dep)
+                if dep.subject == args.subject && !inactive.contains(dep.id.as_str()) =>
+            {
+                Some(dep)
+            }
+            _ => None,
+        }) {
+            let (gone, rest): (Vec<&String>, Vec<&String>) = dep
+                .body
+                .depends_on
+                .iter()
+                .partition(|target| args.on.is_empty() || args.on.contains(target));
+            if gone.is_empty() {
+                continue;
+            }
+            removed += gone.len();
+            revokes.push(dep.id.clone());
+            for target in gone {
+                if !dropped.contains(target) {
+                    dropped.push(target.clone());
+                }
+            }
+            for target in rest {
+                if !kept.contains(target) {
+                    kept.push(target.clone());
+                }
+            }
+        }
+        if revokes.is_empty() {
+            continue;
+        }
+
+        let revocation = attestation::finalize_record(Record::Revocation(RevocationRecord {
+            metabox: "1".into(),
+            record_type: "revocation".into(),
+            subject: args.subject.clone(),
+            issuer: issuer.clone(),
+            issuer_type: None,
+            created_at: now,
+            id: String::new(),
+            body: RevocationBody {
+                revokes,
+                summary: format!("Removed dependency on {}", dropped.join(", ")),
+            },
+        }));
+        all.push(revocation.clone());
+        attestation::validate_revocation_targets(&all)?;
+
+        // Declare the kept edges before revoking the old ones, so an
+        // interruption leaves extra edges rather than missing ones.
+        if !kept.is_empty() {
+            let replacement = attestation::finalize_record(Record::Dependency(DependencyRecord {
+                metabox: "1".into(),
+                record_type: "dependency".into(),
+                subject: args.subject.clone(),
+                issuer: issuer.clone(),
+                issuer_type: None,
+                created_at: now,
+                id: String::new(),
+                body: DependencyBody { depends_on: kept },
+            }));
+            lock.append(&replacement)?;
+        }
+        lock.append(&revocation)?;
+        println!("  {}: updated", qf.path.display());
+    }
+
+    if removed == 0 {
+        return Err(crate::Error::Validation(format!(
+            "No matching dependency records found for '{}'",
+            args.subject
+        )));
+    }
+
+    println!("Removed {removed} edge(s) from {}", args.subject);
+    Ok(())
+}
+
+fn run_ls(args: LsArgs) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            format: args.format.clone(),
+            ..Default::default()
+        },
+    )?;
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let inactive = scoring::inactive_ids(qual_files.iter().flat_map(|qf| &qf.records));
+    let deps: Vec<(&Path, &DependencyRecord)> = qual_files
+        .iter()
+        .flat_map(|qf| {
+            qf.records.iter().filter_map(|r| match r {
+                Record::Dependency(dep) if !inactive.contains(dep.id.as_str()) => {
+                    Some((qf.path.as_path(), dep))
+                }
+                _ => None,
+            })
+        })
+        .filter(|(_, dep)| args.subject.as_ref().is_none_or(|s| *s == dep.subject))
+        .collect();
+
+    if config.format == "json" {
+        let entries: Vec<serde_json::Value> = deps
+            .iter()
+            .map(|(path, dep)| {
+                serde_json::json!({
+                    "file": path.display().to_string(),
+                    "id": dep.id,
+                    "subject": dep.subject,
+                    "depends_on": dep.body.depends_on,
+                    "issuer": dep.issuer,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    if deps.is_empty() {
+        println!("No dependency records found.");
+        return Ok(());
+    }
+
+    for (path, dep) in &deps {
+        println!(
+            "  {} -> {}  ({})",
+            dep.subject,
+            dep.body.depends_on.join(", "),
+            path.display()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use clap::Args as ClapArgs;
+use std::io::Write;
+use std::path::Path;
+
+use crate::attestation::Record;
+use crate::cli::config;
+use crate::intoto;
+use crate::qual_file::{self, find_project_root};
+use crate::sarif;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Artifacts whose records to export (default: all)
+    pub artifacts: Vec<String>,
+
+    /// Export format (in-toto, sarif)
+    #[arg(long)]
+    pub format: String,
+
+    /// Write to a file instead of stdout
+    #[arg(long, short)]
+    pub output: Option<String>,
+
+    /// Skip malformed .qual lines with a warning instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = config::discover(discover_root, !args.no_ignore, args.lenient)?;
+
+    let (lines, what) = match args.format.as_str() {
+        "in-toto" => {
+            let lines = export_in_toto(&args, discover_root, &qual_files)?;
+            let what = format!("{} statement(s)", lines.len());
+            (lines, what)
+        }
+        "sarif" => {
+            let config = config::load(root.as_deref(), &config::Overrides::default())?;
+            let log =
+                sarif::from_records(&selected_records(&args, &qual_files), &config.sarif.levels);
+            let results: usize = log.runs.iter().map(|r| r.results.len()).sum();
+            let what = format!("{} result(s) in {} run(s)", results, log.runs.len());
+            (vec![serde_json::to_string_pretty(&log)?], what)
+        }
+        other => {
+            return Err(crate::Error::Validation(format!(
+                "unknown export format: '{other}' (expected in-toto or sarif)"
+            )));
+        }
+    };
+
+    let mut out: Box<dyn Write> = match args.output {
+        Some(ref path) => Box::new(std::fs::File::create(path)?),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    for line in &lines {
+        writeln!(out, "{line}")?;
+    }
+
+    if let Some(ref path) = args.output {
+        eprintln!("Exported {what} to {path}");
+    }
+    Ok(())
+}
+
+/// All records, or only those for the requested artifacts.
+fn selected_records(args: &Args, qual_files: &[qual_file::QualFile]) -> Vec<Record> {
+    qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter())
+        .filter(|r| args.artifacts.is_empty() || args.artifacts.iter().any(|a| a == r.subject()))
+        .cloned()
+        .collect()
+}
+
+/// One in-toto Statement per record, as JSONL. Each subject's digest is the
+/// BLAKE3 hash of the artifact file under the project root; records whose
+/// subject is not a regular file have no digest and are skipped with a
+/// warning.
+fn export_in_toto(
+    args: &Args,
+    root: &Path,
+    qual_files: &[qual_file::QualFile],
+) -> crate::Result<Vec<String>> {
+    let mut lines = Vec::new();
+    let mut skipped = 0;
+    for record in &selected_records(args, qual_files) {
+        if intoto::predicate_type(record).is_none() {
+            continue;
+        }
+        let Some(digest) = intoto::artifact_digest(&root.join(record.subject())) else {
+            eprintln!(
+                "warning: skipping {} {}: not a regular file, so it has no digest",
+                &record.id()[..record.id().len().min(8)],
+                record.subject()
+            );
+            skipped += 1;
+            continue;
+        };
+        if let Some(statement) = intoto::to_statement(record, digest) {
+            lines.push(serde_json::to_string(&statement)?);
+        }
+    }
+    if skipped > 0 {
+        eprintln!("warning: skipped {skipped} record(s) without a subject digest");
+    }
+    Ok(lines)
+}
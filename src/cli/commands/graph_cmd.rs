@@ -1,6 +1,7 @@
 use clap::Args as ClapArgs;
 use std::path::Path;
 
+use crate::cli::config;
 use crate::graph;
 use crate::qual_file::find_project_root;
 
@@ -13,26 +14,42 @@ pub struct Args {
     /// Path to the dependency graph file
     #[arg(long)]
     pub graph: Option<String>,
+
+    /// Skip malformed .qual lines with a warning instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
 }
 
 pub fn run(args: Args) -> crate::Result<()> {
-    let graph_path = if let Some(ref path) = args.graph {
-        std::path::PathBuf::from(path)
-    } else {
-        let root =
-            find_project_root(Path::new(".")).unwrap_or_else(|| std::path::PathBuf::from("."));
-        root.join("qualifier.graph.jsonl")
-    };
-
-    if !graph_path.exists() {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            graph: args.graph.clone(),
+            ..Default::default()
+        },
+    )?;
+    let graph_path = root
+        .as_deref()
+        .unwrap_or(Path::new("."))
+        .join(&config.graph);
+
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = config::discover(discover_root, !args.no_ignore, args.lenient)?;
+    let g = config::load_graph(&config.graph, root.as_deref(), &qual_files)?;
+
+    if g.is_empty() && !graph_path.exists() {
         return Err(crate::Error::Validation(format!(
-            "Graph file not found: {} (run `qualifier init` first)",
+            "Graph file not found: {} and no dependency records in .qual files \
+             (run `qualifier init` first)",
             graph_path.display()
         )));
     }
 
-    let g = graph::load(&graph_path)?;
-
     match args.format.as_str() {
         "dot" => print!("{}", g.to_dot()),
         "json" => print!("{}", graph::to_jsonl(&g)),
@@ -0,0 +1,164 @@
+use clap::Args as ClapArgs;
+use std::collections::{BTreeMap, HashSet};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use crate::attestation::{self, Record};
+use crate::cli::config;
+use crate::intoto;
+use crate::qual_file::{self, QualFile, find_project_root};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// JSONL file of in-toto Statements or DSSE envelopes (`-` for stdin)
+    pub file: String,
+
+    /// Import format (in-toto)
+    #[arg(long, default_value = "in-toto")]
+    pub format: String,
+
+    /// Report what would be imported without writing
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    if args.format != "in-toto" {
+        return Err(crate::Error::Validation(format!(
+            "unknown import format: '{}' (expected in-toto)",
+            args.format
+        )));
+    }
+
+    let content = if args.file == "-" {
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        std::fs::read_to_string(&args.file)?
+    };
+    let statements = intoto::parse_str(&content)?;
+
+    let root = find_project_root(Path::new("."));
+    let config = config::load(root.as_deref(), &config::Overrides::default())?;
+    let kinds = config::kinds(&config)?;
+
+    // Rebuild and verify every record before writing any of them.
+    let mut records = Vec::new();
+    let mut foreign = 0;
+    for (i, statement) in statements.iter().enumerate() {
+        if !intoto::is_qualifier_predicate(&statement.predicate_type) {
+            foreign += 1;
+            continue;
+        }
+        let record = intoto::from_statement(statement)
+            .and_then(|record| check_subject(record.subject()).map(|()| record))
+            .map_err(|e| crate::Error::Validation(format!("statement {}: {}", i + 1, e)))?;
+        if let Some(att) = record.as_attestation() {
+            let errors = attestation::validate_with(att, kinds);
+            if !errors.is_empty() {
+                return Err(crate::Error::Validation(format!(
+                    "statement {}: {}",
+                    i + 1,
+                    errors.join("; ")
+                )));
+            }
+        }
+        records.push(record);
+    }
+
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+    let mut known: HashSet<String> = qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter().map(|r| r.id().to_string()))
+        .collect();
+
+    let new_records: Vec<Record> = records
+        .into_iter()
+        .filter(|r| known.insert(r.id().to_string()))
+        .collect();
+    let existing = statements.len() - foreign - new_records.len();
+
+    // Refuse dependency edges that would close a cycle in the merged graph.
+    let mut merged = qual_files.clone();
+    merged.push(QualFile {
+        path: PathBuf::from(&args.file),
+        subject: String::new(),
+        records: new_records.clone(),
+    });
+    config::load_graph(&config.graph, root.as_deref(), &merged)?;
+
+    let mut batches: BTreeMap<PathBuf, Vec<Record>> = BTreeMap::new();
+    for record in &new_records {
+        let qual_path = if args.dry_run {
+            qual_file::default_qual_path(record.subject())
+        } else {
+            qual_file::resolve_qual_path(record.subject(), None)?
+        };
+        if args.dry_run {
+            println!(
+                "  would import {} {} -> {}",
+                &record.id()[..8],
+                record.subject(),
+                qual_path.display()
+            );
+        }
+        batches.entry(qual_path).or_default().push(record.clone());
+    }
+
+    if !args.dry_run {
+        // Hold every lock from validation through the appends, and check
+        // the whole batch before writing any of it.
+        let mut locks = qual_file::lock_all(batches.keys().cloned().collect())?;
+        for (path, batch) in &batches {
+            if !batch.iter().any(|r| r.supersedes().is_some()) {
+                continue;
+            }
+            let mut all = locks[path].parse()?.records;
+            all.extend(batch.iter().cloned());
+            attestation::check_supersession_cycles(&all)?;
+            attestation::validate_supersession_targets(&all)?;
+        }
+        for (path, batch) in &batches {
+            let lock = locks.get_mut(path).expect("every batch path is locked");
+            for record in batch {
+                lock.append(record)?;
+            }
+        }
+    }
+
+    let verb = if args.dry_run {
+        "Would import"
+    } else {
+        "Imported"
+    };
+    println!(
+        "{verb} {} record(s) ({} already present, {} non-qualifier statement(s) skipped)",
+        new_records.len(),
+        existing,
+        foreign
+    );
+    Ok(())
+}
+
+/// Reject subjects that would place records outside the project: absolute
+/// paths and paths with `..` components.
+fn check_subject(subject: &str) -> crate::Result<()> {
+    let escapes = Path::new(subject).components().any(|c| {
+        matches!(
+            c,
+            Component::Prefix(_) | Component::RootDir | Component::ParentDir
+        )
+    });
+    if escapes {
+        return Err(crate::Error::Validation(format!(
+            "subject '{subject}' is not a path inside the project"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use chrono::Utc;
+use clap::Args as ClapArgs;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::attestation::{self, Attestation, Record};
+use crate::cli::config;
+use crate::qual_file::{self, find_project_root};
+use crate::sarif;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// SARIF v2.1.0 file to import (`-` for stdin)
+    pub file: String,
+
+    /// Report what would be imported without writing
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(root.as_deref(), &config::Overrides::default())?;
+    let kinds = config::kinds(&config)?;
+
+    let content = if args.file == "-" {
+        let mut buf = String::new();
+        std::io::stdin().read_to_string(&mut buf)?;
+        buf
+    } else {
+        std::fs::read_to_string(&args.file)?
+    };
+    let log = sarif::parse_str(&content)?;
+
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let absolute_root = std::fs::canonicalize(discover_root)?;
+    let now = Utc::now();
+    let (attestations, skipped) =
+        sarif::to_attestations(&log, &config.sarif.levels, &absolute_root, now)?;
+
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+    let discovered: Vec<Record> = qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter().cloned())
+        .collect();
+
+    // Lock every file the import may append to: those of the new findings
+    // and of every finding the same tools reported before. Records are
+    // re-read under the locks, so supersession sees every earlier write.
+    let mut paths = BTreeSet::new();
+    let previous = sarif::resolve_unmatched(&log, &[], &discovered, &absolute_root, now);
+    for subject in attestations.iter().chain(&previous).map(|a| &a.subject) {
+        paths.insert(qual_path(subject, args.dry_run)?);
+    }
+    let locks = if args.dry_run {
+        BTreeMap::new()
+    } else {
+        qual_file::lock_all(paths)?
+    };
+    let mut locked: BTreeMap<PathBuf, Vec<Record>> = BTreeMap::new();
+    for (path, lock) in &locks {
+        locked.insert(std::fs::canonicalize(path)?, lock.parse()?.records);
+    }
+    let mut existing: Vec<Record> = locked.values().flatten().cloned().collect();
+    for qf in &qual_files {
+        if !locked.contains_key(&std::fs::canonicalize(&qf.path)?) {
+            existing.extend(qf.records.iter().cloned());
+        }
+    }
+
+    let attestations = sarif::supersede_previous(attestations, &existing);
+    let resolved = sarif::resolve_unmatched(&log, &attestations, &existing, &absolute_root, now);
+
+    // Validate the whole batch before writing any of it.
+    let mut batches: BTreeMap<PathBuf, Vec<Record>> = BTreeMap::new();
+    for att in attestations.iter().chain(&resolved) {
+        let errors = attestation::validate_with(att, kinds);
+        if !errors.is_empty() {
+            return Err(crate::Error::Validation(format!(
+                "{}: {}",
+                att.subject,
+                errors.join("; ")
+            )));
+        }
+        batches
+            .entry(qual_path(&att.subject, args.dry_run)?)
+            .or_default()
+            .push(Record::Attestation(Box::new(att.clone())));
+    }
+    if !args.dry_run {
+        for (path, batch) in &batches {
+            let mut all = locked
+                .get(&std::fs::canonicalize(path)?)
+                .cloned()
+                .unwrap_or_default();
+            all.extend(batch.iter().cloned());
+            attestation::check_supersession_cycles(&all)?;
+            attestation::validate_supersession_targets(&all)?;
+        }
+    }
+
+    let superseding = attestations
+        .iter()
+        .filter(|a| a.body.supersedes.is_some())
+        .count();
+    if args.dry_run {
+        for att in attestations.iter().chain(&resolved) {
+            print_would_import(att);
+        }
+    } else {
+        let mut locks = locks;
+        for (path, batch) in &batches {
+            let lock = locks.get_mut(path).ok_or_else(|| {
+                crate::Error::Validation(format!("BUG: {} was not locked", path.display()))
+            })?;
+            for record in batch {
+                lock.append(record)?;
+            }
+        }
+    }
+
+    let verb = if args.dry_run {
+        "Would import"
+    } else {
+        "Imported"
+    };
+    println!(
+        "{verb} {} finding(s) ({} superseding earlier findings, {} resolved, {} without a location skipped)",
+        attestations.len(),
+        superseding,
+        resolved.len(),
+        skipped
+    );
+    Ok(())
+}
+
+/// The `.qual` file that receives records about `subject`. A dry run only
+/// computes the path; otherwise its directory is created.
+fn qual_path(subject: &str, dry_run: bool) -> crate::Result<PathBuf> {
+    if dry_run {
+        Ok(qual_file::default_qual_path(subject))
+    } else {
+        qual_file::resolve_qual_path(subject, None)
+    }
+}
+
+fn print_would_import(att: &Attestation) {
+    println!(
+        "  would import [{}] {} {} -> {}",
+        att.body.score,
+        att.body.kind,
+        att.subject,
+        qual_file::default_qual_path(&att.subject).display()
+    );
+}
@@ -0,0 +1,130 @@
+use clap::Args as ClapArgs;
+use rand::RngCore;
+use std::path::{Path, PathBuf};
+
+use crate::cli::commands::attest::{detect_issuer, normalize_issuer_uri};
+use crate::cli::config;
+use crate::qual_file::find_project_root;
+use crate::signing::{self, KeyEntry};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Where to write the signing key (default: ~/.config/qualifier/signing.key)
+    #[arg(long)]
+    pub output: Option<String>,
+
+    /// Issuer URI to bind the key to (defaults to config, then VCS identity)
+    #[arg(long)]
+    pub issuer: Option<String>,
+
+    /// Append the public key to the project keyring
+    #[arg(long)]
+    pub register: bool,
+
+    /// Overwrite an existing signing key
+    #[arg(long)]
+    pub force: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            issuer: args.issuer.clone(),
+            signing_key: args.output.clone(),
+            ..Default::default()
+        },
+    )?;
+
+    let key_path = signing_key_path(&config, root.as_deref())?;
+    if key_path.exists() && !args.force {
+        return Err(crate::Error::Validation(format!(
+            "{} already exists (use --force to overwrite)",
+            key_path.display()
+        )));
+    }
+
+    let issuer = config
+        .issuer
+        .or_else(detect_issuer)
+        .map(normalize_issuer_uri)
+        .ok_or_else(|| {
+            crate::Error::Validation(
+                "--issuer is required (could not detect from VCS config or $USER)".into(),
+            )
+        })?;
+
+    let mut seed = [0u8; 32];
+    rand::rng().fill_bytes(&mut seed);
+    let key = ed25519_dalek::SigningKey::from_bytes(&seed);
+
+    write_private(&key_path, &signing::encode_signing_key(&key))?;
+
+    let entry = KeyEntry {
+        issuer: issuer.clone(),
+        public_key: signing::encode_public_key(&key.verifying_key()),
+    };
+    let line = serde_json::to_string(&entry)?;
+
+    println!("  Wrote signing key to {}", key_path.display());
+    println!("  Key ID: {}", signing::key_id(&key.verifying_key()));
+
+    if args.register {
+        let keyring_path = config::resolve_path(&config.keyring, root.as_deref());
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&keyring_path)?;
+        use std::io::Write;
+        writeln!(file, "{line}")?;
+        println!("  Registered {} in {}", issuer, keyring_path.display());
+    } else {
+        println!("  Add this line to your keyring to trust it:");
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+/// Resolve the signing key location from config, falling back to
+/// `~/.config/qualifier/signing.key`.
+pub(crate) fn signing_key_path(
+    config: &config::Config,
+    root: Option<&Path>,
+) -> crate::Result<PathBuf> {
+    match config.signing_key {
+        Some(ref path) => Ok(config::resolve_path(path, root)),
+        None => config::default_signing_key_path().ok_or_else(|| {
+            crate::Error::Validation("cannot locate signing key: $HOME is not set".into())
+        }),
+    }
+}
+
+/// Write a secret to disk, readable only by the owner where supported.
+fn write_private(path: &Path, secret: &str) -> crate::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode above only applies to a new file; tighten an existing one
+    // before the secret is written to it.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    use std::io::Write;
+    writeln!(file, "{secret}")?;
+    Ok(())
+}
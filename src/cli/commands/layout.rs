@@ -0,0 +1,204 @@
+use chrono::Utc;
+use clap::{Args as ClapArgs, Subcommand};
+use std::path::Path;
+
+use crate::attestation::Record;
+use crate::layout::{self as layout_lib, Layout};
+use crate::qual_file::{self, QualFile, find_project_root};
+use crate::signing::{self, Signature};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: LayoutCommand,
+}
+
+#[derive(Subcommand)]
+pub enum LayoutCommand {
+    /// Move records into the .qual files of another layout
+    Migrate(MigrateArgs),
+}
+
+#[derive(ClapArgs)]
+pub struct MigrateArgs {
+    /// Target layout (file, directory, project)
+    #[arg(long)]
+    pub to: String,
+
+    /// Print the plan without writing
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    match args.command {
+        LayoutCommand::Migrate(args) => run_migrate(args),
+    }
+}
+
+fn run_migrate(args: MigrateArgs) -> crate::Result<()> {
+    let layout: Layout = args.to.parse().map_err(crate::Error::Validation)?;
+    let root = find_project_root(Path::new("."));
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let plan = layout_lib::migrate(discover_root, &qual_files, layout, Utc::now())?;
+    if plan.is_empty() {
+        println!("Already in the {layout} layout");
+        return Ok(());
+    }
+
+    // Never overwrite a .qual file whose records were not read.
+    for qf in &plan.files {
+        if qf.path.exists() && !qual_files.iter().any(|d| d.path == qf.path) {
+            return Err(crate::Error::Validation(format!(
+                "{} exists but was not discovered; move it aside or pass --no-ignore",
+                qf.path.display()
+            )));
+        }
+    }
+
+    // Signatures are keyed by record ID, so each moves to the sidecar of
+    // the file its record moves to. `sidecars[i]` is the (old, new)
+    // contents of the sidecar for `plan.files[i]`.
+    let mut signatures = Vec::new();
+    for qf in &qual_files {
+        signatures.extend(signing::read_signatures(&qf.path)?);
+    }
+    let sidecars = plan
+        .files
+        .iter()
+        .map(|qf| {
+            let old = signing::read_signatures(&qf.path)?;
+            Ok((old, signing::signatures_for(&signatures, &qf.records)))
+        })
+        .collect::<crate::Result<Vec<(Vec<Signature>, Vec<Signature>)>>>()?;
+
+    if args.dry_run {
+        println!("Would migrate to the {layout} layout");
+        print_plan(&plan, &sidecars, &qual_files);
+        return Ok(());
+    }
+
+    // Lock every existing file first, and refuse to overwrite records
+    // written since discovery.
+    let ids = |records: &[Record]| -> Vec<String> {
+        records.iter().map(|r| r.id().to_string()).collect()
+    };
+    let mut locks = Vec::with_capacity(plan.files.len());
+    for qf in &plan.files {
+        let Some(discovered) = qual_files.iter().find(|d| d.path == qf.path) else {
+            continue;
+        };
+        let lock = qual_file::lock(&qf.path)?;
+        if ids(&lock.parse()?.records) != ids(&discovered.records) {
+            return Err(crate::Error::Validation(format!(
+                "{} changed during migration; run it again",
+                qf.path.display()
+            )));
+        }
+        locks.push(lock);
+    }
+
+    // Write every destination before removing any source, so an
+    // interruption can duplicate records but never lose them. Sidecars
+    // first gain the signatures moving in and only drop the ones moving
+    // out once every destination is written.
+    for (qf, (old, new)) in plan.files.iter().zip(&sidecars) {
+        if qf.records.is_empty() {
+            continue;
+        }
+        let lock = match locks.iter().position(|l| l.path() == qf.path) {
+            Some(i) => locks.swap_remove(i),
+            None => {
+                if let Some(parent) = qf.path.parent()
+                    && !parent.as_os_str().is_empty()
+                {
+                    std::fs::create_dir_all(parent)?;
+                }
+                qual_file::lock(&qf.path)?
+            }
+        };
+        lock.replace(&qf.records, false, |written| {
+            if ids(&written.records) != ids(&qf.records) {
+                return Err(crate::Error::Validation(format!(
+                    "{}: records read back differ from the plan",
+                    qf.path.display()
+                )));
+            }
+            Ok(())
+        })?;
+        let merged = merge(old, new);
+        if &merged != old {
+            signing::write_signatures(&qf.path, &merged)?;
+        }
+    }
+    for qf in plan.files.iter().filter(|qf| qf.records.is_empty()) {
+        std::fs::remove_file(&qf.path)?;
+    }
+    for (qf, (old, new)) in plan.files.iter().zip(&sidecars) {
+        let written = if qf.records.is_empty() {
+            old.clone()
+        } else {
+            merge(old, new)
+        };
+        if &written != new {
+            signing::write_signatures(&qf.path, new)?;
+        }
+    }
+    drop(locks);
+
+    println!("Migrated to the {layout} layout");
+    print_plan(&plan, &sidecars, &qual_files);
+    Ok(())
+}
+
+/// `old` followed by the signatures of `new` it lacks.
+fn merge(old: &[Signature], new: &[Signature]) -> Vec<Signature> {
+    let mut merged = old.to_vec();
+    merged.extend(new.iter().filter(|s| !old.contains(s)).cloned());
+    merged
+}
+
+fn print_plan(
+    plan: &layout_lib::Migration,
+    sidecars: &[(Vec<Signature>, Vec<Signature>)],
+    qual_files: &[QualFile],
+) {
+    for (qf, (old, new)) in plan.files.iter().zip(sidecars) {
+        let action = if qf.records.is_empty() {
+            "remove"
+        } else if qual_files.iter().any(|d| d.path == qf.path) {
+            "rewrite"
+        } else {
+            "create"
+        };
+        if qf.records.is_empty() {
+            println!("  {action} {}", qf.path.display());
+        } else {
+            println!(
+                "  {action} {} ({} record(s))",
+                qf.path.display(),
+                qf.records.len()
+            );
+        }
+        if old != new {
+            let sidecar = signing::sig_path(&qf.path);
+            if new.is_empty() {
+                println!("  remove {}", sidecar.display());
+            } else {
+                let action = if old.is_empty() { "create" } else { "rewrite" };
+                println!(
+                    "  {action} {} ({} signature(s))",
+                    sidecar.display(),
+                    new.len()
+                );
+            }
+        }
+    }
+    println!("  moved {} record(s)", plan.moved);
+}
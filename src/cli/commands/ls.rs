@@ -2,8 +2,9 @@ use clap::Args as ClapArgs;
 use std::collections::HashSet;
 use std::path::Path;
 
-use crate::cli::output;
-use crate::qual_file::{self, find_project_root};
+use crate::attestation::Record;
+use crate::cli::{config, output};
+use crate::qual_file::find_project_root;
 use crate::scoring;
 
 #[derive(ClapArgs)]
@@ -20,14 +21,26 @@ pub struct Args {
     #[arg(long)]
     pub unqualified: bool,
 
+    /// Show only artifacts with attestations expiring within this window (e.g. 30d)
+    #[arg(long)]
+    pub expiring_within: Option<String>,
+
     /// Output format (human, json)
-    #[arg(long, default_value = "human")]
-    pub format: String,
+    #[arg(long)]
+    pub format: Option<String>,
 
     /// Path to the dependency graph file
     #[arg(long)]
     pub graph: Option<String>,
 
+    /// Evaluate scores at this RFC 3339 timestamp or date instead of now
+    #[arg(long)]
+    pub at: Option<String>,
+
+    /// Skip malformed .qual lines with a warning instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+
     /// Disable .gitignore and .qualignore filtering
     #[arg(long)]
     pub no_ignore: bool,
@@ -35,11 +48,40 @@ pub struct Args {
 
 pub fn run(args: Args) -> crate::Result<()> {
     let root = find_project_root(Path::new("."));
-    let graph = crate::cli::config::load_graph(args.graph.as_deref(), root.as_deref());
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            graph: args.graph.clone(),
+            format: args.format.clone(),
+            ..Default::default()
+        },
+    )?;
     let discover_root = root.as_deref().unwrap_or(Path::new("."));
-    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+    let qual_files = config::discover(discover_root, !args.no_ignore, args.lenient)?;
+    let graph = config::load_graph(&config.graph, root.as_deref(), &qual_files)?;
+
+    let eval = config::evaluation(&config, args.at.as_deref())?.with_staleness(config::staleness(
+        &config,
+        discover_root,
+        &qual_files,
+    )?);
+    let scores = scoring::effective_scores(&graph, &qual_files, &eval);
 
-    let scores = scoring::effective_scores(&graph, &qual_files);
+    let all_records: Vec<Record> = qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter().cloned())
+        .collect();
+    let expiring = match &args.expiring_within {
+        Some(window) => {
+            let window = config::parse_duration(window)
+                .map_err(|e| crate::Error::Validation(format!("invalid --expiring-within: {e}")))?;
+            let until = eval.at.checked_add_signed(window).ok_or_else(|| {
+                crate::Error::Validation("invalid --expiring-within: out of range".into())
+            })?;
+            Some(scoring::expiring(&all_records, until))
+        }
+        None => None,
+    };
 
     // Build an index of subjects that have records
     let attested: HashSet<String> = qual_files
@@ -60,6 +102,12 @@ pub fn run(args: Args) -> crate::Result<()> {
                 return false;
             }
 
+            if let Some(ref expiring) = expiring
+                && !expiring.iter().any(|r| r.subject() == *subject)
+            {
+                return false;
+            }
+
             if let Some(ref kind_filter) = args.kind {
                 let kind_match = qual_files.iter().any(|qf| {
                     qf.records.iter().any(|r| {
@@ -78,12 +126,20 @@ pub fn run(args: Args) -> crate::Result<()> {
 
     reports.sort_by_key(|(_, r)| r.effective);
 
-    if args.format == "json" {
+    if config.format == "json" {
         println!("{}", output::scores_json(&reports));
     } else if reports.is_empty() {
         println!("No matching artifacts found.");
     } else {
         println!("{}", output::score_table(&reports));
+        for record in expiring.iter().flatten() {
+            if reports
+                .iter()
+                .any(|(subject, _)| subject == record.subject())
+            {
+                println!("{}", output::format_expiry(record, eval.at));
+            }
+        }
     }
 
     Ok(())
@@ -1,10 +1,24 @@
 pub mod attest;
 pub mod check;
 pub mod compact;
+pub mod config_cmd;
+pub mod dep;
+pub mod export;
 pub mod graph_cmd;
 pub mod haiku;
+pub mod import;
+pub mod import_sarif;
 pub mod init;
+pub mod keygen;
+pub mod layout;
 pub mod ls;
 pub mod praise;
+pub mod reanchor;
+pub mod rename;
+pub mod retract;
 pub mod score;
 pub mod show;
+pub mod sign;
+pub mod stale;
+pub mod verify;
+pub mod watch;
@@ -1,7 +1,7 @@
 use clap::Args as ClapArgs;
 use std::path::Path;
 
-use crate::cli::output;
+use crate::cli::{config, output};
 use crate::qual_file::{self, find_project_root};
 use crate::scoring;
 
@@ -11,14 +11,22 @@ pub struct Args {
     pub artifact: String,
 
     /// Output format (human, json)
-    #[arg(long, default_value = "human")]
-    pub format: String,
+    #[arg(long)]
+    pub format: Option<String>,
 
     /// Use VCS blame/annotate on the .qual file instead of record-based output
     #[cfg(not(target_os = "emscripten"))]
     #[arg(long)]
     pub vcs: bool,
 
+    /// Also list revoked records, marked with the revocation that withdrew them
+    #[arg(long)]
+    pub revoked: bool,
+
+    /// Skip malformed .qual lines with a warning instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+
     /// Disable .gitignore and .qualignore filtering
     #[arg(long)]
     pub no_ignore: bool,
@@ -36,8 +44,15 @@ pub fn run(args: Args) -> crate::Result<()> {
 
 fn run_records(args: Args) -> crate::Result<()> {
     let root = find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            format: args.format.clone(),
+            ..Default::default()
+        },
+    )?;
     let discover_root = root.as_deref().unwrap_or(Path::new("."));
-    let all_qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+    let all_qual_files = config::discover(discover_root, !args.no_ignore, args.lenient)?;
 
     let records: Vec<&crate::attestation::Record> =
         qual_file::find_records_for(&args.artifact, &all_qual_files);
@@ -51,10 +66,31 @@ fn run_records(args: Args) -> crate::Result<()> {
 
     let owned: Vec<crate::attestation::Record> = records.iter().map(|r| (*r).clone()).collect();
     let active = scoring::filter_superseded(&owned);
+    let revoked = scoring::revoked_by(&owned);
+    let live_spans = crate::symbol::live_spans(discover_root, &owned);
+    let listed: Vec<&crate::attestation::Record> = if args.revoked {
+        owned
+            .iter()
+            .filter(|r| revoked.contains_key(r.id()) || active.iter().any(|a| a.id() == r.id()))
+            .collect()
+    } else {
+        active
+    };
 
-    if args.format == "json" {
-        let entries: Vec<serde_json::Value> =
-            active.iter().filter_map(|r| record_to_json(r)).collect();
+    if config.format == "json" {
+        let entries: Vec<serde_json::Value> = listed
+            .iter()
+            .filter_map(|r| {
+                let mut entry = record_to_json(r)?;
+                if let Some(rev) = revoked.get(r.id()).filter(|_| args.revoked) {
+                    entry["revoked_by"] = output::revoked_by_json(rev);
+                }
+                if let Some(live) = live_spans.get(r.id()) {
+                    entry["live_span"] = serde_json::to_value(live).unwrap_or_default();
+                }
+                Some(entry)
+            })
+            .collect();
         let output = serde_json::json!({
             "subject": args.artifact,
             "records": entries,
@@ -68,10 +104,14 @@ fn run_records(args: Args) -> crate::Result<()> {
 
     // Human output
     println!();
-    println!("  {} \u{2014} {} records", args.artifact, active.len());
+    println!("  {} \u{2014} {} records", args.artifact, listed.len());
     println!();
 
-    for record in &active {
+    for record in &listed {
+        let revoked_note = revoked
+            .get(record.id())
+            .map(|rev| output::revoked_note(rev))
+            .unwrap_or_default();
         if let Some(att) = record.as_attestation() {
             let date = att.created_at.format("%Y-%m-%d");
             let id_short = if att.id.len() >= 8 {
@@ -96,8 +136,8 @@ fn run_records(args: Args) -> crate::Result<()> {
                 _ => String::new(),
             };
             println!(
-                "          {}  {}  {}{}",
-                att.issuer, date, id_short, issuer_type_suffix,
+                "          {}  {}  {}{}{}",
+                att.issuer, date, id_short, issuer_type_suffix, revoked_note,
             );
 
             // Line 3 (optional): suggested_fix, detail, or span
@@ -117,6 +157,14 @@ fn run_records(args: Args) -> crate::Result<()> {
                     end_str,
                 );
             }
+            if let Some(ref symbol) = att.body.symbol {
+                match live_spans.get(att.id.as_str()) {
+                    Some(Some(span)) => {
+                        println!("          symbol: {symbol} ({})", output::format_span(span))
+                    }
+                    _ => println!("          symbol: {symbol} (unresolved)"),
+                }
+            }
 
             println!();
         } else if let Some(epoch) = record.as_epoch() {
@@ -139,8 +187,8 @@ fn run_records(args: Args) -> crate::Result<()> {
                 _ => String::new(),
             };
             println!(
-                "          {}  {}  {}{}",
-                epoch.issuer, date, id_short, issuer_type_suffix,
+                "          {}  {}  {}{}{}",
+                epoch.issuer, date, id_short, issuer_type_suffix, revoked_note,
             );
             println!();
         }
@@ -178,6 +226,9 @@ fn record_to_json(record: &crate::attestation::Record) -> Option<serde_json::Val
         if let Some(ref span) = att.body.span {
             entry["span"] = serde_json::to_value(span).unwrap_or_default();
         }
+        if let Some(ref symbol) = att.body.symbol {
+            entry["symbol"] = serde_json::json!(symbol);
+        }
         Some(entry)
     } else if let Some(epoch) = record.as_epoch() {
         let mut entry = serde_json::json!({
@@ -0,0 +1,115 @@
+use clap::Args as ClapArgs;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::attestation::{self, Record};
+use crate::cli::{config, output};
+use crate::pin::{self, Anchor};
+use crate::qual_file::{self, find_project_root};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Artifacts to re-anchor (all if omitted)
+    pub artifacts: Vec<String>,
+
+    /// Print the plan without writing
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(root.as_deref(), &config::Overrides::default())?;
+    let kinds = config::kinds(&config)?;
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let records: Vec<Record> = qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter().cloned())
+        .filter(|r| args.artifacts.is_empty() || args.artifacts.iter().any(|a| a == r.subject()))
+        .collect();
+
+    let mut batches: BTreeMap<PathBuf, Vec<(String, Record)>> = BTreeMap::new();
+    let mut moved = 0;
+    let mut unresolved = 0;
+    for outcome in pin::reanchor_records(discover_root, &records) {
+        let original = outcome.attestation;
+        let old_span = original.body.span.as_ref().map(output::format_span);
+        let old_span = old_span.as_deref().unwrap_or_default();
+        let label = format!(
+            "{}  {} {}  {}",
+            original.subject,
+            original.body.kind,
+            old_span,
+            &original.id[..original.id.len().min(8)]
+        );
+        match outcome.anchor {
+            Anchor::Unchanged => {}
+            Anchor::Lost => {
+                unresolved += 1;
+                println!("  {label}  lost (spanned lines not found)");
+            }
+            Anchor::Ambiguous => {
+                unresolved += 1;
+                println!("  {label}  ambiguous (spanned lines found in several places)");
+            }
+            Anchor::Moved { span, fingerprint } => {
+                moved += 1;
+                println!("  {label} -> {}", output::format_span(&span));
+                let att = pin::reanchored(original, span, fingerprint);
+                let errors = attestation::validate_with(&att, kinds);
+                if !errors.is_empty() {
+                    return Err(crate::Error::Validation(format!(
+                        "cannot re-anchor {}: {}",
+                        original.id,
+                        errors.join("; ")
+                    )));
+                }
+                let path = qual_files
+                    .iter()
+                    .find(|qf| qf.records.iter().any(|r| r.id() == original.id))
+                    .map(|qf| qf.path.clone())
+                    .expect("re-anchored records come from a discovered file");
+                batches
+                    .entry(path)
+                    .or_default()
+                    .push((original.id.clone(), Record::Attestation(Box::new(att))));
+            }
+        }
+    }
+
+    if !args.dry_run {
+        // Every re-anchored record was validated above; hold every lock
+        // while checking that the originals are still in place and writing.
+        let mut locks = qual_file::lock_all(batches.keys().cloned().collect())?;
+        for (path, batch) in &batches {
+            let current = locks[path].parse()?.records;
+            for (original, _) in batch {
+                if !current.iter().any(|r| r.id() == original) {
+                    return Err(crate::Error::Validation(format!(
+                        "{} changed during re-anchor; run it again",
+                        path.display()
+                    )));
+                }
+            }
+        }
+        for (path, batch) in &batches {
+            let lock = locks.get_mut(path).expect("every batch path is locked");
+            for (_, record) in batch {
+                lock.append(record)?;
+            }
+        }
+    }
+
+    if args.dry_run {
+        println!("Would re-anchor {moved} attestation(s), {unresolved} unresolved");
+    } else {
+        println!("Re-anchored {moved} attestation(s), {unresolved} unresolved");
+    }
+    Ok(())
+}
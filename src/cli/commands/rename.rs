@@ -0,0 +1,262 @@
+use clap::Args as ClapArgs;
+use std::path::{Component, Path};
+
+use crate::attestation::Record;
+use crate::cli::config;
+use crate::graph::{self, DependencyGraph};
+use crate::qual_file::{self, find_project_root};
+use crate::rename as rename_lib;
+use crate::signing;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// The current subject name
+    pub old: String,
+
+    /// The new subject name
+    pub new: String,
+
+    /// Explicit .qual file for the new subject's records (overrides layout resolution)
+    #[arg(long)]
+    pub file: Option<String>,
+
+    /// Path to the dependency graph file
+    #[arg(long)]
+    pub graph: Option<String>,
+
+    /// Print the plan without writing
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            graph: args.graph.clone(),
+            ..Default::default()
+        },
+    )?;
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let eval = config::evaluation(&config, None)?.with_staleness(config::staleness(
+        &config,
+        discover_root,
+        &qual_files,
+    )?);
+    let plan = rename_lib::rename(&qual_files, &args.old, &args.new, &eval)?;
+
+    let graph_path = config::resolve_path(&config.graph, root.as_deref());
+    let graph_content = if graph_path.exists() {
+        Some(std::fs::read_to_string(&graph_path)?)
+    } else {
+        None
+    };
+    let graph_rewrite = match &graph_content {
+        Some(content) => {
+            let (rewritten, changed) = graph::rename_subject(content, &args.old, &args.new)?;
+            (changed > 0).then_some(rewritten)
+        }
+        None => None,
+    };
+
+    if plan.is_empty() && graph_rewrite.is_none() {
+        return Err(crate::Error::Validation(format!(
+            "No records or graph entries found for '{}'",
+            args.old
+        )));
+    }
+
+    // Refuse renames that would merge two subjects into a cycle.
+    let mut g = match &graph_rewrite {
+        Some(content) => graph::parse_graph(content)?,
+        None if graph_path.exists() => graph::load(&graph_path)?,
+        None => DependencyGraph::empty(),
+    };
+    let touched: Vec<&Path> = plan.files.iter().map(|qf| qf.path.as_path()).collect();
+    g.add_dependency_records(
+        qual_files
+            .iter()
+            .filter(|qf| !touched.contains(&qf.path.as_path()))
+            .chain(&plan.files)
+            .flat_map(|qf| qf.records.iter())
+            .chain(&plan.carried),
+    );
+    g.toposort()?;
+
+    let target = match &args.file {
+        Some(file) => Path::new(file).to_path_buf(),
+        None => qual_file::default_qual_path(&args.new),
+    };
+    // The carried records join a rewritten file if the target is one.
+    let target_index = if plan.carried.is_empty() {
+        None
+    } else {
+        plan.files
+            .iter()
+            .position(|qf| same_file(&qf.path, &target))
+    };
+
+    if args.dry_run {
+        println!("Would rename {} -> {}", args.old, args.new);
+        print_plan(
+            &plan,
+            &target,
+            target_index,
+            graph_rewrite.is_some().then_some(graph_path.as_path()),
+        );
+        return Ok(());
+    }
+
+    // Lock every rewritten file first, and refuse to overwrite records
+    // written since discovery.
+    let ids = |records: &[Record]| -> Vec<String> {
+        records.iter().map(|r| r.id().to_string()).collect()
+    };
+    let mut locks = Vec::with_capacity(plan.files.len());
+    for qf in &plan.files {
+        let lock = qual_file::lock(&qf.path)?;
+        let discovered = qual_files.iter().find(|d| d.path == qf.path);
+        let current = ids(&lock.parse()?.records);
+        if discovered.map(|d| ids(&d.records)) != Some(current) {
+            return Err(crate::Error::Validation(format!(
+                "{} changed during rename; run it again",
+                qf.path.display()
+            )));
+        }
+        locks.push(lock);
+    }
+    let graph_lock = match &graph_rewrite {
+        Some(_) => {
+            let lock = qual_file::lock(&graph_path)?;
+            if graph_content.as_deref() != Some(&std::fs::read_to_string(&graph_path)?) {
+                return Err(crate::Error::Validation(format!(
+                    "{} changed during rename; run it again",
+                    graph_path.display()
+                )));
+            }
+            Some(lock)
+        }
+        None => None,
+    };
+
+    // Carried records that do not join a rewritten file are appended
+    // under the target's lock.
+    let carry_to = if plan.carried.is_empty() || target_index.is_some() {
+        None
+    } else {
+        let file = args.file.as_deref().map(Path::new);
+        Some(qual_file::resolve_qual_path(&args.new, file)?)
+    };
+    let mut writes = Vec::with_capacity(plan.files.len());
+    for (i, (qf, lock)) in plan.files.iter().zip(locks).enumerate() {
+        let mut records = qf.records.clone();
+        if target_index == Some(i) {
+            records.extend(plan.carried.iter().cloned());
+        }
+        let old = signing::read_signatures(&qf.path)?;
+        let sigs = signing::signatures_for(&old, &records);
+        writes.push((qf, records, (old, sigs), lock));
+    }
+
+    // Write the new subject's records before dropping the old ones, so an
+    // interruption can duplicate records but never lose them.
+    if let Some(i) = target_index {
+        let first = writes.remove(i);
+        writes.insert(0, first);
+    } else if let Some(path) = &carry_to {
+        let mut lock = qual_file::lock(path)?;
+        for record in &plan.carried {
+            lock.append(record)?;
+        }
+    }
+    for (qf, records, (old, sigs), lock) in writes {
+        if records.is_empty() {
+            std::fs::remove_file(&qf.path)?;
+            drop(lock);
+        } else {
+            lock.replace(&records, false, |written| {
+                if ids(&written.records) != ids(&records) {
+                    return Err(crate::Error::Validation(format!(
+                        "{}: records read back differ from the plan",
+                        qf.path.display()
+                    )));
+                }
+                Ok(())
+            })?;
+        }
+        if sigs != old {
+            signing::write_signatures(&qf.path, &sigs)?;
+        }
+    }
+    if let (Some(lock), Some(content)) = (graph_lock, &graph_rewrite) {
+        lock.replace_content(content)?;
+    }
+
+    println!("Renamed {} -> {}", args.old, args.new);
+    print_plan(
+        &plan,
+        &target,
+        target_index,
+        graph_rewrite.is_some().then_some(graph_path.as_path()),
+    );
+    Ok(())
+}
+
+/// Returns true if `a` and `b` name the same file: discovery yields
+/// `./src/.qual` where layout resolution yields `src/.qual`.
+fn same_file(a: &Path, b: &Path) -> bool {
+    fn lexical(p: &Path) -> Vec<Component<'_>> {
+        p.components().filter(|c| *c != Component::CurDir).collect()
+    }
+    if lexical(a) == lexical(b) {
+        return true;
+    }
+    match (std::fs::canonicalize(a), std::fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn print_plan(
+    plan: &rename_lib::Rename,
+    target: &Path,
+    target_index: Option<usize>,
+    graph_file: Option<&Path>,
+) {
+    for (i, qf) in plan.files.iter().enumerate() {
+        let action = if qf.records.is_empty() && target_index != Some(i) {
+            "remove"
+        } else {
+            "rewrite"
+        };
+        println!("  {action} {}", qf.path.display());
+    }
+    if !plan.carried.is_empty() {
+        println!(
+            "  append {} record(s) to {}",
+            plan.carried.len(),
+            target.display()
+        );
+    }
+    if let Some(epoch) = plan.epoch() {
+        println!(
+            "  folded {} record(s) into epoch {} (score {})",
+            plan.folded.len(),
+            &epoch.id[..8],
+            epoch.body.score
+        );
+    }
+    if plan.rewired > 0 {
+        println!("  rewired {} dependency record(s)", plan.rewired);
+    }
+    if let Some(path) = graph_file {
+        println!("  rewrite {}", path.display());
+    }
+}
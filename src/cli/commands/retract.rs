@@ -0,0 +1,131 @@
+use chrono::Utc;
+use clap::Args as ClapArgs;
+use std::path::Path;
+
+use crate::attestation::{self, Record, RevocationBody, RevocationRecord};
+use crate::cli::commands::attest::{detect_issuer, normalize_issuer_uri};
+use crate::cli::config;
+use crate::qual_file::{self, QualFile, find_project_root};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// IDs (or unique ID prefixes) of the records to withdraw
+    #[arg(required = true)]
+    pub ids: Vec<String>,
+
+    /// Why the records are being withdrawn
+    #[arg(long, default_value = "Retracted")]
+    pub summary: String,
+
+    /// Issuer identity URI (defaults to VCS user email with mailto:)
+    #[arg(long)]
+    pub issuer: Option<String>,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let targets: Vec<(&QualFile, &Record)> = args
+        .ids
+        .iter()
+        .map(|id| find_record(&qual_files, id))
+        .collect::<crate::Result<_>>()?;
+
+    let (qf, first) = targets[0];
+    let subject = first.subject();
+    if let Some((_, other)) = targets.iter().find(|(_, r)| r.subject() != subject) {
+        return Err(crate::Error::Validation(format!(
+            "all retracted records must share a subject, but '{}' and '{}' differ \
+             — run retract once per subject",
+            subject,
+            other.subject()
+        )));
+    }
+
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            issuer: args.issuer,
+            ..Default::default()
+        },
+    )?;
+    let issuer = normalize_issuer_uri(
+        config
+            .issuer
+            .or_else(detect_issuer)
+            .unwrap_or_else(|| "mailto:unknown@localhost".into()),
+    );
+
+    let mut revokes: Vec<String> = Vec::new();
+    for (_, record) in &targets {
+        if !revokes.iter().any(|id| id == record.id()) {
+            revokes.push(record.id().to_string());
+        }
+    }
+    let revocation = attestation::finalize_record(Record::Revocation(RevocationRecord {
+        metabox: "1".into(),
+        record_type: "revocation".into(),
+        subject: subject.to_string(),
+        issuer,
+        issuer_type: None,
+        created_at: Utc::now(),
+        id: String::new(),
+        body: RevocationBody {
+            revokes,
+            summary: args.summary,
+        },
+    }));
+
+    // Keep the revocation next to what it revokes so compaction can drop both.
+    // Re-read that file under its lock, so the targets cannot be compacted
+    // away between validation and the append.
+    let mut lock = qual_file::lock(&qf.path)?;
+    let mut all: Vec<Record> = qual_files
+        .iter()
+        .filter(|other| other.path != qf.path)
+        .flat_map(|other| other.records.iter().cloned())
+        .chain(lock.parse()?.records)
+        .collect();
+    all.push(revocation.clone());
+    attestation::validate_revocation_targets(&all)?;
+
+    lock.append(&revocation)?;
+    println!(
+        "Retracted {} record(s) from {}",
+        revocation.revokes().len(),
+        subject
+    );
+    println!("  id: {}", revocation.id());
+
+    Ok(())
+}
+
+/// Find the record whose ID is `id` or starts with it.
+fn find_record<'a>(
+    qual_files: &'a [QualFile],
+    id: &str,
+) -> crate::Result<(&'a QualFile, &'a Record)> {
+    let matches: Vec<(&QualFile, &Record)> = qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter().map(move |r| (qf, r)))
+        .filter(|(_, r)| !id.is_empty() && r.id().starts_with(id))
+        .collect();
+
+    match matches.as_slice() {
+        [] => Err(crate::Error::Validation(format!(
+            "No record found with id '{id}'"
+        ))),
+        [one] => Ok(*one),
+        _ if matches.iter().all(|(_, r)| r.id() == matches[0].1.id()) => Ok(matches[0]),
+        _ => Err(crate::Error::Validation(format!(
+            "id prefix '{id}' is ambiguous ({} records match)",
+            matches.len()
+        ))),
+    }
+}
@@ -1,7 +1,7 @@
 use clap::Args as ClapArgs;
 use std::path::Path;
 
-use crate::cli::output;
+use crate::cli::{config, output};
 use crate::qual_file::{self, find_project_root};
 use crate::scoring;
 
@@ -11,13 +11,29 @@ pub struct Args {
     pub artifacts: Vec<String>,
 
     /// Output format (human, json)
-    #[arg(long, default_value = "human")]
-    pub format: String,
+    #[arg(long)]
+    pub format: Option<String>,
 
     /// Path to the dependency graph file
     #[arg(long)]
     pub graph: Option<String>,
 
+    /// Evaluate scores at this RFC 3339 timestamp or date instead of now
+    #[arg(long)]
+    pub at: Option<String>,
+
+    /// Also score each region of overlapping spans within a subject
+    #[arg(long)]
+    pub spans: bool,
+
+    /// Score only attestations tagged with this dimension (e.g. security)
+    #[arg(long)]
+    pub dimension: Option<String>,
+
+    /// Skip malformed .qual lines with a warning instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+
     /// Disable .gitignore and .qualignore filtering
     #[arg(long)]
     pub no_ignore: bool,
@@ -25,11 +41,24 @@ pub struct Args {
 
 pub fn run(args: Args) -> crate::Result<()> {
     let root = find_project_root(Path::new("."));
-    let graph = crate::cli::config::load_graph(args.graph.as_deref(), root.as_deref());
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            graph: args.graph.clone(),
+            format: args.format.clone(),
+            ..Default::default()
+        },
+    )?;
     let discover_root = root.as_deref().unwrap_or(Path::new("."));
-    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+    let qual_files = config::discover(discover_root, !args.no_ignore, args.lenient)?;
+    let graph = config::load_graph(&config.graph, root.as_deref(), &qual_files)?;
 
-    let scores = scoring::effective_scores(&graph, &qual_files);
+    let mut eval = config::evaluation(&config, args.at.as_deref())?
+        .with_staleness(config::staleness(&config, discover_root, &qual_files)?);
+    if let Some(dimension) = args.dimension {
+        eval = eval.with_dimension(dimension);
+    }
+    let scores = scoring::dimension_scores(&graph, &qual_files, &eval);
 
     // Filter to requested artifacts, or show all
     let mut reports: Vec<(String, scoring::ScoreReport)> = if args.artifacts.is_empty() {
@@ -44,7 +73,26 @@ pub fn run(args: Args) -> crate::Result<()> {
     // Sort by effective score ascending (worst first)
     reports.sort_by_key(|(_, r)| r.effective);
 
-    if args.format == "json" {
+    if args.spans {
+        let reports: Vec<(String, scoring::ScoreReport, Vec<scoring::SpanRegion>)> = reports
+            .into_iter()
+            .map(|(subject, report)| {
+                let records: Vec<crate::attestation::Record> =
+                    qual_file::find_records_for(&subject, &qual_files)
+                        .into_iter()
+                        .cloned()
+                        .collect();
+                let live = crate::symbol::live_spans(discover_root, &records);
+                let records = crate::symbol::with_live_spans(&records, &live);
+                let regions = scoring::span_regions(&records, &eval);
+                (subject, report, regions)
+            })
+            .collect();
+        print_with_spans(&config.format, &reports);
+        return Ok(());
+    }
+
+    if config.format == "json" {
         println!("{}", output::scores_json(&reports));
     } else if reports.is_empty() {
         println!("No qualified artifacts found.");
@@ -54,3 +102,31 @@ pub fn run(args: Args) -> crate::Result<()> {
 
     Ok(())
 }
+
+fn print_with_spans(
+    format: &str,
+    reports: &[(String, scoring::ScoreReport, Vec<scoring::SpanRegion>)],
+) {
+    if format == "json" {
+        println!("{}", output::scores_with_spans_json(reports));
+        return;
+    }
+    if reports.is_empty() {
+        println!("No qualified artifacts found.");
+        return;
+    }
+
+    let summary: Vec<(String, scoring::ScoreReport)> = reports
+        .iter()
+        .map(|(subject, report, _)| (subject.clone(), report.clone()))
+        .collect();
+    println!("{}", output::score_table(&summary));
+    for (subject, _, regions) in reports {
+        if regions.is_empty() {
+            continue;
+        }
+        println!();
+        println!("  {subject}");
+        println!("{}", output::span_table(regions));
+    }
+}
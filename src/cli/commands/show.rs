@@ -1,7 +1,10 @@
 use clap::Args as ClapArgs;
+use std::collections::HashMap;
 use std::path::Path;
 
-use crate::cli::output;
+use crate::attestation::Span;
+
+use crate::cli::{config, output};
 use crate::qual_file::{self, find_project_root};
 use crate::scoring;
 
@@ -11,13 +14,25 @@ pub struct Args {
     pub artifact: String,
 
     /// Output format (human, json)
-    #[arg(long, default_value = "human")]
-    pub format: String,
+    #[arg(long)]
+    pub format: Option<String>,
 
     /// Path to the dependency graph file
     #[arg(long)]
     pub graph: Option<String>,
 
+    /// Evaluate scores at this RFC 3339 timestamp or date instead of now
+    #[arg(long)]
+    pub at: Option<String>,
+
+    /// Also list revoked records, marked with the revocation that withdrew them
+    #[arg(long)]
+    pub revoked: bool,
+
+    /// Skip malformed .qual lines with a warning instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+
     /// Disable .gitignore and .qualignore filtering
     #[arg(long)]
     pub no_ignore: bool,
@@ -25,9 +40,17 @@ pub struct Args {
 
 pub fn run(args: Args) -> crate::Result<()> {
     let root = find_project_root(Path::new("."));
-    let graph = crate::cli::config::load_graph(args.graph.as_deref(), root.as_deref());
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            graph: args.graph.clone(),
+            format: args.format.clone(),
+            ..Default::default()
+        },
+    )?;
     let discover_root = root.as_deref().unwrap_or(Path::new("."));
-    let all_qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+    let all_qual_files = config::discover(discover_root, !args.no_ignore, args.lenient)?;
+    let graph = config::load_graph(&config.graph, root.as_deref(), &all_qual_files)?;
 
     let records = qual_file::find_records_for(&args.artifact, &all_qual_files);
 
@@ -38,22 +61,59 @@ pub fn run(args: Args) -> crate::Result<()> {
         )));
     }
 
-    let scores = scoring::effective_scores(&graph, &all_qual_files);
+    let eval = config::evaluation(&config, args.at.as_deref())?.with_staleness(config::staleness(
+        &config,
+        discover_root,
+        &all_qual_files,
+    )?);
+    let scores = scoring::effective_scores(&graph, &all_qual_files, &eval);
     let owned_records: Vec<crate::attestation::Record> =
         records.iter().map(|r| (*r).clone()).collect();
     let report = scores
         .get(&args.artifact)
         .cloned()
         .unwrap_or(scoring::ScoreReport {
-            raw: scoring::raw_score(&owned_records),
-            effective: scoring::raw_score(&owned_records),
+            raw: scoring::raw_score(&owned_records, &eval),
+            effective: scoring::raw_score(&owned_records, &eval),
             limiting_path: None,
         });
 
-    if args.format == "json" {
+    let live_spans = crate::symbol::live_spans(discover_root, &owned_records);
+    let active = scoring::filter_superseded(&owned_records);
+    let revoked = scoring::revoked_by(&owned_records);
+    let listed: Vec<&crate::attestation::Record> = if args.revoked {
+        owned_records
+            .iter()
+            .filter(|r| revoked.contains_key(r.id()) || active.iter().any(|a| a.id() == r.id()))
+            .collect()
+    } else {
+        active.clone()
+    };
+
+    if config.format == "json" {
+        let contributions: Vec<output::Contribution> = active
+            .iter()
+            .filter_map(|r| {
+                let weighting = eval.trust.weigh(r);
+                Some(output::Contribution {
+                    id: r.id().to_string(),
+                    nominal: r.score()?,
+                    decayed: scoring::contribution(r, &eval)?,
+                    weight: weighting.percent,
+                    weight_reasons: weighting.reasons,
+                })
+            })
+            .collect();
         println!(
             "{}",
-            output::show_json(&args.artifact, &report, &owned_records)
+            output::show_json(
+                &args.artifact,
+                &report,
+                &owned_records,
+                &contributions,
+                args.revoked.then_some(&revoked),
+                &live_spans,
+            )
         );
         return Ok(());
     }
@@ -72,10 +132,13 @@ pub fn run(args: Args) -> crate::Result<()> {
         println!("  Effective score: {}", report.effective);
     }
 
-    let active = scoring::filter_superseded(&owned_records);
     println!();
-    println!("  Records ({}):", active.len());
-    for record in &active {
+    println!("  Records ({}):", listed.len());
+    for record in &listed {
+        let revoked_note = revoked
+            .get(record.id())
+            .map(|rev| output::revoked_note(rev))
+            .unwrap_or_default();
         if let Some(att) = record.as_attestation() {
             let date = att.created_at.format("%Y-%m-%d");
             let issuer_short = att
@@ -84,21 +147,27 @@ pub fn run(args: Args) -> crate::Result<()> {
                 .and_then(|e| e.split('@').next())
                 .unwrap_or(&att.issuer);
             println!(
-                "    {} {}  {:?}  {}  {}",
+                "    {} {}  {:?}  {}  {}{}{}{}{}{}",
                 output::format_score(att.body.score),
                 att.body.kind,
                 att.body.summary,
                 issuer_short,
                 date,
+                decay_note(record, &eval),
+                expiry_note(record, &eval),
+                weight_note(record, &eval),
+                symbol_note(att, &live_spans),
+                revoked_note,
             );
         } else if let Some(epoch) = record.as_epoch() {
             let date = epoch.created_at.format("%Y-%m-%d");
             println!(
-                "    {} epoch  {:?}  {}  {}",
+                "    {} epoch  {:?}  {}  {}{}",
                 output::format_score(epoch.body.score),
                 epoch.body.summary,
                 epoch.issuer,
                 date,
+                revoked_note,
             );
         }
     }
@@ -106,3 +175,58 @@ pub fn run(args: Args) -> crate::Result<()> {
 
     Ok(())
 }
+
+/// ` (decayed to N)` for records under a decay rule, empty otherwise.
+fn decay_note(record: &crate::attestation::Record, eval: &scoring::Evaluation) -> String {
+    if eval.decay_rule(record).is_none() {
+        return String::new();
+    }
+    match scoring::contribution(record, eval) {
+        Some(decayed) => format!("  (decayed to {decayed})"),
+        None => String::new(),
+    }
+}
+
+/// ` (weighted N% to M: reasons)` for attestations a trust rule applies to,
+/// empty otherwise.
+fn weight_note(record: &crate::attestation::Record, eval: &scoring::Evaluation) -> String {
+    let weighting = eval.trust.weigh(record);
+    if weighting.reasons.is_empty() {
+        return String::new();
+    }
+    let counted = scoring::contribution(record, eval).unwrap_or_default();
+    format!(
+        "  (weighted {}% to {counted}: {})",
+        weighting.percent,
+        weighting.reasons.join(", ")
+    )
+}
+
+/// ` (expires YYYY-MM-DD)` or ` (expired YYYY-MM-DD)` for records with an
+/// `expires_at`, empty otherwise.
+fn expiry_note(record: &crate::attestation::Record, eval: &scoring::Evaluation) -> String {
+    let Some(expires_at) = record.expires_at() else {
+        return String::new();
+    };
+    let verb = if record.is_expired(eval.at) {
+        "expired"
+    } else {
+        "expires"
+    };
+    format!("  ({verb} {})", expires_at.format("%Y-%m-%d"))
+}
+
+/// ` (symbol NAME at L45–60)` or ` (symbol NAME unresolved)` for
+/// symbol-addressed attestations, empty otherwise.
+fn symbol_note(
+    att: &crate::attestation::Attestation,
+    live_spans: &HashMap<&str, Option<Span>>,
+) -> String {
+    let Some(symbol) = att.body.symbol.as_deref() else {
+        return String::new();
+    };
+    match live_spans.get(att.id.as_str()) {
+        Some(Some(span)) => format!("  (symbol {symbol} at {})", output::format_span(span)),
+        _ => format!("  (symbol {symbol} unresolved)"),
+    }
+}
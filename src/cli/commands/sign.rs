@@ -0,0 +1,92 @@
+use clap::Args as ClapArgs;
+use std::path::Path;
+
+use crate::cli::commands::attest::{detect_issuer, normalize_issuer_uri};
+use crate::cli::commands::keygen::signing_key_path;
+use crate::cli::config;
+use crate::qual_file::{self, find_project_root};
+use crate::signing;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Artifacts whose records to sign (default: all)
+    pub artifacts: Vec<String>,
+
+    /// Path to the Ed25519 signing key
+    #[arg(long)]
+    pub key: Option<String>,
+
+    /// Sign records issued by this URI (defaults to config, then VCS identity)
+    #[arg(long)]
+    pub issuer: Option<String>,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            issuer: args.issuer.clone(),
+            signing_key: args.key.clone(),
+            ..Default::default()
+        },
+    )?;
+
+    let key_path = signing_key_path(&config, root.as_deref())?;
+    let encoded = std::fs::read_to_string(&key_path).map_err(|e| {
+        crate::Error::Validation(format!(
+            "cannot read signing key {}: {e} (run `qualifier keygen` first)",
+            key_path.display()
+        ))
+    })?;
+    let key = signing::decode_signing_key(&encoded)?;
+    let keyid = signing::key_id(&key.verifying_key());
+
+    let issuer = config
+        .issuer
+        .or_else(detect_issuer)
+        .map(normalize_issuer_uri)
+        .ok_or_else(|| {
+            crate::Error::Validation(
+                "--issuer is required (could not detect from VCS config or $USER)".into(),
+            )
+        })?;
+
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let mut signed = 0;
+    let mut files = 0;
+    for qf in &qual_files {
+        if !args.artifacts.is_empty() && !args.artifacts.contains(&qf.subject) {
+            continue;
+        }
+
+        let existing = signing::read_signatures(&qf.path)?;
+        let new_sigs: Vec<signing::Signature> = qf
+            .records
+            .iter()
+            .filter(|r| r.issuer() == Some(issuer.as_str()))
+            .filter(|r| {
+                !existing
+                    .iter()
+                    .any(|s| s.record_id == r.id() && s.keyid == keyid)
+            })
+            .filter_map(|r| signing::sign_record(r, &key))
+            .collect();
+
+        if new_sigs.is_empty() {
+            continue;
+        }
+        signing::append_signatures(&qf.path, &new_sigs)?;
+        signed += new_sigs.len();
+        files += 1;
+    }
+
+    println!("Signed {signed} record(s) in {files} file(s) as {issuer} (key {keyid})");
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use clap::Args as ClapArgs;
+use std::path::Path;
+
+use crate::attestation::Record;
+use crate::cli::config;
+use crate::pin::{self, StaleReason};
+use crate::qual_file::find_project_root;
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Artifacts to check (all if omitted)
+    pub artifacts: Vec<String>,
+
+    /// Output format (human, json)
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// Skip malformed .qual lines with a warning instead of failing
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            format: args.format.clone(),
+            ..Default::default()
+        },
+    )?;
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = config::discover(discover_root, !args.no_ignore, args.lenient)?;
+
+    let records: Vec<Record> = qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter().cloned())
+        .filter(|r| args.artifacts.is_empty() || args.artifacts.iter().any(|a| a == r.subject()))
+        .collect();
+    let stale = pin::stale_records(discover_root, &records);
+
+    if config.format == "json" {
+        let entries: Vec<serde_json::Value> = stale
+            .iter()
+            .map(|s| {
+                let (reason, current) = match &s.reason {
+                    StaleReason::Changed { current } => ("changed", Some(current)),
+                    StaleReason::Missing => ("missing", None),
+                };
+                serde_json::json!({
+                    "subject": s.record.subject(),
+                    "id": s.record.id(),
+                    "kind": s.record.kind().map(|k| k.to_string()),
+                    "score": s.record.score(),
+                    "pinned_digest": s.record.as_attestation().and_then(|a| a.body.digest.clone()),
+                    "current_digest": current,
+                    "reason": reason,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    if stale.is_empty() {
+        println!("No stale attestations.");
+        return Ok(());
+    }
+
+    for s in &stale {
+        let Some(att) = s.record.as_attestation() else {
+            continue;
+        };
+        let reason = match s.reason {
+            StaleReason::Changed { .. } => "content changed",
+            StaleReason::Missing => "subject missing",
+        };
+        println!(
+            "  {}  {} {:?}  {}  {}",
+            att.subject,
+            att.body.kind,
+            att.body.summary,
+            &att.id[..att.id.len().min(8)],
+            reason
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use clap::Args as ClapArgs;
+use std::path::Path;
+
+use crate::cli::config;
+use crate::qual_file::{self, find_project_root};
+use crate::signing::{self, Keyring, Verification};
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Artifacts whose records to verify (default: all)
+    pub artifacts: Vec<String>,
+
+    /// Path to the keyring file
+    #[arg(long)]
+    pub keyring: Option<String>,
+
+    /// Also fail on unsigned records and records from issuers not in the keyring
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Output format (human, json)
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new("."));
+    let config = config::load(
+        root.as_deref(),
+        &config::Overrides {
+            keyring: args.keyring.clone(),
+            format: args.format.clone(),
+            ..Default::default()
+        },
+    )?;
+    let keyring = Keyring::load(&config::resolve_path(&config.keyring, root.as_deref()))?;
+
+    let discover_root = root.as_deref().unwrap_or(Path::new("."));
+    let qual_files = qual_file::discover(discover_root, !args.no_ignore)?;
+
+    let mut results = Vec::new();
+    for qf in &qual_files {
+        if !args.artifacts.is_empty() && !args.artifacts.contains(&qf.subject) {
+            continue;
+        }
+        let sigs = signing::read_signatures(&qf.path)?;
+        for record in &qf.records {
+            results.push((qf, record, signing::verify_record(record, &sigs, &keyring)));
+        }
+    }
+
+    let count = |f: fn(&Verification) -> bool| results.iter().filter(|(_, _, v)| f(v)).count();
+    let verified = count(|v| matches!(v, Verification::Verified { .. }));
+    let unsigned = count(|v| matches!(v, Verification::Unsigned));
+    let unknown = count(|v| matches!(v, Verification::UnknownIssuer));
+    let invalid = count(|v| matches!(v, Verification::Invalid { .. }));
+
+    if config.format == "json" {
+        let entries: Vec<serde_json::Value> = results
+            .iter()
+            .map(|(qf, record, v)| {
+                let (status, keyid, reason) = match v {
+                    Verification::Verified { keyid } => ("verified", Some(keyid.as_str()), None),
+                    Verification::Unsigned => ("unsigned", None, None),
+                    Verification::UnknownIssuer => ("unknown_issuer", None, None),
+                    Verification::Invalid { reason } => ("invalid", None, Some(reason.as_str())),
+                };
+                serde_json::json!({
+                    "file": qf.path.display().to_string(),
+                    "subject": qf.subject,
+                    "id": record.id(),
+                    "issuer": record.issuer(),
+                    "status": status,
+                    "keyid": keyid,
+                    "reason": reason,
+                })
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).unwrap_or_default()
+        );
+    } else {
+        for (qf, record, v) in &results {
+            let label = match v {
+                Verification::Verified { .. } => continue,
+                Verification::Unsigned if !args.strict => continue,
+                Verification::UnknownIssuer if !args.strict => continue,
+                Verification::Unsigned => "UNSIGNED".to_string(),
+                Verification::UnknownIssuer => "UNKNOWN ISSUER".to_string(),
+                Verification::Invalid { reason } => format!("INVALID ({reason})"),
+            };
+            let id = record.id();
+            eprintln!(
+                "{}: {} {} issuer={}",
+                label,
+                qf.subject,
+                &id[..id.len().min(8)],
+                record.issuer().unwrap_or("(none)")
+            );
+        }
+        println!(
+            "{verified} verified, {unsigned} unsigned, {unknown} unknown issuer, {invalid} invalid"
+        );
+    }
+
+    let failed = if args.strict {
+        invalid + unsigned + unknown
+    } else {
+        invalid
+    };
+    if failed > 0 {
+        return Err(crate::Error::CheckFailed(format!(
+            "{failed} record(s) failed signature verification"
+        )));
+    }
+    Ok(())
+}
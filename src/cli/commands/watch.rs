@@ -0,0 +1,156 @@
+use clap::Args as ClapArgs;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+
+use crate::cli::{config, output};
+use crate::qual_file::{self, find_project_root};
+use crate::scoring::Evaluation;
+use crate::watch::IncrementalScores;
+
+/// How long to wait for a burst of file events to settle.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+#[derive(ClapArgs)]
+pub struct Args {
+    /// Output format (human, json)
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// Path to the dependency graph file
+    #[arg(long)]
+    pub graph: Option<String>,
+
+    /// Disable .gitignore and .qualignore filtering
+    #[arg(long)]
+    pub no_ignore: bool,
+}
+
+pub fn run(args: Args) -> crate::Result<()> {
+    let root = find_project_root(Path::new(".")).unwrap_or_else(|| PathBuf::from("."));
+    let root = root.canonicalize()?;
+    let config = config::load(
+        Some(&root),
+        &config::Overrides {
+            graph: args.graph.clone(),
+            format: args.format.clone(),
+            ..Default::default()
+        },
+    )?;
+    let json = config.format == "json";
+    let graph_path = config::resolve_path(&config.graph, Some(&root));
+
+    // Decay and expiry depend on the time and staleness on the records, so
+    // every update is evaluated afresh.
+    let evaluate = |qual_files: &[qual_file::QualFile]| -> crate::Result<Evaluation> {
+        Ok(config::evaluation(&config, None)?
+            .with_staleness(config::staleness(&config, &root, qual_files)?))
+    };
+    let qual_files = qual_file::discover(&root, !args.no_ignore)?;
+    let eval = evaluate(&qual_files)?;
+    let mut state = IncrementalScores::new(qual_files, Some(graph_path.clone()), eval)?;
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(watch_error)?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "event": "ready",
+                "files": state.file_count(),
+                "subjects": state.reports().len(),
+            })
+        );
+    } else {
+        println!(
+            "Watching {} .qual file(s) under {} (Ctrl-C to stop)",
+            state.file_count(),
+            root.display()
+        );
+    }
+
+    while let Ok(first) = rx.recv() {
+        // Collect the whole burst, then re-score once.
+        let mut events = vec![first];
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            events.push(event);
+        }
+
+        let mut candidates: BTreeSet<PathBuf> = BTreeSet::new();
+        for event in events {
+            match event {
+                Ok(event) => candidates.extend(
+                    event
+                        .paths
+                        .into_iter()
+                        .filter(|p| *p == graph_path || qual_file::is_qual_path(p)),
+                ),
+                Err(e) => report_error(json, &watch_error(e)),
+            }
+        }
+        if candidates.is_empty() {
+            continue;
+        }
+
+        // New files only count if discovery would pick them up.
+        let discoverable: BTreeSet<PathBuf> = if candidates
+            .iter()
+            .any(|p| *p != graph_path && !state.is_tracked(p))
+        {
+            match qual_file::discover_paths(&root, !args.no_ignore) {
+                Ok(paths) => paths.into_iter().collect(),
+                Err(e) => {
+                    report_error(json, &e);
+                    continue;
+                }
+            }
+        } else {
+            BTreeSet::new()
+        };
+        let changed: Vec<PathBuf> = candidates
+            .into_iter()
+            .filter(|p| *p == graph_path || state.is_tracked(p) || discoverable.contains(p))
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+
+        match state.update_with(&changed, evaluate) {
+            Ok(deltas) => {
+                for delta in &deltas {
+                    if json {
+                        println!("{}", output::delta_json(delta));
+                    } else {
+                        println!("{}", output::format_delta(delta));
+                    }
+                }
+            }
+            Err(e) => report_error(json, &e),
+        }
+    }
+
+    Ok(())
+}
+
+fn watch_error(e: notify::Error) -> crate::Error {
+    crate::Error::Io(std::io::Error::other(e))
+}
+
+/// Report a recoverable error without stopping the watcher.
+fn report_error(json: bool, e: &crate::Error) {
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({"event": "error", "message": e.to_string()})
+        );
+    } else {
+        eprintln!("error: {e}");
+    }
+}
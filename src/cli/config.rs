@@ -1,6 +1,8 @@
 use figment::Figment;
 use figment::providers::{Env, Format, Serialized, Toml};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
 
 /// Qualifier configuration, merged from multiple sources via figment.
@@ -13,7 +15,7 @@ use std::path::{Path, PathBuf};
 /// 5. Defaults
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// Path to the dependency graph file.
+    /// Path to the dependency graph file, relative to the project root.
     #[serde(default = "default_graph_path")]
     pub graph: PathBuf,
 
@@ -28,12 +30,77 @@ pub struct Config {
     /// Minimum score threshold for `qualifier check`.
     #[serde(default)]
     pub min_score: i32,
+
+    /// Path to the keyring of trusted public keys, relative to the project root.
+    #[serde(default = "default_keyring_path")]
+    pub keyring: PathBuf,
+
+    /// Path to the Ed25519 signing key used by `qualifier sign`.
+    #[serde(default)]
+    pub signing_key: Option<PathBuf>,
+
+    /// Per-kind score decay (`[decay.<kind>]` tables). Empty disables decay.
+    #[serde(default)]
+    pub decay: crate::scoring::DecayModel,
+
+    /// SARIF import settings (`[sarif]` table).
+    #[serde(default)]
+    pub sarif: SarifConfig,
+
+    /// Stale-record scoring (`[stale]` table).
+    #[serde(default)]
+    pub stale: StaleConfig,
+
+    /// Issuer trust weights and subject allowlists (`[trust]` table).
+    #[serde(default)]
+    pub trust: crate::trust::TrustModel,
+
+    /// Project-defined kinds (`[kinds.<name>]` tables).
+    #[serde(default)]
+    pub kinds: crate::kinds::KindRegistry,
+
+    /// Reject kinds that are neither built in nor declared in `kinds`.
+    #[serde(default)]
+    pub strict_kinds: bool,
+
+    /// Per-dimension settings (`[dimensions.<tag>]` tables).
+    #[serde(default)]
+    pub dimensions: BTreeMap<String, DimensionConfig>,
+}
+
+/// The `[sarif]` configuration table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SarifConfig {
+    /// Score assigned to each SARIF level by `qualifier import-sarif`.
+    #[serde(default)]
+    pub levels: crate::sarif::LevelScores,
+}
+
+/// A `[dimensions.<tag>]` configuration table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DimensionConfig {
+    /// Minimum effective score in this dimension for `qualifier check`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<i32>,
+}
+
+/// The `[stale]` configuration table.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StaleConfig {
+    /// Percentage of its score a record keeps once its pinned content
+    /// changes (`0` excludes it). Unset leaves stale records at full weight.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u8>,
 }
 
 fn default_graph_path() -> PathBuf {
     PathBuf::from("qualifier.graph.jsonl")
 }
 
+fn default_keyring_path() -> PathBuf {
+    PathBuf::from("qualifier.keys.jsonl")
+}
+
 fn default_format() -> String {
     "human".into()
 }
@@ -45,54 +112,308 @@ impl Default for Config {
             issuer: None,
             format: default_format(),
             min_score: 0,
+            keyring: default_keyring_path(),
+            signing_key: None,
+            decay: crate::scoring::DecayModel::default(),
+            sarif: SarifConfig::default(),
+            stale: StaleConfig::default(),
+            trust: crate::trust::TrustModel::default(),
+            kinds: crate::kinds::KindRegistry::default(),
+            strict_kinds: false,
+            dimensions: BTreeMap::new(),
         }
     }
 }
 
-/// Load configuration by merging all sources.
-pub fn load(project_root: Option<&Path>) -> Config {
-    let mut figment = Figment::new().merge(Serialized::defaults(Config::default()));
+/// Values given explicitly on the command line. Unset flags fall through
+/// to the lower-precedence layers.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Overrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyring: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+}
 
-    // User-level config: ~/.config/qualifier/config.toml
-    if let Ok(home) = std::env::var("HOME") {
-        let user_config = PathBuf::from(home)
+/// The configuration keys, in display order.
+pub const KEYS: [&str; 16] = [
+    "graph",
+    "issuer",
+    "format",
+    "min_score",
+    "keyring",
+    "signing_key",
+    "decay",
+    "sarif.levels.error",
+    "sarif.levels.warning",
+    "sarif.levels.note",
+    "sarif.levels.none",
+    "stale.weight",
+    "trust",
+    "kinds",
+    "strict_kinds",
+    "dimensions",
+];
+
+/// The configuration layer an effective value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layer {
+    Cli,
+    Env,
+    Project(PathBuf),
+    User(PathBuf),
+    Default,
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Layer::Cli => write!(f, "cli"),
+            Layer::Env => write!(f, "env"),
+            Layer::Project(path) => write!(f, "project ({})", path.display()),
+            Layer::User(path) => write!(f, "user ({})", path.display()),
+            Layer::Default => write!(f, "default"),
+        }
+    }
+}
+
+fn user_config_path() -> Option<PathBuf> {
+    std::env::var("HOME").ok().map(|home| {
+        PathBuf::from(home)
             .join(".config")
             .join("qualifier")
-            .join("config.toml");
+            .join("config.toml")
+    })
+}
+
+/// Default location of the signing key: `~/.config/qualifier/signing.key`.
+pub fn default_signing_key_path() -> Option<PathBuf> {
+    std::env::var("HOME").ok().map(|home| {
+        PathBuf::from(home)
+            .join(".config")
+            .join("qualifier")
+            .join("signing.key")
+    })
+}
+
+fn project_config_path(project_root: Option<&Path>) -> Option<PathBuf> {
+    project_root.map(|root| root.join(".qualifier.toml"))
+}
+
+/// Make CLI path values independent of the working directory, since the
+/// merged values are later resolved against the project root.
+fn absolutize_overrides(overrides: &Overrides) -> Overrides {
+    let mut overrides = overrides.clone();
+    for path in [
+        &mut overrides.graph,
+        &mut overrides.keyring,
+        &mut overrides.signing_key,
+    ] {
+        if let Some(p) = path.as_ref()
+            && Path::new(p).is_relative()
+            && let Ok(cwd) = std::env::current_dir()
+        {
+            *path = Some(cwd.join(p).to_string_lossy().into_owned());
+        }
+    }
+    overrides
+}
+
+fn figment(project_root: Option<&Path>, overrides: &Overrides) -> Figment {
+    let mut figment = Figment::new().merge(Serialized::defaults(Config::default()));
+
+    // User-level config: ~/.config/qualifier/config.toml
+    if let Some(user_config) = user_config_path() {
         figment = figment.merge(Toml::file(user_config));
     }
 
     // Project-level config: <root>/.qualifier.toml
-    if let Some(root) = project_root {
-        let project_config = root.join(".qualifier.toml");
+    if let Some(project_config) = project_config_path(project_root) {
         figment = figment.merge(Toml::file(project_config));
     }
 
     // Environment variables: QUALIFIER_GRAPH, QUALIFIER_ISSUER, etc.
     figment = figment.merge(Env::prefixed("QUALIFIER_"));
 
-    figment.extract().unwrap_or_default()
+    // CLI flags
+    figment.merge(Serialized::defaults(absolutize_overrides(overrides)))
 }
 
-/// Load the dependency graph, falling back to an empty graph.
+/// Load configuration by merging all sources, with `overrides` on top.
+pub fn load(project_root: Option<&Path>, overrides: &Overrides) -> crate::Result<Config> {
+    figment(project_root, overrides)
+        .extract()
+        .map_err(|e| crate::Error::Validation(format!("invalid configuration: {e}")))
+}
+
+/// Report which layer supplied the effective value of each key in [`KEYS`].
+pub fn sources(project_root: Option<&Path>, overrides: &Overrides) -> Vec<(&'static str, Layer)> {
+    let cli = Figment::from(Serialized::defaults(overrides.clone()));
+    let env = Figment::from(Env::prefixed("QUALIFIER_"));
+    let project = project_config_path(project_root).map(|p| (Figment::from(Toml::file(&p)), p));
+    let user = user_config_path().map(|p| (Figment::from(Toml::file(&p)), p));
+
+    KEYS.iter()
+        .map(|&key| {
+            let layer = if cli.contains(key) {
+                Layer::Cli
+            } else if env.contains(key) {
+                Layer::Env
+            } else if let Some((ref f, ref p)) = project
+                && f.contains(key)
+            {
+                Layer::Project(p.clone())
+            } else if let Some((ref f, ref p)) = user
+                && f.contains(key)
+            {
+                Layer::User(p.clone())
+            } else {
+                Layer::Default
+            };
+            (key, layer)
+        })
+        .collect()
+}
+
+/// Build the scoring [`Evaluation`](crate::scoring::Evaluation) from the
+/// configured decay model and trust weights. `at` is an RFC 3339 timestamp;
+/// it defaults to now.
+pub fn evaluation(config: &Config, at: Option<&str>) -> crate::Result<crate::scoring::Evaluation> {
+    let at = match at {
+        Some(s) => parse_timestamp(s)
+            .map_err(|e| crate::Error::Validation(format!("invalid --at timestamp: {e}")))?,
+        None => chrono::Utc::now(),
+    };
+    let trust = crate::trust::Trust::new(config.trust.clone())
+        .map_err(|e| crate::Error::Validation(format!("invalid configuration: {e}")))?;
+    Ok(crate::scoring::Evaluation::at(at)
+        .with_decay(config.decay.clone())
+        .with_trust(trust))
+}
+
+/// The [`Staleness`](crate::scoring::Staleness) to score with. Empty unless
+/// `[stale] weight` is configured, since finding stale records reads every
+/// pinned subject under `root`.
+pub fn staleness(
+    config: &Config,
+    root: &Path,
+    qual_files: &[crate::qual_file::QualFile],
+) -> crate::Result<crate::scoring::Staleness> {
+    let Some(weight) = config.stale.weight else {
+        return Ok(crate::scoring::Staleness::default());
+    };
+    if weight > 100 {
+        return Err(crate::Error::Validation(format!(
+            "invalid configuration: stale.weight {weight} is out of range [0, 100]"
+        )));
+    }
+    let records: Vec<crate::attestation::Record> = qual_files
+        .iter()
+        .flat_map(|qf| qf.records.iter().cloned())
+        .collect();
+    Ok(crate::pin::staleness(root, &records, weight))
+}
+
+/// The declared kinds, after checking that each entry is consistent.
+pub fn kinds(config: &Config) -> crate::Result<&crate::kinds::KindRegistry> {
+    config
+        .kinds
+        .validate()
+        .map_err(|e| crate::Error::Validation(format!("invalid configuration: {e}")))?;
+    Ok(&config.kinds)
+}
+
+/// Parse an RFC 3339 timestamp, or a `YYYY-MM-DD` date meaning midnight UTC.
+pub fn parse_timestamp(s: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    if let Ok(at) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(at.with_timezone(&chrono::Utc));
+    }
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| format!("'{s}' is not an RFC 3339 timestamp or YYYY-MM-DD date"))
+}
+
+/// Parse a non-negative duration such as `30d`, `2w`, or `12h`.
+pub fn parse_duration(s: &str) -> Result<chrono::Duration, String> {
+    let invalid = || format!("'{s}' is not a duration (e.g. 30d, 2w, 12h)");
+    let (split, _) = s.char_indices().last().ok_or_else(invalid)?;
+    let (count, unit) = s.split_at(split);
+    if count.starts_with(['-', '+']) {
+        return Err(invalid());
+    }
+    let count: i64 = count.parse().map_err(|_| invalid())?;
+    match unit {
+        "h" => chrono::Duration::try_hours(count),
+        "d" => chrono::Duration::try_days(count),
+        "w" => chrono::Duration::try_weeks(count),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+/// Resolve a configured path against the project root.
+pub fn resolve_path(path: &Path, root: Option<&Path>) -> PathBuf {
+    match root {
+        Some(root) => root.join(path),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Files under the project root that qualifier reads or writes itself,
+/// rather than artifacts: the config and ignore files, the graph file, the
+/// keyring, and the signing key.
+pub fn managed_paths(config: &Config, root: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![
+        root.join(".qualifier.toml"),
+        root.join(".qualignore"),
+        resolve_path(&config.graph, Some(root)),
+        resolve_path(&config.keyring, Some(root)),
+    ];
+    paths.extend(
+        config
+            .signing_key
+            .as_deref()
+            .map(|key| resolve_path(key, Some(root))),
+    );
+    paths
+}
+
+/// Discover the `.qual` files under `root`. With `lenient`, malformed lines
+/// are skipped and reported on stderr instead of failing.
+pub fn discover(
+    root: &Path,
+    respect_ignore: bool,
+    lenient: bool,
+) -> crate::Result<Vec<crate::qual_file::QualFile>> {
+    if !lenient {
+        return crate::qual_file::discover(root, respect_ignore);
+    }
+    let (qual_files, diagnostics) = crate::qual_file::discover_lenient(root, respect_ignore)?;
+    for diagnostic in &diagnostics {
+        eprintln!("warning: {diagnostic}");
+    }
+    if !diagnostics.is_empty() {
+        eprintln!("warning: skipped {} malformed line(s)", diagnostics.len());
+    }
+    Ok(qual_files)
+}
+
+/// Load the dependency graph from the graph file and `dependency` records.
 ///
-/// If `explicit_path` is set, loads from that path.
-/// Otherwise looks for `qualifier.graph.jsonl` under `root`.
+/// A relative `graph` path is resolved against `root`. A missing graph file
+/// is not an error; edges then come from `qual_files` alone.
 pub fn load_graph(
-    explicit_path: Option<&str>,
+    graph: &Path,
     root: Option<&Path>,
-) -> crate::graph::DependencyGraph {
-    if let Some(path) = explicit_path {
-        crate::graph::load(Path::new(path))
-            .unwrap_or_else(|_| crate::graph::DependencyGraph::empty())
-    } else if let Some(root) = root {
-        let default = root.join("qualifier.graph.jsonl");
-        if default.exists() {
-            crate::graph::load(&default).unwrap_or_else(|_| crate::graph::DependencyGraph::empty())
-        } else {
-            crate::graph::DependencyGraph::empty()
-        }
-    } else {
-        crate::graph::DependencyGraph::empty()
-    }
+    qual_files: &[crate::qual_file::QualFile],
+) -> crate::Result<crate::graph::DependencyGraph> {
+    crate::graph::build(Some(&resolve_path(graph, root)), qual_files)
 }
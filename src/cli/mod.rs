@@ -29,15 +29,43 @@ pub enum Commands {
     Check(commands::check::Args),
     /// Compact a .qual file
     Compact(commands::compact::Args),
+    /// Inspect the layered configuration
+    Config(commands::config_cmd::Args),
+    /// Manage dependency records
+    Dep(commands::dep::Args),
+    /// Export records in an interchange format
+    Export(commands::export::Args),
     /// Visualize the dependency graph
     Graph(commands::graph_cmd::Args),
     /// Print a random qualifier haiku
     Haiku,
+    /// Import records from in-toto Statements
+    Import(commands::import::Args),
+    /// Import SARIF findings as attestations
+    ImportSarif(commands::import_sarif::Args),
     /// Initialize qualifier in a repository
     Init,
+    /// Generate an Ed25519 signing key
+    Keygen(commands::keygen::Args),
+    /// Move records between .qual layouts
+    Layout(commands::layout::Args),
     /// Show who attested an artifact and why
     #[command(alias = "blame")]
     Praise(commands::praise::Args),
+    /// Move fingerprinted spans to where their lines are now
+    Reanchor(commands::reanchor::Args),
+    /// Rename a subject, carrying its history and dependency edges
+    Rename(commands::rename::Args),
+    /// Withdraw records with a revocation
+    Retract(commands::retract::Args),
+    /// Sign records with a local Ed25519 key
+    Sign(commands::sign::Args),
+    /// List attestations whose pinned content has changed
+    Stale(commands::stale::Args),
+    /// Verify record signatures against the keyring
+    Verify(commands::verify::Args),
+    /// Re-score continuously as .qual files change
+    Watch(commands::watch::Args),
 }
 
 pub fn run() {
@@ -59,13 +87,27 @@ pub fn run() {
         Commands::Ls(args) => commands::ls::run(args),
         Commands::Check(args) => commands::check::run(args),
         Commands::Compact(args) => commands::compact::run(args),
+        Commands::Config(args) => commands::config_cmd::run(args),
+        Commands::Dep(args) => commands::dep::run(args),
+        Commands::Export(args) => commands::export::run(args),
         Commands::Graph(args) => commands::graph_cmd::run(args),
         Commands::Haiku => {
             commands::haiku::run();
             Ok(())
         }
+        Commands::Import(args) => commands::import::run(args),
+        Commands::ImportSarif(args) => commands::import_sarif::run(args),
         Commands::Init => commands::init::run(),
+        Commands::Keygen(args) => commands::keygen::run(args),
+        Commands::Layout(args) => commands::layout::run(args),
         Commands::Praise(args) => commands::praise::run(args),
+        Commands::Reanchor(args) => commands::reanchor::run(args),
+        Commands::Rename(args) => commands::rename::run(args),
+        Commands::Retract(args) => commands::retract::run(args),
+        Commands::Sign(args) => commands::sign::run(args),
+        Commands::Stale(args) => commands::stale::run(args),
+        Commands::Verify(args) => commands::verify::run(args),
+        Commands::Watch(args) => commands::watch::run(args),
     };
 
     if let Err(e) = result {
@@ -1,7 +1,10 @@
 use comfy_table::{Cell, CellAlignment, Color, Table};
 
-use crate::attestation::Record;
-use crate::scoring::{self, ScoreReport};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::attestation::{Record, RevocationRecord, Span};
+use crate::scoring::{self, ScoreReport, SpanRegion};
+use crate::watch::ScoreDelta;
 
 /// Format a score for human display: `[+40]` or `[-30]` or `[  0]`.
 pub fn format_score(score: i32) -> String {
@@ -74,14 +77,200 @@ pub fn scores_json(reports: &[(String, ScoreReport)]) -> String {
     serde_json::to_string_pretty(&entries).unwrap_or_default()
 }
 
+/// Build a comfy-table of span regions for `qualifier score --spans`.
+pub fn span_table(regions: &[SpanRegion]) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec!["LINES", "RAW", "", "RECORDS"]);
+
+    for region in regions {
+        let lines = if region.start_line == region.end_line {
+            format!("L{}", region.start_line)
+        } else {
+            format!("L{}\u{2013}{}", region.start_line, region.end_line)
+        };
+        table.add_row(vec![
+            Cell::new(lines),
+            Cell::new(region.raw)
+                .set_alignment(CellAlignment::Right)
+                .fg(score_color(region.raw)),
+            Cell::new(scoring::score_bar(region.raw, 10)),
+            Cell::new(region.records.len()).set_alignment(CellAlignment::Right),
+        ]);
+    }
+
+    table
+}
+
+/// JSON output for `qualifier score --spans`: each score entry gains a
+/// `spans` array of regions.
+pub fn scores_with_spans_json(reports: &[(String, ScoreReport, Vec<SpanRegion>)]) -> String {
+    let entries: Vec<serde_json::Value> = reports
+        .iter()
+        .map(|(subject, report, regions)| {
+            let spans: Vec<serde_json::Value> = regions
+                .iter()
+                .map(|region| {
+                    serde_json::json!({
+                        "start_line": region.start_line,
+                        "end_line": region.end_line,
+                        "raw_score": region.raw,
+                        "records": region.records,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "subject": subject,
+                "raw_score": report.raw,
+                "effective_score": report.effective,
+                "status": scoring::score_status(report),
+                "limiting_path": report.limiting_path,
+                "spans": spans,
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&entries).unwrap_or_default()
+}
+
+/// One-line description of a score change for `qualifier watch`, e.g.
+/// `lib/auth 60 → -20 (limited by lib/crypto)`.
+pub fn format_delta(delta: &ScoreDelta) -> String {
+    let effective = |r: &Option<ScoreReport>| match r {
+        Some(r) => r.effective.to_string(),
+        None => "none".into(),
+    };
+    let limited = match delta.after.as_ref().and_then(|r| r.limiting_path.as_ref()) {
+        Some(path) => format!(" (limited by {})", path.join(" -> ")),
+        None => String::new(),
+    };
+    format!(
+        "{} {} \u{2192} {}{}",
+        delta.subject,
+        effective(&delta.before),
+        effective(&delta.after),
+        limited
+    )
+}
+
+/// Single-line JSON event for a score change in `qualifier watch`.
+pub fn delta_json(delta: &ScoreDelta) -> String {
+    let before = delta.before.as_ref().map(|r| {
+        serde_json::json!({
+            "raw_score": r.raw,
+            "effective_score": r.effective,
+        })
+    });
+    let after = delta.after.as_ref().map(|r| {
+        serde_json::json!({
+            "raw_score": r.raw,
+            "effective_score": r.effective,
+            "status": scoring::score_status(r),
+            "limiting_path": r.limiting_path,
+        })
+    });
+    serde_json::json!({
+        "event": "score",
+        "subject": delta.subject,
+        "before": before,
+        "after": after,
+    })
+    .to_string()
+}
+
+/// The nominal and decayed score of one active record, and the trust
+/// weighting (in percent) included in `decayed`.
+#[derive(serde::Serialize)]
+pub struct Contribution {
+    pub id: String,
+    pub nominal: i32,
+    pub decayed: i32,
+    pub weight: u32,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub weight_reasons: Vec<String>,
+}
+
 /// JSON output for a single artifact show.
-pub fn show_json(subject: &str, report: &ScoreReport, records: &[Record]) -> String {
-    serde_json::to_string_pretty(&serde_json::json!({
+pub fn show_json(
+    subject: &str,
+    report: &ScoreReport,
+    records: &[Record],
+    contributions: &[Contribution],
+    revoked: Option<&HashMap<&str, &RevocationRecord>>,
+    live_spans: &HashMap<&str, Option<Span>>,
+) -> String {
+    let mut value = serde_json::json!({
         "subject": subject,
         "raw_score": report.raw,
         "effective_score": report.effective,
         "limiting_path": report.limiting_path,
         "records": records,
-    }))
-    .unwrap_or_default()
+        "contributions": contributions,
+    });
+    if let Some(revoked) = revoked {
+        let revoked: BTreeMap<&str, serde_json::Value> = revoked
+            .iter()
+            .map(|(id, rev)| (*id, revoked_by_json(rev)))
+            .collect();
+        value["revoked"] = serde_json::json!(revoked);
+    }
+    if !live_spans.is_empty() {
+        let live: BTreeMap<&str, &Option<Span>> =
+            live_spans.iter().map(|(id, span)| (*id, span)).collect();
+        value["live_spans"] = serde_json::json!(live);
+    }
+    serde_json::to_string_pretty(&value).unwrap_or_default()
+}
+
+/// One line describing when `record` expires relative to `at`, e.g.
+/// `  src/a.rs  waiver "accepted risk"  expires 2026-12-01`.
+pub fn format_expiry(record: &Record, at: chrono::DateTime<chrono::Utc>) -> String {
+    let kind = record.kind().map(|k| k.to_string()).unwrap_or_default();
+    let summary = record
+        .as_attestation()
+        .map(|a| a.body.summary.as_str())
+        .unwrap_or_default();
+    let verb = if record.is_expired(at) {
+        "expired"
+    } else {
+        "expires"
+    };
+    let date = record
+        .expires_at()
+        .map(|e| e.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    format!(
+        "  {}  {} {:?}  {} {}",
+        record.subject(),
+        kind,
+        summary,
+        verb,
+        date
+    )
+}
+
+/// The `revoked_by` object attached to a withdrawn record in JSON output.
+pub fn revoked_by_json(revocation: &RevocationRecord) -> serde_json::Value {
+    serde_json::json!({
+        "id": revocation.id,
+        "issuer": revocation.issuer,
+        "summary": revocation.body.summary,
+        "created_at": revocation.created_at.to_rfc3339(),
+    })
+}
+
+/// `  [revoked by abcd1234: reason]` for human output.
+pub fn revoked_note(revocation: &RevocationRecord) -> String {
+    let id_short = revocation.id.get(..8).unwrap_or(&revocation.id);
+    format!("  [revoked by {id_short}: {:?}]", revocation.body.summary)
+}
+
+/// A line range as `L42` or `L42–58`.
+pub fn format_span(span: &Span) -> String {
+    let start = span.start.line;
+    let end = span.end_or_start().line;
+    if start == end {
+        format!("L{start}")
+    } else {
+        format!("L{start}\u{2013}{end}")
+    }
 }
@@ -1,11 +1,12 @@
-use std::collections::HashMap;
-
-use chrono::Utc;
+use std::collections::{HashMap, HashSet};
 
 use crate::attestation::{self, Epoch, EpochBody, IssuerType, Record};
 use crate::qual_file::QualFile;
 use crate::scoring;
 
+/// Issuer of the epochs written by a snapshot.
+pub const COMPACT_ISSUER: &str = "urn:qualifier:compact";
+
 /// Result of a compaction operation.
 #[derive(Debug, Clone)]
 pub struct CompactResult {
@@ -17,19 +18,24 @@ pub struct CompactResult {
     pub pruned: usize,
 }
 
-/// Prune superseded records, keeping only chain tips.
+/// Prune superseded and revoked records, keeping only chain tips.
 ///
 /// The raw score of the artifact is preserved as an invariant.
-/// Non-attestation records (epochs, dependencies, unknowns) are always kept.
+/// Non-attestation records (epochs, dependencies, unknowns) are always kept,
+/// except revocations whose targets were all in this file and are now gone.
 pub fn prune(qual_file: &QualFile) -> (QualFile, CompactResult) {
     let before = qual_file.records.len();
-    let active = scoring::filter_superseded(&qual_file.records);
-    let after = active.len();
+    let records: Vec<Record> = scoring::filter_superseded(&qual_file.records)
+        .into_iter()
+        .filter(|r| !is_spent_revocation(r, &qual_file.records))
+        .cloned()
+        .collect();
+    let after = records.len();
 
     let pruned_file = QualFile {
         path: qual_file.path.clone(),
         subject: qual_file.subject.clone(),
-        records: active.into_iter().cloned().collect(),
+        records,
     };
 
     let result = CompactResult {
@@ -41,13 +47,92 @@ pub fn prune(qual_file: &QualFile) -> (QualFile, CompactResult) {
     (pruned_file, result)
 }
 
-/// Collapse all scored records into epoch records — one per distinct subject.
+/// Returns true if `record` is a revocation whose targets are all in
+/// `records`, so dropping the targets leaves it with nothing to revoke.
+pub fn is_spent_revocation(record: &Record, records: &[Record]) -> bool {
+    record.as_revocation().is_some_and(|rev| {
+        rev.body
+            .revokes
+            .iter()
+            .all(|id| records.iter().any(|r| r.id() == id))
+    })
+}
+
+/// Returns true if `record` must keep its own line instead of being folded
+/// into an epoch: a scored record whose contribution under `eval` can differ
+/// from its nominal score, now or later.
+///
+/// An epoch carries one fixed score. It never expires or decays, carries no
+/// tags, and is never marked stale (SPEC §3.3). Folding an attestation that
+/// relies on any of those, or a record weighted by trust, would change
+/// scores.
+pub fn keeps_own_weight(record: &Record, eval: &scoring::Evaluation) -> bool {
+    record.is_scored()
+        && (record.expires_at().is_some_and(|e| e > eval.at)
+            || eval.decay_rule(record).is_some()
+            || !record.tags().is_empty()
+            || eval.stale.ids.contains(record.id())
+            || eval.trust.weigh(record).percent != 100)
+}
+
+/// Split a subject's scored records into those to fold into an epoch issued
+/// by `epoch_issuer` and the IDs of active records to keep as they are (see
+/// [`keeps_own_weight`]).
+///
+/// If the epoch itself would be weighted by trust, or the folded part would
+/// be clamped while kept records still count beside it, the epoch could not
+/// reproduce the subject's raw score, so every active record is kept and
+/// only inactive ones are folded.
+pub(crate) fn split_fold<'a>(
+    records: &[&'a Record],
+    inactive: &HashSet<&str>,
+    eval: &scoring::Evaluation,
+    epoch_issuer: &str,
+) -> (Vec<&'a Record>, HashSet<&'a str>) {
+    let active: Vec<&'a Record> = records
+        .iter()
+        .copied()
+        .filter(|r| !inactive.contains(r.id()))
+        .collect();
+    let mut kept: HashSet<&str> = active
+        .iter()
+        .filter(|r| keeps_own_weight(r, eval))
+        .map(|r| r.id())
+        .collect();
+    let folded_sum = active
+        .iter()
+        .filter(|r| !kept.contains(r.id()))
+        .filter_map(|r| scoring::contribution(r, eval))
+        .fold(0i32, |acc, s| acc.saturating_add(s));
+    let epoch_weight = records.first().map_or(100, |r| {
+        eval.trust
+            .weigh_issuer(r.subject(), epoch_issuer, Some(&IssuerType::Tool))
+            .percent
+    });
+    if epoch_weight != 100
+        || (!kept.is_empty() && attestation::clamp_score(folded_sum) != folded_sum)
+    {
+        kept = active.iter().map(|r| r.id()).collect();
+    }
+    let folded = records
+        .iter()
+        .copied()
+        .filter(|r| !kept.contains(r.id()))
+        .collect();
+    (folded, kept)
+}
+
+/// Collapse scored records into epoch records — one per distinct subject.
 ///
-/// Each epoch record's score equals the raw score of its subject's
-/// non-superseded scored records, preserving the scoring invariant.
+/// Each epoch record's score equals the raw score under `eval` of the
+/// active (non-superseded, non-revoked) scored records it folds, so the
+/// subject's raw score under `eval` is unchanged.
 ///
-/// Non-scored records (dependencies, unknowns) are passed through unchanged.
-pub fn snapshot(qual_file: &QualFile) -> (QualFile, CompactResult) {
+/// Non-scored records (dependencies, unknowns) are passed through unchanged,
+/// as are active attestations whose contribution depends on more than their
+/// score (see [`keeps_own_weight`]). Revocations are dropped once
+/// everything they revoke is folded into an epoch.
+pub fn snapshot(qual_file: &QualFile, eval: &scoring::Evaluation) -> (QualFile, CompactResult) {
     let before = qual_file.records.len();
 
     if before == 0 {
@@ -61,31 +146,36 @@ pub fn snapshot(qual_file: &QualFile) -> (QualFile, CompactResult) {
         );
     }
 
-    // Separate scored records from passthrough (non-scored)
+    let inactive = scoring::inactive_ids(&qual_file.records);
     let mut by_subject: HashMap<&str, Vec<&Record>> = HashMap::new();
-    let mut passthrough: Vec<Record> = Vec::new();
-
-    for record in &qual_file.records {
-        if record.is_scored() {
-            by_subject.entry(record.subject()).or_default().push(record);
-        } else {
-            passthrough.push(record.clone());
-        }
+    for record in qual_file.records.iter().filter(|r| r.is_scored()) {
+        by_subject.entry(record.subject()).or_default().push(record);
     }
 
+    let mut kept: HashSet<&str> = HashSet::new();
     let mut epoch_records = Vec::new();
     for (subject, records) in &by_subject {
-        let raw = scoring::raw_score_from_refs(records);
-        let refs: Vec<String> = records.iter().map(|r| r.id().to_string()).collect();
-        let count = records.len();
+        let (folded, keep) = split_fold(records, &inactive, eval, COMPACT_ISSUER);
+        kept.extend(keep);
+        if folded.is_empty() {
+            continue;
+        }
+        let active: Vec<&Record> = folded
+            .iter()
+            .copied()
+            .filter(|r| !inactive.contains(r.id()))
+            .collect();
+        let raw = scoring::raw_score_from_refs(&active, eval);
+        let refs: Vec<String> = folded.iter().map(|r| r.id().to_string()).collect();
+        let count = folded.len();
 
         let epoch = attestation::finalize_epoch(Epoch {
             metabox: "1".into(),
             record_type: "epoch".into(),
             subject: subject.to_string(),
-            issuer: "urn:qualifier:compact".into(),
+            issuer: COMPACT_ISSUER.into(),
             issuer_type: Some(IssuerType::Tool),
-            created_at: Utc::now(),
+            created_at: eval.at,
             id: String::new(),
             body: EpochBody {
                 refs,
@@ -100,8 +190,20 @@ pub fn snapshot(qual_file: &QualFile) -> (QualFile, CompactResult) {
     // Sort by subject name for deterministic output
     epoch_records.sort_by(|a, b| a.subject().cmp(b.subject()));
 
-    // Append passthrough records
-    epoch_records.extend(passthrough);
+    // Append passthrough records in file order
+    epoch_records.extend(
+        qual_file
+            .records
+            .iter()
+            .filter(|r| {
+                if r.is_scored() {
+                    kept.contains(r.id())
+                } else {
+                    !is_spent_revocation(r, &qual_file.records)
+                }
+            })
+            .cloned(),
+    );
 
     let after = epoch_records.len();
     let snapshot_file = QualFile {
@@ -126,6 +228,15 @@ mod tests {
     use chrono::Utc;
     use std::path::PathBuf;
 
+    /// Evaluation without decay, at a fixed instant.
+    fn nominal() -> scoring::Evaluation {
+        scoring::Evaluation::at(
+            chrono::DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
     fn make_att(subject: &str, kind: Kind, score: i32, summary: &str) -> Attestation {
         attestation::finalize(Attestation {
             metabox: "1".into(),
@@ -139,6 +250,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind,
                 r#ref: None,
                 score,
@@ -146,6 +260,7 @@ mod tests {
                 suggested_fix: None,
                 summary: summary.into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         })
@@ -168,6 +283,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score,
@@ -175,6 +293,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "updated".into(),
                 supersedes: Some(supersedes_id.into()),
+                symbol: None,
                 tags: vec![],
             },
         })))
@@ -229,9 +348,9 @@ mod tests {
         let extra = make_record("test.rs", Kind::Praise, 20, "nice");
 
         let qf = make_qual_file(vec![original, replacement, extra]);
-        let score_before = scoring::raw_score(&qf.records);
+        let score_before = scoring::raw_score(&qf.records, &nominal());
         let (pruned, _) = prune(&qf);
-        let score_after = scoring::raw_score(&pruned.records);
+        let score_after = scoring::raw_score(&pruned.records, &nominal());
 
         assert_eq!(score_before, score_after, "prune must preserve raw score");
     }
@@ -239,7 +358,7 @@ mod tests {
     #[test]
     fn test_snapshot_empty() {
         let qf = make_qual_file(vec![]);
-        let (snapped, result) = snapshot(&qf);
+        let (snapped, result) = snapshot(&qf, &nominal());
         assert_eq!(result.before, 0);
         assert_eq!(result.after, 0);
         assert!(snapped.records.is_empty());
@@ -252,7 +371,7 @@ mod tests {
             make_record("test.rs", Kind::Concern, -10, "meh"),
         ];
         let qf = make_qual_file(records);
-        let (snapped, result) = snapshot(&qf);
+        let (snapped, result) = snapshot(&qf, &nominal());
 
         assert_eq!(result.before, 2);
         assert_eq!(result.after, 1);
@@ -271,9 +390,9 @@ mod tests {
         let extra = make_record("test.rs", Kind::Praise, 20, "nice");
 
         let qf = make_qual_file(vec![original, replacement, extra]);
-        let score_before = scoring::raw_score(&qf.records);
-        let (snapped, _) = snapshot(&qf);
-        let score_after = scoring::raw_score(&snapped.records);
+        let score_before = scoring::raw_score(&qf.records, &nominal());
+        let (snapped, _) = snapshot(&qf, &nominal());
+        let score_after = scoring::raw_score(&snapped.records, &nominal());
 
         assert_eq!(
             score_before, score_after,
@@ -288,10 +407,10 @@ mod tests {
         let c = make_superseding("test.rs", 10, b.id());
 
         let qf = make_qual_file(vec![a, b, c]);
-        let score_before = scoring::raw_score(&qf.records);
+        let score_before = scoring::raw_score(&qf.records, &nominal());
         assert_eq!(score_before, 10);
 
-        let (snapped, _) = snapshot(&qf);
+        let (snapped, _) = snapshot(&qf, &nominal());
         assert_eq!(snapped.records.len(), 1);
         assert_eq!(snapped.records[0].as_epoch().unwrap().body.score, 10);
         assert_eq!(snapped.records[0].as_epoch().unwrap().body.refs.len(), 3);
@@ -342,9 +461,9 @@ mod tests {
         let e_id = e.id().to_string();
 
         let qf = make_qual_file(vec![a, b, c, d, e]);
-        let score_before = scoring::raw_score(&qf.records);
+        let score_before = scoring::raw_score(&qf.records, &nominal());
         let (pruned, result) = prune(&qf);
-        let score_after = scoring::raw_score(&pruned.records);
+        let score_after = scoring::raw_score(&pruned.records, &nominal());
 
         assert_eq!(result.after, 1);
         assert_eq!(pruned.records[0].id(), e_id);
@@ -355,7 +474,7 @@ mod tests {
     fn test_snapshot_single_record() {
         let records = vec![make_record("test.rs", Kind::Praise, 40, "good")];
         let qf = make_qual_file(records);
-        let (snapped, result) = snapshot(&qf);
+        let (snapped, result) = snapshot(&qf, &nominal());
 
         assert_eq!(result.before, 1);
         assert_eq!(result.after, 1);
@@ -378,7 +497,7 @@ mod tests {
             records,
         };
 
-        let (snapped, result) = snapshot(&qf);
+        let (snapped, result) = snapshot(&qf, &nominal());
 
         assert_eq!(result.before, 3);
         assert_eq!(result.after, 2);
@@ -428,4 +547,212 @@ mod tests {
         assert!(pruned.records.iter().any(|r| r.id() == a2_id));
         assert!(pruned.records.iter().any(|r| r.id() == b1_id));
     }
+
+    fn make_revocation(revokes: &str) -> Record {
+        attestation::finalize_record(Record::Revocation(attestation::RevocationRecord {
+            metabox: "1".into(),
+            record_type: "revocation".into(),
+            subject: "test.rs".into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: nominal().at,
+            id: String::new(),
+            body: attestation::RevocationBody {
+                revokes: vec![revokes.into()],
+                summary: "wrong".into(),
+            },
+        }))
+    }
+
+    #[test]
+    fn test_prune_drops_revoked_records_and_spent_revocations() {
+        let keep = make_record("test.rs", Kind::Pass, 20, "ok");
+        let wrong = make_record("test.rs", Kind::Blocker, -80, "wrong file");
+        let revocation = make_revocation(wrong.id());
+        // Revokes a record that lives in another file, so it must stay.
+        let elsewhere = make_revocation("ffffffff");
+        let qf = make_qual_file(vec![keep.clone(), wrong, revocation, elsewhere.clone()]);
+
+        let (pruned, result) = prune(&qf);
+        let ids: Vec<&str> = pruned.records.iter().map(|r| r.id()).collect();
+        assert_eq!(ids, vec![keep.id(), elsewhere.id()]);
+        assert_eq!(result.pruned, 2);
+        assert_eq!(
+            scoring::raw_score(&pruned.records, &nominal()),
+            scoring::raw_score(&qf.records, &nominal())
+        );
+    }
+
+    #[test]
+    fn test_snapshot_excludes_revoked_records() {
+        let keep = make_record("test.rs", Kind::Pass, 20, "ok");
+        let wrong = make_record("test.rs", Kind::Blocker, -80, "wrong file");
+        let revocation = make_revocation(wrong.id());
+        let qf = make_qual_file(vec![keep, wrong, revocation]);
+
+        let (snap, _) = snapshot(&qf, &nominal());
+        assert_eq!(snap.records.len(), 1);
+        assert_eq!(snap.records[0].as_epoch().unwrap().body.score, 20);
+    }
+
+    #[test]
+    fn test_snapshot_keeps_records_that_have_not_expired() {
+        let keep = make_record("test.rs", Kind::Pass, 20, "ok");
+        let mut waiver = make_att("test.rs", Kind::Waiver, 10, "until next quarter");
+        waiver.body.expires_at = Some(Utc::now() + chrono::Duration::days(90));
+        let waiver = Record::Attestation(Box::new(attestation::finalize(waiver)));
+        let mut lapsed = make_att("test.rs", Kind::Waiver, 30, "lapsed");
+        lapsed.body.expires_at = Some(Utc::now() - chrono::Duration::days(1));
+        let lapsed = Record::Attestation(Box::new(attestation::finalize(lapsed)));
+        let qf = make_qual_file(vec![keep, waiver.clone(), lapsed]);
+
+        let now = scoring::Evaluation::at(Utc::now());
+        let (snap, _) = snapshot(&qf, &now);
+        assert_eq!(snap.records.len(), 2);
+        assert_eq!(snap.records[0].as_epoch().unwrap().body.score, 20);
+        assert_eq!(snap.records[1].id(), waiver.id());
+
+        assert_eq!(scoring::raw_score(&snap.records, &now), 30);
+        assert_eq!(
+            scoring::raw_score(&snap.records, &now),
+            scoring::raw_score(&qf.records, &now)
+        );
+        let later = scoring::Evaluation::at(Utc::now() + chrono::Duration::days(91));
+        assert_eq!(scoring::raw_score(&snap.records, &later), 20);
+    }
+
+    #[test]
+    fn test_snapshot_keeps_trust_weighted_and_stale_records() {
+        let maint = make_record("src/crypto/aes.rs", Kind::Concern, -10, "maint");
+        let mut bot = make_att("src/crypto/aes.rs", Kind::Praise, 50, "bot");
+        bot.issuer = "mailto:bot@test.com".into();
+        let bot = Record::Attestation(Box::new(attestation::finalize(bot)));
+        let stale = make_record("src/crypto/aes.rs", Kind::Pass, 20, "outdated");
+        let qf = make_qual_file(vec![bot.clone(), maint, stale.clone()]);
+
+        let trust = crate::trust::Trust::new(crate::trust::TrustModel {
+            subjects: vec![crate::trust::SubjectTrust {
+                pattern: "src/crypto/**".into(),
+                weight: None,
+                allow: vec!["mailto:test@test.com".into(), COMPACT_ISSUER.into()],
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        let eval = nominal()
+            .with_trust(trust)
+            .with_staleness(scoring::Staleness {
+                ids: [stale.id().to_string()].into(),
+                weight: 50,
+            });
+
+        let (snap, _) = snapshot(&qf, &eval);
+        assert_eq!(snap.records.len(), 3);
+        assert_eq!(snap.records[0].as_epoch().unwrap().body.score, -10);
+        assert_eq!(snap.records[1].id(), bot.id());
+        assert_eq!(snap.records[2].id(), stale.id());
+        assert_eq!(scoring::raw_score(&snap.records, &eval), 0);
+        assert_eq!(
+            scoring::raw_score(&snap.records, &eval),
+            scoring::raw_score(&qf.records, &eval)
+        );
+    }
+
+    #[test]
+    fn test_snapshot_folds_nothing_when_the_epoch_would_be_weighted() {
+        let maint = make_record("src/crypto/aes.rs", Kind::Concern, -10, "maint");
+        let fix = make_superseding("src/crypto/aes.rs", 20, maint.id());
+        let qf = make_qual_file(vec![maint.clone(), fix.clone()]);
+
+        let trust = crate::trust::Trust::new(crate::trust::TrustModel {
+            subjects: vec![crate::trust::SubjectTrust {
+                pattern: "src/crypto/**".into(),
+                weight: None,
+                allow: vec!["mailto:test@test.com".into()],
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        let eval = nominal().with_trust(trust);
+
+        let (snap, _) = snapshot(&qf, &eval);
+        let epoch = snap.records[0].as_epoch().unwrap();
+        assert_eq!(epoch.body.refs, vec![maint.id().to_string()]);
+        assert_eq!(epoch.body.score, 0);
+        assert_eq!(snap.records[1].id(), fix.id());
+        assert_eq!(scoring::raw_score(&snap.records, &eval), 20);
+        assert_eq!(
+            scoring::raw_score(&snap.records, &eval),
+            scoring::raw_score(&qf.records, &eval)
+        );
+    }
+
+    #[test]
+    fn test_snapshot_folds_nothing_when_the_epoch_would_clamp() {
+        let mut records = vec![
+            make_record("test.rs", Kind::Blocker, -100, "a"),
+            make_record("test.rs", Kind::Blocker, -100, "b"),
+        ];
+        let mut waiver = make_att("test.rs", Kind::Waiver, 100, "until release");
+        waiver.body.expires_at = Some(nominal().at + chrono::Duration::days(30));
+        records.push(Record::Attestation(Box::new(attestation::finalize(waiver))));
+        let qf = make_qual_file(records);
+
+        let (snap, _) = snapshot(&qf, &nominal());
+        assert_eq!(snap.records, qf.records);
+    }
+
+    #[test]
+    fn test_snapshot_keeps_decaying_records() {
+        let mut praise = make_att("test.rs", Kind::Praise, 80, "fast");
+        praise.created_at = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let praise = Record::Attestation(Box::new(attestation::finalize(praise)));
+        let pass = make_record("test.rs", Kind::Pass, 10, "ok");
+        let qf = make_qual_file(vec![praise.clone(), pass]);
+
+        let mut decay = scoring::DecayModel::default();
+        decay.0.insert(
+            "praise".into(),
+            scoring::DecayRule {
+                half_life_days: Some(180),
+                ..Default::default()
+            },
+        );
+        let eval = nominal().with_decay(decay.clone());
+
+        let (snap, _) = snapshot(&qf, &eval);
+        assert_eq!(snap.records.len(), 2);
+        assert_eq!(snap.records[0].as_epoch().unwrap().body.score, 10);
+        assert_eq!(snap.records[1].id(), praise.id());
+        assert_eq!(
+            scoring::raw_score(&snap.records, &eval),
+            scoring::raw_score(&qf.records, &eval)
+        );
+        let later =
+            scoring::Evaluation::at(eval.at + chrono::Duration::days(365)).with_decay(decay);
+        assert_eq!(
+            scoring::raw_score(&snap.records, &later),
+            scoring::raw_score(&qf.records, &later)
+        );
+    }
+
+    #[test]
+    fn test_snapshot_keeps_tagged_records_in_their_dimensions() {
+        let mut security = make_att("test.rs", Kind::Concern, -20, "unchecked input");
+        security.body.tags = vec!["security".into()];
+        let security = Record::Attestation(Box::new(attestation::finalize(security)));
+        let pass = make_record("test.rs", Kind::Pass, 30, "ok");
+        let qf = make_qual_file(vec![security.clone(), pass]);
+
+        let (snap, _) = snapshot(&qf, &nominal());
+        assert_eq!(snap.records.len(), 2);
+        assert_eq!(snap.records[0].as_epoch().unwrap().body.score, 30);
+        assert_eq!(snap.records[1].id(), security.id());
+
+        let dimension = nominal().with_dimension("security");
+        assert_eq!(scoring::raw_score(&snap.records, &dimension), -20);
+        assert_eq!(scoring::raw_score(&snap.records, &nominal()), 10);
+    }
 }
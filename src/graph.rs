@@ -5,6 +5,10 @@ use petgraph::algo;
 use petgraph::graph::{DiGraph, NodeIndex};
 use serde::Deserialize;
 
+use crate::attestation::Record;
+use crate::qual_file::QualFile;
+use crate::scoring;
+
 /// A dependency graph over qualified artifact names.
 #[derive(Debug, Clone)]
 pub struct DependencyGraph {
@@ -41,6 +45,33 @@ impl DependencyGraph {
         }
     }
 
+    /// Add a dependency edge `subject -> dependency`, creating nodes as needed.
+    ///
+    /// Adding an edge that already exists is a no-op, so edges declared by
+    /// several sources collapse into their union.
+    pub fn add_edge(&mut self, subject: &str, dependency: &str) {
+        let from = self.get_or_insert(subject);
+        let to = self.get_or_insert(dependency);
+        self.graph.update_edge(from, to, ());
+    }
+
+    /// Add the edges declared by every `dependency` record in `records`.
+    ///
+    /// Records of other types are ignored, as are dependency records revoked
+    /// by a revocation among `records`. Does not check for cycles.
+    pub fn add_dependency_records<'a>(&mut self, records: impl IntoIterator<Item = &'a Record>) {
+        let records: Vec<&Record> = records.into_iter().collect();
+        let inactive = scoring::inactive_ids(records.iter().copied());
+        for record in records.into_iter().filter(|r| !inactive.contains(r.id())) {
+            if let Record::Dependency(dep) = record {
+                self.get_or_insert(&dep.subject);
+                for target in &dep.body.depends_on {
+                    self.add_edge(&dep.subject, target);
+                }
+            }
+        }
+    }
+
     /// Return all artifact names in the graph.
     pub fn artifacts(&self) -> Vec<&str> {
         self.nodes.keys().map(|s| s.as_str()).collect()
@@ -58,6 +89,18 @@ impl DependencyGraph {
         }
     }
 
+    /// Return the artifacts that directly depend on `artifact`.
+    pub fn dependents(&self, artifact: &str) -> Vec<&str> {
+        match self.nodes.get(artifact) {
+            Some(&idx) => self
+                .graph
+                .neighbors_directed(idx, petgraph::Direction::Incoming)
+                .map(|n| self.graph[n].as_str())
+                .collect(),
+            None => vec![],
+        }
+    }
+
     /// Check if the graph contains an artifact.
     pub fn contains(&self, artifact: &str) -> bool {
         self.nodes.contains_key(artifact)
@@ -126,7 +169,16 @@ pub fn load(path: &Path) -> crate::Result<DependencyGraph> {
 /// Parse a dependency graph from a JSONL string.
 pub fn parse_graph(content: &str) -> crate::Result<DependencyGraph> {
     let mut dg = DependencyGraph::empty();
+    parse_entries(content, &mut dg)?;
+
+    // Verify acyclicity
+    dg.toposort()?;
+
+    Ok(dg)
+}
 
+/// Parse graph-file entries into an existing graph without checking for cycles.
+fn parse_entries(content: &str, dg: &mut DependencyGraph) -> crate::Result<()> {
     for (line_no, line) in content.lines().enumerate() {
         let trimmed = line.trim();
         if trimmed.is_empty() || trimmed.starts_with("//") {
@@ -136,19 +188,86 @@ pub fn parse_graph(content: &str) -> crate::Result<DependencyGraph> {
         let entry: GraphEntry = serde_json::from_str(trimmed)
             .map_err(|e| crate::Error::Validation(format!("graph line {}: {}", line_no + 1, e)))?;
 
-        let from = dg.get_or_insert(&entry.subject);
+        dg.get_or_insert(&entry.subject);
         for dep in &entry.depends_on {
-            let to = dg.get_or_insert(dep);
-            dg.graph.add_edge(from, to, ());
+            dg.add_edge(&entry.subject, dep);
         }
     }
+    Ok(())
+}
+
+/// Build the dependency graph from every source in SPEC §3.4.1.
+///
+/// Merges the legacy graph file at `graph_file` (skipped when `None` or
+/// absent on disk) with the `dependency` records found in `qual_files`.
+/// When both sources declare edges for the same subject, the union is used.
+/// Cycles are detected on the merged graph, so a cycle split across the two
+/// sources is still rejected.
+pub fn build(graph_file: Option<&Path>, qual_files: &[QualFile]) -> crate::Result<DependencyGraph> {
+    let mut dg = DependencyGraph::empty();
+
+    if let Some(path) = graph_file
+        && path.exists()
+    {
+        let content = std::fs::read_to_string(path)?;
+        parse_entries(&content, &mut dg)?;
+    }
+
+    dg.add_dependency_records(qual_files.iter().flat_map(|qf| qf.records.iter()));
 
-    // Verify acyclicity
     dg.toposort()?;
 
     Ok(dg)
 }
 
+/// Rename `old` to `new` in graph-file content, as either a subject or a
+/// dependency.
+///
+/// Only entries that mention `old` are rewritten; comments, blank lines, and
+/// all other entries are preserved verbatim. Returns the new content and the
+/// number of entries changed.
+pub fn rename_subject(content: &str, old: &str, new: &str) -> crate::Result<(String, usize)> {
+    let mut out = String::with_capacity(content.len());
+    let mut changed = 0;
+
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+
+        let mut entry: GraphEntry = serde_json::from_str(trimmed)
+            .map_err(|e| crate::Error::Validation(format!("graph line {}: {}", line_no + 1, e)))?;
+        if entry.subject != old && !entry.depends_on.iter().any(|d| d == old) {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        }
+
+        if entry.subject == old {
+            entry.subject = new.to_string();
+        }
+        let mut deps: Vec<String> = Vec::with_capacity(entry.depends_on.len());
+        for dep in entry.depends_on {
+            let dep = if dep == old { new.to_string() } else { dep };
+            if !deps.contains(&dep) {
+                deps.push(dep);
+            }
+        }
+        let rewritten = serde_json::json!({
+            "subject": entry.subject,
+            "depends_on": deps,
+        });
+        out.push_str(&serde_json::to_string(&rewritten)?);
+        out.push('\n');
+        changed += 1;
+    }
+
+    Ok((out, changed))
+}
+
 /// Serialize the graph to JSONL format.
 pub fn to_jsonl(graph: &DependencyGraph) -> String {
     let mut out = String::new();
@@ -177,6 +296,36 @@ pub fn to_jsonl(graph: &DependencyGraph) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::attestation::{
+        self, DependencyBody, DependencyRecord, RevocationBody, RevocationRecord,
+    };
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn make_dep(subject: &str, depends_on: &[&str]) -> Record {
+        attestation::finalize_record(Record::Dependency(DependencyRecord {
+            metabox: "1".into(),
+            record_type: "dependency".into(),
+            subject: subject.into(),
+            issuer: "https://build.example.com".into(),
+            issuer_type: None,
+            created_at: chrono::DateTime::parse_from_rfc3339("2026-02-24T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            id: String::new(),
+            body: DependencyBody {
+                depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            },
+        }))
+    }
+
+    fn make_qual_file(records: Vec<Record>) -> QualFile {
+        QualFile {
+            path: PathBuf::from(".qual"),
+            subject: "./".into(),
+            records,
+        }
+    }
 
     #[test]
     fn test_empty_graph() {
@@ -304,4 +453,97 @@ mod tests {
         let g = DependencyGraph::empty();
         assert!(g.dependencies("nonexistent").is_empty());
     }
+
+    #[test]
+    fn test_build_from_records_only() {
+        let qf = make_qual_file(vec![make_dep("app", &["lib"]), make_dep("lib", &[])]);
+        let g = build(None, &[qf]).unwrap();
+        assert_eq!(g.len(), 2);
+        assert_eq!(g.dependencies("app"), vec!["lib"]);
+    }
+
+    #[test]
+    fn test_build_merges_graph_file_and_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("qualifier.graph.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"subject":"app","depends_on":["lib"]}
+{"subject":"lib","depends_on":[]}
+"#,
+        )
+        .unwrap();
+
+        // Same subject declared in both sources: edges are unioned, not replaced.
+        let qf = make_qual_file(vec![make_dep("app", &["lib", "util"])]);
+        let g = build(Some(&path), &[qf]).unwrap();
+
+        let mut app_deps = g.dependencies("app");
+        app_deps.sort();
+        assert_eq!(app_deps, vec!["lib", "util"]);
+        assert_eq!(g.len(), 3);
+    }
+
+    #[test]
+    fn test_build_skips_revoked_records() {
+        let old = make_dep("app", &["lib", "util"]);
+        let revocation = attestation::finalize_record(Record::Revocation(RevocationRecord {
+            metabox: "1".into(),
+            record_type: "revocation".into(),
+            subject: "app".into(),
+            issuer: "https://build.example.com".into(),
+            issuer_type: None,
+            created_at: Utc::now(),
+            id: String::new(),
+            body: RevocationBody {
+                revokes: vec![old.id().to_string()],
+                summary: "Dropped util".into(),
+            },
+        }));
+        let qf = make_qual_file(vec![old, make_dep("app", &["lib"]), revocation]);
+        let g = build(None, &[qf]).unwrap();
+        assert_eq!(g.dependencies("app"), vec!["lib"]);
+        assert!(!g.artifacts().contains(&"util"));
+    }
+
+    #[test]
+    fn test_build_missing_graph_file_is_empty_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("qualifier.graph.jsonl");
+        let g = build(Some(&path), &[]).unwrap();
+        assert!(g.is_empty());
+    }
+
+    #[test]
+    fn test_build_detects_cycle_across_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("qualifier.graph.jsonl");
+        std::fs::write(&path, r#"{"subject":"a","depends_on":["b"]}"#).unwrap();
+
+        let qf = make_qual_file(vec![make_dep("b", &["a"])]);
+        let err = build(Some(&path), &[qf]).unwrap_err();
+        assert!(matches!(err, crate::Error::Cycle { .. }));
+    }
+
+    #[test]
+    fn test_rename_subject_rewrites_only_matching_entries() {
+        let content = "// build graph\n\
+            {\"subject\":\"app\",\"depends_on\":[\"old\",\"lib\"]}\n\
+            {\"subject\":\"old\",\"depends_on\":[]}\n\
+            {\"subject\": \"lib\", \"depends_on\": []}\n";
+
+        let (out, changed) = rename_subject(content, "old", "new").unwrap();
+        assert_eq!(changed, 2);
+        assert_eq!(
+            out,
+            "// build graph\n\
+             {\"depends_on\":[\"new\",\"lib\"],\"subject\":\"app\"}\n\
+             {\"depends_on\":[],\"subject\":\"new\"}\n\
+             {\"subject\": \"lib\", \"depends_on\": []}\n"
+        );
+
+        let g = parse_graph(&out).unwrap();
+        assert_eq!(g.dependencies("app").len(), 2);
+        assert!(!g.contains("old"));
+    }
 }
@@ -0,0 +1,379 @@
+//! in-toto v1 Statement projection (SPEC §5.1).
+//!
+//! Attestations, epochs, and dependency records project losslessly into
+//! in-toto Statements: every body field becomes a predicate field, and the
+//! envelope fields that are not captured by the Statement itself (`id`,
+//! `issuer`, `issuer_type`, `created_at`) are carried alongside them.
+//!
+//! The projection is reversible: [`from_statement`] rebuilds the record and
+//! checks that its recomputed ID equals `predicate.qualifier_id`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::{Deserialize, Serialize};
+
+use crate::attestation::{self, Record};
+
+/// The in-toto Statement `_type` URI.
+pub const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+
+/// Predicate type URI for attestation records.
+pub const ATTESTATION_PREDICATE: &str = "https://qualifier.dev/attestation/v1";
+
+/// Predicate type URI for epoch records.
+pub const EPOCH_PREDICATE: &str = "https://qualifier.dev/epoch/v1";
+
+/// Predicate type URI for dependency records.
+pub const DEPENDENCY_PREDICATE: &str = "https://qualifier.dev/dependency/v1";
+
+/// DSSE payload type for an in-toto Statement.
+pub const DSSE_PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+
+/// An in-toto v1 Statement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statement {
+    #[serde(rename = "_type")]
+    pub statement_type: String,
+    pub subject: Vec<ResourceDescriptor>,
+    #[serde(rename = "predicateType")]
+    pub predicate_type: String,
+    pub predicate: serde_json::Map<String, serde_json::Value>,
+}
+
+/// An in-toto resource descriptor identifying the artifact a Statement is about.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceDescriptor {
+    pub name: String,
+    /// Algorithm name → hex digest. Empty when the artifact is not a file
+    /// on disk (e.g. a directory or logical subject).
+    #[serde(default)]
+    pub digest: BTreeMap<String, String>,
+}
+
+/// The predicate type URI for a record, or `None` if the record type has no
+/// in-toto projection.
+pub fn predicate_type(record: &Record) -> Option<&'static str> {
+    match record {
+        Record::Attestation(_) => Some(ATTESTATION_PREDICATE),
+        Record::Epoch(_) => Some(EPOCH_PREDICATE),
+        Record::Dependency(_) => Some(DEPENDENCY_PREDICATE),
+        Record::Policy(_) | Record::Revocation(_) | Record::Unknown(_) => None,
+    }
+}
+
+/// BLAKE3 hex digest of the artifact file at `path`, or `None` if it is not
+/// a readable regular file.
+pub fn artifact_digest(path: &Path) -> Option<String> {
+    if !path.is_file() {
+        return None;
+    }
+    let content = std::fs::read(path).ok()?;
+    Some(blake3::hash(&content).to_hex().to_string())
+}
+
+/// Project a record into an in-toto Statement.
+///
+/// `digest` is the BLAKE3 hash of the artifact content (see
+/// [`artifact_digest`]); in-toto v1 requires every subject to carry one.
+/// Returns `None` for record types without a predicate type (policies and
+/// unknown types).
+pub fn to_statement(record: &Record, digest: String) -> Option<Statement> {
+    let predicate_type = predicate_type(record)?;
+    let (body, issuer_type, created_at) = match record {
+        Record::Attestation(a) => (
+            serde_json::to_value(&a.body),
+            a.issuer_type.as_ref(),
+            a.created_at,
+        ),
+        Record::Epoch(e) => (
+            serde_json::to_value(&e.body),
+            e.issuer_type.as_ref(),
+            e.created_at,
+        ),
+        Record::Dependency(d) => (
+            serde_json::to_value(&d.body),
+            d.issuer_type.as_ref(),
+            d.created_at,
+        ),
+        Record::Policy(_) | Record::Revocation(_) | Record::Unknown(_) => return None,
+    };
+
+    let mut predicate = match body.ok()? {
+        serde_json::Value::Object(map) => map,
+        _ => return None,
+    };
+    predicate.insert("qualifier_id".into(), record.id().into());
+    predicate.insert("issuer".into(), record.issuer()?.into());
+    if let Some(issuer_type) = issuer_type {
+        predicate.insert("issuer_type".into(), issuer_type.to_string().into());
+    }
+    predicate.insert("created_at".into(), serde_json::to_value(created_at).ok()?);
+
+    Some(Statement {
+        statement_type: STATEMENT_TYPE.into(),
+        subject: vec![ResourceDescriptor {
+            name: record.subject().to_string(),
+            digest: BTreeMap::from([("blake3".to_string(), digest)]),
+        }],
+        predicate_type: predicate_type.into(),
+        predicate,
+    })
+}
+
+/// Whether `predicate_type` is one of qualifier's predicate type URIs.
+pub fn is_qualifier_predicate(predicate_type: &str) -> bool {
+    [ATTESTATION_PREDICATE, EPOCH_PREDICATE, DEPENDENCY_PREDICATE].contains(&predicate_type)
+}
+
+/// Rebuild a record from a Statement with a qualifier predicate type.
+///
+/// Fails if the predicate type is not a qualifier URI, if the predicate does
+/// not describe a valid record, or if the recomputed record ID differs from
+/// `predicate.qualifier_id`.
+pub fn from_statement(statement: &Statement) -> crate::Result<Record> {
+    let record_type = match statement.predicate_type.as_str() {
+        ATTESTATION_PREDICATE => "attestation",
+        EPOCH_PREDICATE => "epoch",
+        DEPENDENCY_PREDICATE => "dependency",
+        other => {
+            return Err(crate::Error::Validation(format!(
+                "not a qualifier predicate type: {other}"
+            )));
+        }
+    };
+    let subject = match statement.subject.as_slice() {
+        [descriptor] => descriptor.name.clone(),
+        _ => {
+            return Err(crate::Error::Validation(format!(
+                "expected exactly one subject, found {}",
+                statement.subject.len()
+            )));
+        }
+    };
+
+    let mut body = statement.predicate.clone();
+    let expected_id = match body.remove("qualifier_id") {
+        Some(serde_json::Value::String(id)) => id,
+        _ => {
+            return Err(crate::Error::Validation(
+                "predicate is missing qualifier_id".into(),
+            ));
+        }
+    };
+
+    let mut record = serde_json::Map::new();
+    record.insert("metabox".into(), "1".into());
+    record.insert("type".into(), record_type.into());
+    record.insert("subject".into(), subject.into());
+    for field in ["issuer", "issuer_type", "created_at"] {
+        if let Some(value) = body.remove(field) {
+            record.insert(field.into(), value);
+        }
+    }
+    record.insert("id".into(), "".into());
+    record.insert("body".into(), serde_json::Value::Object(body));
+
+    let record: Record = serde_json::from_value(serde_json::Value::Object(record))?;
+    let record = attestation::finalize_record(record);
+    if record.id() != expected_id {
+        return Err(crate::Error::Validation(format!(
+            "qualifier_id {} does not match recomputed id {}",
+            expected_id,
+            record.id()
+        )));
+    }
+    Ok(record)
+}
+
+/// Parse one JSONL line holding either a bare Statement or a DSSE envelope
+/// wrapping one. Envelope signatures are not checked here.
+pub fn parse_line(line: &str) -> crate::Result<Statement> {
+    let value: serde_json::Value = serde_json::from_str(line)?;
+    let Some(payload_type) = value.get("payloadType") else {
+        return Ok(serde_json::from_value(value)?);
+    };
+
+    if payload_type != DSSE_PAYLOAD_TYPE {
+        return Err(crate::Error::Validation(format!(
+            "unsupported DSSE payloadType: {payload_type}"
+        )));
+    }
+    let payload = value
+        .get("payload")
+        .and_then(|p| p.as_str())
+        .ok_or_else(|| crate::Error::Validation("DSSE envelope has no payload".into()))?;
+    let decoded = BASE64
+        .decode(payload)
+        .map_err(|e| crate::Error::Validation(format!("invalid DSSE payload: {e}")))?;
+    Ok(serde_json::from_slice(&decoded)?)
+}
+
+/// Parse JSONL content of Statements and/or DSSE envelopes. Blank lines and
+/// `//` comments are skipped.
+pub fn parse_str(content: &str) -> crate::Result<Vec<Statement>> {
+    let mut statements = Vec::new();
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+        let statement = parse_line(trimmed)
+            .map_err(|e| crate::Error::Validation(format!("line {}: {}", line_no + 1, e)))?;
+        statements.push(statement);
+    }
+    Ok(statements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{
+        self, Attestation, AttestationBody, DependencyBody, DependencyRecord, IssuerType, Kind,
+    };
+    use chrono::{DateTime, Utc};
+
+    fn ts() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-02-25T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn make_attestation() -> Record {
+        attestation::finalize_record(Record::Attestation(Box::new(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: "src/parser.rs".into(),
+            issuer: "mailto:alice@example.com".into(),
+            issuer_type: Some(IssuerType::Human),
+            created_at: ts(),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
+                kind: Kind::Concern,
+                r#ref: Some("git:3aba500".into()),
+                score: -10,
+                span: None,
+                suggested_fix: None,
+                summary: "Panics on malformed input".into(),
+                supersedes: None,
+                symbol: None,
+                tags: vec!["robustness".into()],
+            },
+        })))
+    }
+
+    #[test]
+    fn test_attestation_statement() {
+        let record = make_attestation();
+        let stmt = to_statement(&record, "abc123".into()).unwrap();
+
+        assert_eq!(stmt.statement_type, STATEMENT_TYPE);
+        assert_eq!(stmt.predicate_type, ATTESTATION_PREDICATE);
+        assert_eq!(stmt.subject[0].name, "src/parser.rs");
+        assert_eq!(stmt.subject[0].digest["blake3"], "abc123");
+
+        let p = &stmt.predicate;
+        assert_eq!(p["qualifier_id"], record.id());
+        assert_eq!(p["kind"], "concern");
+        assert_eq!(p["score"], -10);
+        assert_eq!(p["summary"], "Panics on malformed input");
+        assert_eq!(p["tags"][0], "robustness");
+        assert_eq!(p["issuer"], "mailto:alice@example.com");
+        assert_eq!(p["issuer_type"], "human");
+        assert_eq!(p["created_at"], "2026-02-25T10:00:00Z");
+        assert_eq!(p["ref"], "git:3aba500");
+    }
+
+    #[test]
+    fn test_dependency_statement() {
+        let record = attestation::finalize_record(Record::Dependency(DependencyRecord {
+            metabox: "1".into(),
+            record_type: "dependency".into(),
+            subject: "bin/server".into(),
+            issuer: "https://build.example.com".into(),
+            issuer_type: None,
+            created_at: ts(),
+            id: String::new(),
+            body: DependencyBody {
+                depends_on: vec!["lib/http".into()],
+            },
+        }));
+        let stmt = to_statement(&record, "abc123".into()).unwrap();
+
+        assert_eq!(stmt.predicate_type, DEPENDENCY_PREDICATE);
+        assert_eq!(stmt.subject[0].digest["blake3"], "abc123");
+        assert_eq!(stmt.predicate["depends_on"][0], "lib/http");
+        assert!(!stmt.predicate.contains_key("issuer_type"));
+    }
+
+    #[test]
+    fn test_unknown_record_has_no_statement() {
+        let record = Record::Unknown(serde_json::json!({"type": "custom", "id": "x"}));
+        assert!(to_statement(&record, "abc123".into()).is_none());
+    }
+
+    #[test]
+    fn test_statement_roundtrip() {
+        let record = make_attestation();
+        let stmt = to_statement(&record, "abc123".into()).unwrap();
+        let line = serde_json::to_string(&stmt).unwrap();
+
+        let parsed = parse_line(&line).unwrap();
+        assert_eq!(from_statement(&parsed).unwrap(), record);
+    }
+
+    #[test]
+    fn test_dsse_envelope_roundtrip() {
+        let record = make_attestation();
+        let stmt = to_statement(&record, "abc123".into()).unwrap();
+        let envelope = serde_json::json!({
+            "payloadType": DSSE_PAYLOAD_TYPE,
+            "payload": BASE64.encode(serde_json::to_vec(&stmt).unwrap()),
+            "signatures": [],
+        });
+
+        let parsed = parse_str(&format!("// upstream\n{envelope}\n")).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(from_statement(&parsed[0]).unwrap(), record);
+    }
+
+    #[test]
+    fn test_from_statement_rejects_id_mismatch() {
+        let record = make_attestation();
+        let mut stmt = to_statement(&record, "abc123".into()).unwrap();
+        stmt.predicate.insert("score".into(), 50.into());
+
+        let err = from_statement(&stmt).unwrap_err();
+        assert!(err.to_string().contains("does not match recomputed id"));
+    }
+
+    #[test]
+    fn test_from_statement_rejects_foreign_predicate() {
+        let record = make_attestation();
+        let mut stmt = to_statement(&record, "abc123".into()).unwrap();
+        stmt.predicate_type = "https://slsa.dev/provenance/v1".into();
+
+        assert!(!is_qualifier_predicate(&stmt.predicate_type));
+        assert!(from_statement(&stmt).is_err());
+    }
+
+    #[test]
+    fn test_artifact_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.rs");
+        std::fs::write(&file, "fn main() {}\n").unwrap();
+
+        assert_eq!(
+            artifact_digest(&file).unwrap(),
+            blake3::hash(b"fn main() {}\n").to_hex().to_string()
+        );
+        assert!(artifact_digest(dir.path()).is_none());
+        assert!(artifact_digest(&dir.path().join("missing")).is_none());
+    }
+}
@@ -0,0 +1,214 @@
+//! Project-defined attestation kinds (SPEC §2.7.2).
+//!
+//! A [`KindRegistry`] declares custom kinds, such as `security-review` or
+//! `flaky`, with a default score, an allowed score range, and a sign. It
+//! may also tighten the built-in kinds.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::attestation::{Attestation, Kind};
+
+/// The sign a kind's scores must have.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sign {
+    /// Scores must be greater than zero.
+    Positive,
+    /// Scores must be less than zero.
+    Negative,
+    /// Any score is allowed.
+    #[default]
+    Any,
+}
+
+impl Sign {
+    fn allows(self, score: i32) -> bool {
+        match self {
+            Sign::Positive => score > 0,
+            Sign::Negative => score < 0,
+            Sign::Any => true,
+        }
+    }
+}
+
+/// A `[kinds.<name>]` entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KindSpec {
+    /// Score used when `attest` is given no `--score`. Unset falls back to
+    /// the built-in default (`0` for custom kinds).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_score: Option<i32>,
+    /// Inclusive `[min, max]` range scores must fall in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<[i32; 2]>,
+    /// The sign scores must have.
+    #[serde(default, skip_serializing_if = "is_any")]
+    pub sign: Sign,
+}
+
+fn is_any(sign: &Sign) -> bool {
+    *sign == Sign::Any
+}
+
+impl KindSpec {
+    /// Why `score` is not allowed for the kind `name`, if it is not.
+    fn check(&self, name: &str, score: i32) -> Option<String> {
+        if let Some([min, max]) = self.range
+            && !(min..=max).contains(&score)
+        {
+            return Some(format!(
+                "score {score} is out of range [{min}, {max}] for kind '{name}'"
+            ));
+        }
+        if !self.sign.allows(score) {
+            let sign = match self.sign {
+                Sign::Positive => "positive",
+                Sign::Negative => "negative",
+                Sign::Any => unreachable!("any sign allows every score"),
+            };
+            return Some(format!(
+                "kind '{name}' requires a {sign} score, got {score}"
+            ));
+        }
+        None
+    }
+}
+
+/// Project kinds keyed by name: the `[kinds]` table of `.qualifier.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KindRegistry(pub BTreeMap<String, KindSpec>);
+
+impl KindRegistry {
+    /// Check that each entry's range, sign, and default score agree.
+    pub fn validate(&self) -> crate::Result<()> {
+        for (name, spec) in &self.0 {
+            if let Some([min, max]) = spec.range
+                && (min > max || min < -100 || max > 100)
+            {
+                return Err(crate::Error::Validation(format!(
+                    "kind '{name}': range [{min}, {max}] must be ordered and within [-100, 100]"
+                )));
+            }
+            let default = self.default_score(&name.parse().unwrap());
+            if let Some(why) = spec.check(name, default) {
+                return Err(crate::Error::Validation(format!("default_score: {why}")));
+            }
+        }
+        Ok(())
+    }
+
+    /// The score `attest` uses for `kind` when none is given.
+    pub fn default_score(&self, kind: &Kind) -> i32 {
+        self.0
+            .get(&kind.to_string())
+            .and_then(|spec| spec.default_score)
+            .unwrap_or_else(|| kind.default_score())
+    }
+
+    /// Whether `kind` is built in or declared here.
+    pub fn is_known(&self, kind: &Kind) -> bool {
+        !matches!(kind, Kind::Custom(_)) || self.0.contains_key(&kind.to_string())
+    }
+
+    /// Declared kind names, for typo suggestions.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Range and sign violations of `attestation` against its kind's entry.
+    pub fn check(&self, attestation: &Attestation) -> Vec<String> {
+        let name = attestation.body.kind.to_string();
+        self.0
+            .get(&name)
+            .and_then(|spec| spec.check(&name, attestation.body.score))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> KindRegistry {
+        KindRegistry(BTreeMap::from([
+            (
+                "security-review".into(),
+                KindSpec {
+                    default_score: Some(20),
+                    range: Some([0, 50]),
+                    sign: Sign::Any,
+                },
+            ),
+            (
+                "flaky".into(),
+                KindSpec {
+                    default_score: Some(-15),
+                    range: None,
+                    sign: Sign::Negative,
+                },
+            ),
+            (
+                "praise".into(),
+                KindSpec {
+                    default_score: Some(40),
+                    ..Default::default()
+                },
+            ),
+        ]))
+    }
+
+    #[test]
+    fn test_default_scores() {
+        let registry = registry();
+        assert!(registry.validate().is_ok());
+        assert_eq!(
+            registry.default_score(&"security-review".parse().unwrap()),
+            20
+        );
+        assert_eq!(registry.default_score(&Kind::Praise), 40);
+        assert_eq!(registry.default_score(&Kind::Concern), -10);
+        assert_eq!(registry.default_score(&"other".parse().unwrap()), 0);
+    }
+
+    #[test]
+    fn test_is_known() {
+        let registry = registry();
+        assert!(registry.is_known(&Kind::Waiver));
+        assert!(registry.is_known(&"flaky".parse().unwrap()));
+        assert!(!registry.is_known(&"perf-regression".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_range_and_sign() {
+        let spec = &registry().0["flaky"];
+        assert!(spec.check("flaky", -5).is_none());
+        assert!(spec.check("flaky", 5).unwrap().contains("negative"));
+
+        let spec = &registry().0["security-review"];
+        assert!(
+            spec.check("security-review", 60)
+                .unwrap()
+                .contains("[0, 50]")
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_inconsistent_entries() {
+        let mut bad = registry();
+        bad.0.get_mut("flaky").unwrap().default_score = Some(10);
+        assert!(bad.validate().is_err());
+
+        let mut bad = registry();
+        bad.0.get_mut("security-review").unwrap().range = Some([50, 0]);
+        assert!(bad.validate().is_err());
+
+        // A negative-only kind without a default score falls back to 0.
+        let mut bad = registry();
+        bad.0.get_mut("flaky").unwrap().default_score = None;
+        assert!(bad.validate().is_err());
+    }
+}
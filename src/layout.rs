@@ -0,0 +1,320 @@
+//! Migration between `.qual` layouts (SPEC §2.10.2).
+//!
+//! Records are addressed by their `subject` field, not by the file holding
+//! them, so moving a record between files never changes its ID or its
+//! score. A migration regroups every record into the file the target
+//! layout assigns its subject.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::attestation::Record;
+use crate::qual_file::{self, QualFile};
+use crate::scoring;
+
+/// A `.qual` layout strategy (SPEC §2.10).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// One `.qual` file per subject: `src/parser.rs.qual`.
+    File,
+    /// One `.qual` file per directory: `src/.qual`.
+    Directory,
+    /// One `.qual` file at the project root.
+    Project,
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Layout::File => write!(f, "file"),
+            Layout::Directory => write!(f, "directory"),
+            Layout::Project => write!(f, "project"),
+        }
+    }
+}
+
+impl std::str::FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "file" => Ok(Layout::File),
+            "directory" => Ok(Layout::Directory),
+            "project" => Ok(Layout::Project),
+            other => Err(format!(
+                "unknown layout '{other}' (expected file, directory, or project)"
+            )),
+        }
+    }
+}
+
+impl Layout {
+    /// The `.qual` file, relative to the project root, that holds records
+    /// about `subject` in this layout. Subjects ending in `/` name a
+    /// directory, whose file is the directory's own `.qual`.
+    pub fn path_for(self, subject: &str) -> PathBuf {
+        match self {
+            Layout::Project => PathBuf::from(".qual"),
+            _ if subject.ends_with('/') => PathBuf::from(format!("{subject}.qual")),
+            Layout::File => PathBuf::from(format!("{subject}.qual")),
+            Layout::Directory => match Path::new(subject).parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.join(".qual"),
+                _ => PathBuf::from(".qual"),
+            },
+        }
+    }
+}
+
+/// The planned effect of migrating a set of `.qual` files to a layout.
+#[derive(Debug, Clone, Default)]
+pub struct Migration {
+    /// New contents of every `.qual` file the migration changes, sorted by
+    /// path. A file left with no records is to be removed.
+    pub files: Vec<QualFile>,
+    /// Number of records that move to a different file.
+    pub moved: usize,
+}
+
+impl Migration {
+    /// Returns true if every record is already where the layout puts it.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Plan the migration of `qual_files`, found under `root`, to `layout`.
+///
+/// Each record moves to the file `layout` assigns its subject. Records keep
+/// their relative order, so each subject's history reads the same after the
+/// move. Records without a subject, such as some unknown record types, stay
+/// in the file they were found in.
+///
+/// The plan is checked before it is returned: every record must survive,
+/// and every subject must have the same raw score as before. Nothing is
+/// written; the caller applies the returned plan.
+pub fn migrate(
+    root: &Path,
+    qual_files: &[QualFile],
+    layout: Layout,
+    now: DateTime<Utc>,
+) -> crate::Result<Migration> {
+    let mut targets: BTreeMap<PathBuf, Vec<Record>> = qual_files
+        .iter()
+        .map(|qf| (qf.path.clone(), Vec::new()))
+        .collect();
+    let mut moved = 0;
+    for qf in qual_files {
+        for record in &qf.records {
+            let target = match record.subject() {
+                "" => qf.path.clone(),
+                subject => root.join(layout.path_for(subject)),
+            };
+            if target != qf.path {
+                moved += 1;
+            }
+            targets.entry(target).or_default().push(record.clone());
+        }
+    }
+
+    verify(qual_files, &targets, now)?;
+
+    let ids = |records: &[Record]| -> Vec<String> {
+        records.iter().map(|r| r.id().to_string()).collect()
+    };
+    let files = targets
+        .into_iter()
+        .filter(|(path, records)| {
+            let before = qual_files.iter().find(|qf| &qf.path == path);
+            before.map(|qf| ids(&qf.records)) != Some(ids(records))
+        })
+        .map(|(path, records)| QualFile {
+            subject: qual_file::subject_name(&path),
+            path,
+            records,
+        })
+        .collect();
+    Ok(Migration { files, moved })
+}
+
+/// Check that `after` holds every record of `before` and scores each
+/// subject the same.
+fn verify(
+    before: &[QualFile],
+    after: &BTreeMap<PathBuf, Vec<Record>>,
+    now: DateTime<Utc>,
+) -> crate::Result<()> {
+    let count_before: usize = before.iter().map(|qf| qf.records.len()).sum();
+    let count_after: usize = after.values().map(Vec::len).sum();
+    if count_before != count_after {
+        return Err(crate::Error::Validation(format!(
+            "BUG: migration changed the record count from {count_before} to {count_after}"
+        )));
+    }
+
+    // Migration preserves nominal scores, so evaluate without decay.
+    let eval = scoring::Evaluation::at(now);
+    let scores_before = raw_scores(before.iter().flat_map(|qf| &qf.records), &eval);
+    let scores_after = raw_scores(after.values().flatten(), &eval);
+    for (subject, score) in &scores_before {
+        let moved = scores_after.get(subject).copied().unwrap_or_default();
+        if moved != *score {
+            return Err(crate::Error::Validation(format!(
+                "BUG: migration changed raw score of {subject} from {score} to {moved}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Raw score of every subject among `records`.
+fn raw_scores<'a>(
+    records: impl Iterator<Item = &'a Record>,
+    eval: &scoring::Evaluation,
+) -> BTreeMap<&'a str, i32> {
+    let mut by_subject: BTreeMap<&str, Vec<Record>> = BTreeMap::new();
+    for record in records {
+        by_subject
+            .entry(record.subject())
+            .or_default()
+            .push(record.clone());
+    }
+    by_subject
+        .into_iter()
+        .map(|(subject, records)| (subject, scoring::raw_score(&records, eval)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{self, Attestation, AttestationBody, Kind};
+
+    fn record(subject: &str, score: i32, summary: &str, supersedes: Option<&str>) -> Record {
+        Record::Attestation(Box::new(attestation::finalize(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: "2026-01-01T00:00:00Z".parse().unwrap(),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
+                kind: Kind::Pass,
+                r#ref: None,
+                score,
+                span: None,
+                suggested_fix: None,
+                summary: summary.into(),
+                supersedes: supersedes.map(Into::into),
+                symbol: None,
+                tags: vec![],
+            },
+        })))
+    }
+
+    fn qual_file(path: &str, records: Vec<Record>) -> QualFile {
+        QualFile {
+            path: PathBuf::from(path),
+            subject: qual_file::subject_name(Path::new(path)),
+            records,
+        }
+    }
+
+    #[test]
+    fn test_path_for() {
+        assert_eq!(
+            Layout::File.path_for("src/a.rs"),
+            Path::new("src/a.rs.qual")
+        );
+        assert_eq!(
+            Layout::Directory.path_for("src/a.rs"),
+            Path::new("src/.qual")
+        );
+        assert_eq!(Layout::Directory.path_for("a.rs"), Path::new(".qual"));
+        assert_eq!(Layout::Directory.path_for("src/"), Path::new("src/.qual"));
+        assert_eq!(Layout::Project.path_for("src/a.rs"), Path::new(".qual"));
+    }
+
+    #[test]
+    fn test_migrate_to_file_layout_splits_by_subject() {
+        let a1 = record("src/a.rs", 10, "a1", None);
+        let b = record("src/b.rs", -20, "b", None);
+        let a2 = record("src/a.rs", 30, "a2", Some(a1.id()));
+        let unknown: Record =
+            serde_json::from_str(r#"{"metabox":"1","type":"x-custom","id":"u1","body":{}}"#)
+                .unwrap();
+        let files = vec![qual_file(
+            "src/.qual",
+            vec![a1.clone(), b.clone(), a2.clone(), unknown.clone()],
+        )];
+
+        let plan = migrate(Path::new(""), &files, Layout::File, Utc::now()).unwrap();
+        let paths: Vec<&Path> = plan.files.iter().map(|qf| qf.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("src/.qual"),
+                Path::new("src/a.rs.qual"),
+                Path::new("src/b.rs.qual")
+            ]
+        );
+        assert_eq!(plan.moved, 3);
+        // The subjectless record stays put; a's history keeps its order.
+        assert_eq!(plan.files[0].records, vec![unknown]);
+        assert_eq!(plan.files[1].records, vec![a1, a2]);
+        assert_eq!(plan.files[2].records, vec![b]);
+    }
+
+    #[test]
+    fn test_migrate_to_project_merges_and_empties_sources() {
+        let a = record("src/a.rs", 10, "a", None);
+        let b = record("lib/b.rs", 20, "b", None);
+        let files = vec![
+            qual_file("lib/.qual", vec![b.clone()]),
+            qual_file("src/a.rs.qual", vec![a.clone()]),
+        ];
+
+        let plan = migrate(Path::new(""), &files, Layout::Project, Utc::now()).unwrap();
+        let summary: Vec<(&Path, usize)> = plan
+            .files
+            .iter()
+            .map(|qf| (qf.path.as_path(), qf.records.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Path::new(".qual"), 2),
+                (Path::new("lib/.qual"), 0),
+                (Path::new("src/a.rs.qual"), 0)
+            ]
+        );
+        assert_eq!(plan.files[0].records, vec![b, a]);
+    }
+
+    #[test]
+    fn test_migrate_is_empty_when_already_in_layout() {
+        let files = vec![qual_file(
+            "src/.qual",
+            vec![
+                record("src/a.rs", 10, "a", None),
+                record("src/b.rs", 20, "b", None),
+            ],
+        )];
+        let plan = migrate(Path::new(""), &files, Layout::Directory, Utc::now()).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.moved, 0);
+    }
+
+    #[test]
+    fn test_layout_parse() {
+        assert_eq!("directory".parse::<Layout>().unwrap(), Layout::Directory);
+        assert!("tree".parse::<Layout>().unwrap_err().contains("tree"));
+    }
+}
@@ -1,8 +1,20 @@
 pub mod attestation;
 pub mod compact;
 pub mod graph;
+pub mod intoto;
+pub mod kinds;
+pub mod layout;
+pub mod pin;
+pub mod policy;
 pub mod qual_file;
+pub mod rename;
+pub mod sarif;
 pub mod scoring;
+pub mod signing;
+#[cfg(feature = "symbols")]
+pub mod symbol;
+pub mod trust;
+pub mod watch;
 
 #[cfg(feature = "cli")]
 pub mod cli;
@@ -0,0 +1,434 @@
+//! Content pinning (SPEC §2.4.4, §4.7).
+//!
+//! An attestation may carry the BLAKE3 `digest` of the content it reviewed —
+//! the whole subject file, or just the lines of its span. A record is
+//! **stale** once the subject's current content no longer hashes to that
+//! digest.
+//!
+//! A spanned attestation may also carry a [`Fingerprint`] of its lines, so
+//! the span can be found again after code moves.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::attestation::{self, Attestation, Fingerprint, Position, Record, Span};
+use crate::scoring;
+
+/// Lines of context fingerprinted on each side of a span.
+const CONTEXT_LINES: usize = 2;
+
+/// Hex characters kept from each line hash.
+const LINE_HASH_LEN: usize = 16;
+
+/// Why a pinned record is stale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaleReason {
+    /// The pinned content has changed; `current` is its digest now.
+    Changed { current: String },
+    /// The subject file no longer exists.
+    Missing,
+}
+
+/// An active record whose pinned content no longer matches.
+#[derive(Debug, Clone)]
+pub struct Stale<'a> {
+    pub record: &'a Record,
+    pub reason: StaleReason,
+}
+
+/// BLAKE3 hex digest of `content`, restricted to the span's lines if given.
+///
+/// Span lines are 1-indexed and inclusive, each with its line terminator;
+/// columns are ignored. Lines past the end of the content are skipped.
+pub fn content_digest(content: &[u8], span: Option<&Span>) -> String {
+    let Some(span) = span else {
+        return blake3::hash(content).to_hex().to_string();
+    };
+    let start = span.start.line.max(1) as usize;
+    let end = span.end.as_ref().map_or(start, |e| e.line as usize);
+    let mut hasher = blake3::Hasher::new();
+    for line in content
+        .split_inclusive(|&b| b == b'\n')
+        .skip(start - 1)
+        .take(end.saturating_sub(start) + 1)
+    {
+        hasher.update(line);
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+/// Digest of `subject`'s current content under `root`, or `None` if the
+/// subject is not a readable file.
+pub fn current_digest(root: &Path, subject: &str, span: Option<&Span>) -> Option<String> {
+    let path = root.join(subject);
+    if !path.is_file() {
+        return None;
+    }
+    let content = std::fs::read(path).ok()?;
+    Some(content_digest(&content, span))
+}
+
+/// Active pinned records in `records` whose content under `root` has changed
+/// or disappeared, in input order.
+pub fn stale_records<'a>(root: &Path, records: &'a [Record]) -> Vec<Stale<'a>> {
+    scoring::filter_superseded(records)
+        .into_iter()
+        .filter_map(|record| {
+            let att = record.as_attestation()?;
+            let pinned = att.body.digest.as_deref()?;
+            let reason = match current_digest(root, &att.subject, att.body.span.as_ref()) {
+                None => StaleReason::Missing,
+                Some(current) if current != pinned => StaleReason::Changed { current },
+                Some(_) => return None,
+            };
+            Some(Stale { record, reason })
+        })
+        .collect()
+}
+
+/// The [`scoring::Staleness`] for `records`: stale record IDs with `weight`.
+pub fn staleness(root: &Path, records: &[Record], weight: u8) -> scoring::Staleness {
+    scoring::Staleness {
+        ids: stale_records(root, records)
+            .into_iter()
+            .map(|s| s.record.id().to_string())
+            .collect(),
+        weight,
+    }
+}
+
+/// Hash of a line with leading, trailing, and repeated whitespace removed,
+/// so re-indenting code does not lose its anchor.
+fn line_hash(line: &str) -> String {
+    let normalized = line.split_whitespace().collect::<Vec<_>>().join(" ");
+    blake3::hash(normalized.as_bytes()).to_hex()[..LINE_HASH_LEN].to_string()
+}
+
+fn line_hashes(content: &str) -> Vec<String> {
+    content.lines().map(line_hash).collect()
+}
+
+/// Fingerprint of lines `start..end` (0-indexed, exclusive) of `hashes`.
+fn fingerprint_at(hashes: &[String], start: usize, end: usize) -> Fingerprint {
+    Fingerprint {
+        after: hashes[end..(end + CONTEXT_LINES).min(hashes.len())].to_vec(),
+        before: hashes[start.saturating_sub(CONTEXT_LINES)..start].to_vec(),
+        lines: hashes[start..end].to_vec(),
+    }
+}
+
+/// Fingerprint the lines of `span` in `content`, or `None` if the span runs
+/// past the end of the content.
+pub fn fingerprint(content: &str, span: &Span) -> Option<Fingerprint> {
+    let hashes = line_hashes(content);
+    let start = span.start.line.max(1) as usize - 1;
+    let end = span.end_or_start().line as usize;
+    if end <= start || end > hashes.len() {
+        return None;
+    }
+    Some(fingerprint_at(&hashes, start, end))
+}
+
+/// Where a fingerprinted span's lines are in the current content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Anchor {
+    /// The lines are still where the span says.
+    Unchanged,
+    /// The lines moved to `span`; `fingerprint` is re-taken there.
+    Moved {
+        span: Span,
+        fingerprint: Fingerprint,
+    },
+    /// The lines no longer appear, or the subject file is gone.
+    Lost,
+    /// The lines appear in several places that context cannot tell apart.
+    Ambiguous,
+}
+
+/// Number of context hashes in `fingerprint` that match around `start`.
+fn context_matches(hashes: &[String], start: usize, fingerprint: &Fingerprint) -> usize {
+    let end = start + fingerprint.lines.len();
+    let before = fingerprint
+        .before
+        .iter()
+        .rev()
+        .zip(hashes[..start].iter().rev())
+        .filter(|(a, b)| a == b)
+        .count();
+    let after = fingerprint
+        .after
+        .iter()
+        .zip(&hashes[end..])
+        .filter(|(a, b)| a == b)
+        .count();
+    before + after
+}
+
+/// Find `fingerprint`'s lines in `content`.
+///
+/// Candidates are the places where every spanned line matches. The one with
+/// the most matching context lines wins, then the one closest to `span`;
+/// a tie on both is [`Anchor::Ambiguous`].
+pub fn locate(content: &str, span: &Span, fingerprint: &Fingerprint) -> Anchor {
+    let hashes = line_hashes(content);
+    let n = fingerprint.lines.len();
+    if n == 0 || n > hashes.len() {
+        return Anchor::Lost;
+    }
+    let original = span.start.line.max(1) as usize - 1;
+    let candidates: Vec<(usize, (Reverse<usize>, usize))> = (0..=hashes.len() - n)
+        .filter(|&i| hashes[i..i + n] == fingerprint.lines[..])
+        .map(|i| {
+            let rank = (
+                Reverse(context_matches(&hashes, i, fingerprint)),
+                i.abs_diff(original),
+            );
+            (i, rank)
+        })
+        .collect();
+    let Some(best) = candidates.iter().map(|(_, rank)| *rank).min() else {
+        return Anchor::Lost;
+    };
+    let mut top = candidates.iter().filter(|(_, rank)| *rank == best);
+    let (start, _) = *top.next().expect("best comes from candidates");
+    if top.next().is_some() {
+        return Anchor::Ambiguous;
+    }
+    if start == original {
+        return Anchor::Unchanged;
+    }
+
+    let span = Span {
+        start: Position {
+            line: start as u32 + 1,
+            col: span.start.col,
+        },
+        end: Some(Position {
+            line: (start + n) as u32,
+            col: span.end_or_start().col,
+        }),
+    };
+    Anchor::Moved {
+        span,
+        fingerprint: fingerprint_at(&hashes, start, start + n),
+    }
+}
+
+/// The outcome of locating one fingerprinted attestation.
+#[derive(Debug, Clone)]
+pub struct Reanchor<'a> {
+    pub attestation: &'a Attestation,
+    pub anchor: Anchor,
+}
+
+/// Locate every active, fingerprinted attestation in `records` within its
+/// subject's current content under `root`, in input order.
+pub fn reanchor_records<'a>(root: &Path, records: &'a [Record]) -> Vec<Reanchor<'a>> {
+    let mut contents: HashMap<&str, Option<String>> = HashMap::new();
+    scoring::filter_superseded(records)
+        .into_iter()
+        .filter_map(|record| {
+            let att = record.as_attestation()?;
+            let span = att.body.span.as_ref()?;
+            let fingerprint = att.body.fingerprint.as_ref()?;
+            let content = contents
+                .entry(att.subject.as_str())
+                .or_insert_with(|| std::fs::read_to_string(root.join(&att.subject)).ok());
+            let anchor = match content {
+                Some(content) => locate(content, span, fingerprint),
+                None => Anchor::Lost,
+            };
+            Some(Reanchor {
+                attestation: att,
+                anchor,
+            })
+        })
+        .collect()
+}
+
+/// An attestation that supersedes `original` with the same signal at `span`.
+///
+/// Moving a span is not a new judgment, so everything but the span and
+/// fingerprint carries over unchanged: the body (`digest`, `expires_at`,
+/// ...), `issuer`, `issuer_type`, and `created_at`. Decay, trust, and
+/// policy treat the re-anchored record exactly as they treated the original.
+pub fn reanchored(original: &Attestation, span: Span, fingerprint: Fingerprint) -> Attestation {
+    let mut att = original.clone();
+    att.body.span = Some(span);
+    att.body.fingerprint = Some(fingerprint);
+    att.body.supersedes = Some(original.id.clone());
+    att.id = String::new();
+    attestation::finalize(att)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{AttestationBody, Kind};
+    use chrono::Utc;
+
+    fn span(start: u32, end: u32) -> Span {
+        Span {
+            start: Position {
+                line: start,
+                col: None,
+            },
+            end: Some(Position {
+                line: end,
+                col: None,
+            }),
+        }
+    }
+
+    fn pinned(subject: &str, digest: String, span: Option<Span>) -> Record {
+        Record::Attestation(Box::new(attestation::finalize(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: Utc::now(),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: Some(digest),
+                expires_at: None,
+                fingerprint: None,
+                kind: Kind::Pass,
+                r#ref: None,
+                score: 20,
+                span,
+                suggested_fix: None,
+                summary: "reviewed".into(),
+                supersedes: None,
+                symbol: None,
+                tags: vec![],
+            },
+        })))
+    }
+
+    #[test]
+    fn test_span_digest_covers_only_its_lines() {
+        let before = b"fn a() {}\nfn b() {}\nfn c() {}\n";
+        let after = b"fn a() {}\nfn b() {}\nfn c() { todo!() }\n";
+        let lines_1_2 = Some(span(1, 2));
+
+        assert_eq!(
+            content_digest(before, lines_1_2.as_ref()),
+            content_digest(after, lines_1_2.as_ref())
+        );
+        assert_ne!(content_digest(before, None), content_digest(after, None));
+        assert_ne!(
+            content_digest(before, Some(&span(3, 3))),
+            content_digest(after, Some(&span(3, 3)))
+        );
+        assert_eq!(
+            content_digest(before, None),
+            blake3::hash(before).to_hex().to_string()
+        );
+    }
+
+    #[test]
+    fn test_stale_records_detects_changed_and_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "one\ntwo\n").unwrap();
+        std::fs::write(dir.path().join("b.rs"), "same\n").unwrap();
+
+        let fresh = pinned("b.rs", content_digest(b"same\n", None), None);
+        let changed = pinned("a.rs", content_digest(b"one\nTWO\n", None), None);
+        let span_fresh = pinned("a.rs", content_digest(b"one\n", None), Some(span(1, 1)));
+        let missing = pinned("gone.rs", content_digest(b"", None), None);
+        let records = vec![fresh, changed.clone(), span_fresh, missing.clone()];
+
+        let stale = stale_records(dir.path(), &records);
+        assert_eq!(stale.len(), 2);
+        assert_eq!(stale[0].record.id(), changed.id());
+        assert_eq!(
+            stale[0].reason,
+            StaleReason::Changed {
+                current: content_digest(b"one\ntwo\n", None)
+            }
+        );
+        assert_eq!(stale[1].record.id(), missing.id());
+        assert_eq!(stale[1].reason, StaleReason::Missing);
+    }
+
+    const ORIGINAL: &str = "use std::io;\n\nfn parse() {\n    input.unwrap()\n}\n\nfn main() {}\n";
+
+    #[test]
+    fn test_locate_follows_moved_lines() {
+        let fp = fingerprint(ORIGINAL, &span(3, 5)).unwrap();
+        assert_eq!(fp.lines.len(), 3);
+        assert_eq!(fp.before.len(), 2);
+        assert_eq!(fp.after.len(), 2);
+        assert_eq!(locate(ORIGINAL, &span(3, 5), &fp), Anchor::Unchanged);
+
+        // Two lines inserted above, and the body re-indented.
+        let moved = "//! Parser\n\nuse std::io;\n\nfn parse() {\n        input.unwrap()\n}\n\nfn main() {}\n";
+        match locate(moved, &span(3, 5), &fp) {
+            Anchor::Moved {
+                span: new,
+                fingerprint,
+            } => {
+                assert_eq!(new, span(5, 7));
+                assert_eq!(fingerprint.lines, fp.lines);
+            }
+            other => panic!("expected Moved, got {other:?}"),
+        }
+
+        let rewritten = ORIGINAL.replace("input.unwrap()", "input?");
+        assert_eq!(locate(&rewritten, &span(3, 5), &fp), Anchor::Lost);
+    }
+
+    #[test]
+    fn test_locate_uses_context_to_break_ties() {
+        let content = "a\ntodo!()\nb\nc\ntodo!()\nd\n";
+        let fp = fingerprint(content, &span(5, 5)).unwrap();
+
+        // Both copies move down one line; context picks the right one.
+        let shifted = format!("new\n{content}");
+        match locate(&shifted, &span(5, 5), &fp) {
+            Anchor::Moved { span: new, .. } => assert_eq!(new, span(6, 6)),
+            other => panic!("expected Moved, got {other:?}"),
+        }
+
+        let no_context = Fingerprint {
+            after: vec![],
+            before: vec![],
+            lines: fp.lines.clone(),
+        };
+        assert_eq!(
+            locate("x\ntodo!()\ny\ntodo!()\n", &span(3, 3), &no_context),
+            Anchor::Ambiguous
+        );
+    }
+
+    #[test]
+    fn test_reanchored_supersedes_with_new_span() {
+        let fp = fingerprint(ORIGINAL, &span(3, 5)).unwrap();
+        let Record::Attestation(original) =
+            pinned("a.rs", content_digest(b"", None), Some(span(3, 5)))
+        else {
+            unreachable!()
+        };
+        let moved = format!("\n\n{ORIGINAL}");
+        let Anchor::Moved {
+            span: new,
+            fingerprint,
+        } = locate(&moved, &span(3, 5), &fp)
+        else {
+            panic!("expected Moved");
+        };
+
+        let att = reanchored(&original, new.clone(), fingerprint);
+        assert_eq!(att.body.supersedes.as_deref(), Some(original.id.as_str()));
+        assert_eq!(att.issuer, original.issuer);
+        assert_eq!(att.issuer_type, original.issuer_type);
+        assert_eq!(att.created_at, original.created_at);
+        assert_eq!(att.body.span, Some(new));
+        assert_eq!(att.body.digest, original.body.digest);
+        assert_eq!(att.body.summary, original.body.summary);
+        assert!(attestation::validate(&att).is_empty());
+    }
+}
@@ -0,0 +1,512 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use globset::{GlobBuilder, GlobMatcher};
+use serde::Serialize;
+
+use crate::attestation::{Attestation, IssuerType, Kind, PolicyRecord, PolicyRule, Record};
+use crate::qual_file::QualFile;
+use crate::scoring::{self, Evaluation, ScoreReport};
+
+/// A single failed policy check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    /// ID of the policy record that declared the rule.
+    pub policy_id: String,
+    /// The subject that failed the rule.
+    pub subject: String,
+    /// What went wrong.
+    #[serde(flatten)]
+    pub kind: ViolationKind,
+}
+
+/// The rule check that a subject failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum ViolationKind {
+    /// Effective score is below the rule's `min_score`.
+    BelowMinScore { min_score: i32, effective: i32 },
+    /// Fewer matching active attestations than a requirement demands.
+    MissingKind {
+        kind: Kind,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        issuer_type: Option<IssuerType>,
+        required: u32,
+        found: u32,
+    },
+    /// An active attestation has a forbidden kind.
+    ForbiddenKind { kind: Kind, record_id: String },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ViolationKind::BelowMinScore {
+                min_score,
+                effective,
+            } => write!(
+                f,
+                "{}: effective score {} is below policy minimum {}",
+                self.subject, effective, min_score
+            ),
+            ViolationKind::MissingKind {
+                kind,
+                issuer_type,
+                required,
+                found,
+            } => {
+                let from = match issuer_type {
+                    Some(t) => format!(" from a {t} issuer"),
+                    None => String::new(),
+                };
+                write!(
+                    f,
+                    "{}: requires {} active '{}'{}, found {}",
+                    self.subject, required, kind, from, found
+                )
+            }
+            ViolationKind::ForbiddenKind { kind, record_id } => write!(
+                f,
+                "{}: forbidden kind '{}' is active (record {})",
+                self.subject,
+                kind,
+                &record_id[..record_id.len().min(8)]
+            ),
+        }
+    }
+}
+
+/// Return the policy records that are not superseded or revoked, in discovery order.
+pub fn active_policies(qual_files: &[QualFile]) -> Vec<&PolicyRecord> {
+    let records = qual_files.iter().flat_map(|qf| qf.records.iter());
+    let inactive = scoring::inactive_ids(records.clone());
+    records
+        .filter_map(|r| r.as_policy())
+        .filter(|p| !inactive.contains(p.id.as_str()))
+        .collect()
+}
+
+/// Compile a policy `subjects` glob.
+pub fn compile_glob(pattern: &str) -> crate::Result<GlobMatcher> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .map(|g| g.compile_matcher())
+        .map_err(|e| crate::Error::Validation(format!("invalid glob '{pattern}': {e}")))
+}
+
+/// Returns true if an active (non-superseded, non-revoked) `record` still
+/// counts for policy under `eval`: it has not expired, and neither trust
+/// nor staleness weights it to nothing. Its score does not matter, so a
+/// deliberate score-0 `pass` still satisfies a requirement.
+pub fn in_force(record: &Record, eval: &Evaluation) -> bool {
+    if record.is_expired(eval.at) || eval.trust.weigh(record).percent == 0 {
+        return false;
+    }
+    eval.stale.weight > 0 || !eval.stale.ids.contains(record.id())
+}
+
+/// Evaluate every active policy against the given effective scores.
+///
+/// Each subject in `scores` or `files` is checked against every rule whose
+/// `subjects` glob matches it; `files` names subjects that may have no
+/// records, which score 0. Required and forbidden kinds are both checked
+/// against the subject's attestations that are in force under `eval` (see
+/// [`in_force`]), so an expired or untrusted record neither satisfies a
+/// requirement nor trips a ban. Violations are returned sorted by subject,
+/// then in policy and rule order.
+pub fn evaluate(
+    qual_files: &[QualFile],
+    files: &[String],
+    scores: &HashMap<String, ScoreReport>,
+    eval: &Evaluation,
+) -> crate::Result<Vec<Violation>> {
+    let policies = active_policies(qual_files);
+    if policies.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut rules: Vec<(&PolicyRecord, &PolicyRule, GlobMatcher)> = Vec::new();
+    for policy in &policies {
+        for rule in &policy.body.rules {
+            rules.push((policy, rule, compile_glob(&rule.subjects)?));
+        }
+    }
+
+    let records = qual_files.iter().flat_map(|qf| qf.records.iter());
+    let inactive = scoring::inactive_ids(records.clone());
+    let mut active: HashMap<&str, Vec<&Attestation>> = HashMap::new();
+    for record in records.filter(|r| !inactive.contains(r.id()) && in_force(r, eval)) {
+        if let Some(att) = record.as_attestation() {
+            active.entry(att.subject.as_str()).or_default().push(att);
+        }
+    }
+
+    let subjects: BTreeSet<&String> = scores.keys().chain(files).collect();
+
+    let mut violations = Vec::new();
+    for subject in subjects {
+        let effective = scores.get(subject).map_or(0, |report| report.effective);
+        let atts = active
+            .get(subject.as_str())
+            .map(Vec::as_slice)
+            .unwrap_or(&[]);
+
+        for (policy, rule, matcher) in &rules {
+            if !matcher.is_match(subject) {
+                continue;
+            }
+            let mut violate = |kind| {
+                violations.push(Violation {
+                    policy_id: policy.id.clone(),
+                    subject: subject.clone(),
+                    kind,
+                })
+            };
+
+            if let Some(min_score) = rule.min_score
+                && effective < min_score
+            {
+                violate(ViolationKind::BelowMinScore {
+                    min_score,
+                    effective,
+                });
+            }
+
+            for req in &rule.require {
+                let required = req.min_count.unwrap_or(1);
+                let found = atts
+                    .iter()
+                    .filter(|a| a.body.kind == req.kind)
+                    .filter(|a| {
+                        req.issuer_type
+                            .as_ref()
+                            .is_none_or(|t| a.issuer_type.as_ref() == Some(t))
+                    })
+                    .count() as u32;
+                if found < required {
+                    violate(ViolationKind::MissingKind {
+                        kind: req.kind.clone(),
+                        issuer_type: req.issuer_type.clone(),
+                        required,
+                        found,
+                    });
+                }
+            }
+
+            for att in atts.iter().filter(|a| rule.forbid.contains(&a.body.kind)) {
+                violate(ViolationKind::ForbiddenKind {
+                    kind: att.body.kind.clone(),
+                    record_id: att.id.clone(),
+                });
+            }
+        }
+    }
+
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{self, AttestationBody, PolicyBody, Record, Requirement, fixtures};
+    use crate::graph::DependencyGraph;
+    use crate::scoring;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn make_record(
+        subject: &str,
+        kind: Kind,
+        score: i32,
+        issuer_type: Option<IssuerType>,
+    ) -> Record {
+        Record::Attestation(Box::new(attestation::finalize(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type,
+            created_at: chrono::DateTime::parse_from_rfc3339("2026-02-24T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
+                kind,
+                r#ref: None,
+                score,
+                span: None,
+                suggested_fix: None,
+                summary: "x".into(),
+                supersedes: None,
+                symbol: None,
+                tags: vec![],
+            },
+        })))
+    }
+
+    fn make_policy(rules: Vec<PolicyRule>, supersedes: Option<&str>) -> Record {
+        attestation::finalize_record(Record::Policy(PolicyRecord {
+            metabox: "1".into(),
+            record_type: "policy".into(),
+            subject: ".".into(),
+            issuer: "mailto:lead@test.com".into(),
+            issuer_type: None,
+            created_at: chrono::DateTime::parse_from_rfc3339("2026-02-24T09:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            id: String::new(),
+            body: PolicyBody {
+                rules,
+                summary: "release gate".into(),
+                supersedes: supersedes.map(String::from),
+            },
+        }))
+    }
+
+    fn rule(subjects: &str) -> PolicyRule {
+        PolicyRule {
+            forbid: vec![],
+            min_score: None,
+            require: vec![],
+            subjects: subjects.into(),
+        }
+    }
+
+    fn eval(records: Vec<Record>) -> Vec<Violation> {
+        eval_with(records, &[], &scoring::Evaluation::at(Utc::now()))
+    }
+
+    fn eval_with(records: Vec<Record>, files: &[&str], at: &Evaluation) -> Vec<Violation> {
+        let qfs = vec![QualFile {
+            path: PathBuf::from(".qual"),
+            subject: "./".into(),
+            records,
+        }];
+        let scores = scoring::effective_scores(&DependencyGraph::empty(), &qfs, at);
+        let files: Vec<String> = files.iter().map(|f| f.to_string()).collect();
+        evaluate(&qfs, &files, &scores, at).unwrap()
+    }
+
+    #[test]
+    fn test_no_policies_no_violations() {
+        let v = eval(vec![make_record("src/a.rs", Kind::Blocker, -50, None)]);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn test_min_score_per_glob() {
+        let policy = make_policy(
+            vec![PolicyRule {
+                min_score: Some(10),
+                ..rule("src/**")
+            }],
+            None,
+        );
+        let v = eval(vec![
+            policy,
+            make_record("src/a.rs", Kind::Concern, -10, None),
+            make_record("docs/b.md", Kind::Concern, -10, None),
+        ]);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].subject, "src/a.rs");
+        assert!(matches!(
+            v[0].kind,
+            ViolationKind::BelowMinScore {
+                min_score: 10,
+                effective: -10
+            }
+        ));
+    }
+
+    #[test]
+    fn test_require_kind_from_issuer_type() {
+        let policy = make_policy(
+            vec![PolicyRule {
+                require: vec![Requirement {
+                    issuer_type: Some(IssuerType::Human),
+                    kind: Kind::Pass,
+                    min_count: None,
+                }],
+                ..rule("*.rs")
+            }],
+            None,
+        );
+        let v = eval(vec![
+            policy,
+            make_record("a.rs", Kind::Pass, 20, Some(IssuerType::Ai)),
+            make_record("b.rs", Kind::Pass, 20, Some(IssuerType::Human)),
+        ]);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].subject, "a.rs");
+        assert!(matches!(
+            v[0].kind,
+            ViolationKind::MissingKind {
+                required: 1,
+                found: 0,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_require_applies_to_files_without_records() {
+        let policy = make_policy(
+            vec![PolicyRule {
+                require: vec![Requirement {
+                    issuer_type: None,
+                    kind: Kind::Pass,
+                    min_count: None,
+                }],
+                ..rule("src/**")
+            }],
+            None,
+        );
+        let at = scoring::Evaluation::at(Utc::now());
+        let v = eval_with(vec![policy], &["src/a.rs", "docs/b.md"], &at);
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0].subject, "src/a.rs");
+        assert!(matches!(
+            v[0].kind,
+            ViolationKind::MissingKind { found: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn test_policy_ignores_records_no_longer_in_force() {
+        let policy = make_policy(
+            vec![PolicyRule {
+                forbid: vec![Kind::Blocker],
+                require: vec![Requirement {
+                    issuer_type: None,
+                    kind: Kind::Pass,
+                    min_count: None,
+                }],
+                ..rule("*.rs")
+            }],
+            None,
+        );
+        let date = |s: &str| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let lead = |subject: &str, kind: Kind, score: i32, expires_at: Option<&str>| {
+            Record::Attestation(Box::new(attestation::finalize(Attestation {
+                issuer: "mailto:lead@test.com".into(),
+                ..fixtures::attestation(
+                    subject,
+                    AttestationBody {
+                        kind,
+                        score,
+                        summary: "x".into(),
+                        expires_at: expires_at.map(date),
+                        ..Default::default()
+                    },
+                )
+            })))
+        };
+        let untrusted = make_record("b.rs", Kind::Pass, 20, None);
+        let trust = crate::trust::Trust::new(crate::trust::TrustModel {
+            issuers: [(untrusted.issuer().unwrap().to_string(), 0)].into(),
+            ..Default::default()
+        })
+        .unwrap();
+        let at = scoring::Evaluation::at(date("2100-01-01T00:00:00Z")).with_trust(trust);
+
+        let v = eval_with(
+            vec![
+                policy,
+                lead("a.rs", Kind::Pass, 20, Some("2099-01-01T00:00:00Z")),
+                untrusted,
+                // A deliberate score-0 pass still counts...
+                lead("c.rs", Kind::Pass, 0, None),
+                // ...and an expired blocker no longer does.
+                lead("d.rs", Kind::Pass, 20, None),
+                lead("d.rs", Kind::Blocker, -50, Some("2099-01-01T00:00:00Z")),
+            ],
+            &[],
+            &at,
+        );
+        let subjects: Vec<&str> = v.iter().map(|v| v.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["a.rs", "b.rs"], "{v:?}");
+        assert!(
+            v.iter()
+                .all(|v| matches!(v.kind, ViolationKind::MissingKind { found: 0, .. }))
+        );
+    }
+
+    #[test]
+    fn test_forbid_active_kind() {
+        let policy = make_policy(
+            vec![PolicyRule {
+                forbid: vec![Kind::Blocker],
+                ..rule("**")
+            }],
+            None,
+        );
+        let blocker = make_record("a.rs", Kind::Blocker, -50, None);
+        let blocker_id = blocker.id().to_string();
+        let v = eval(vec![policy, blocker]);
+        assert_eq!(v.len(), 1);
+        assert_eq!(
+            v[0].kind,
+            ViolationKind::ForbiddenKind {
+                kind: Kind::Blocker,
+                record_id: blocker_id,
+            }
+        );
+    }
+
+    #[test]
+    fn test_superseded_policy_is_inactive() {
+        let strict = make_policy(
+            vec![PolicyRule {
+                min_score: Some(50),
+                ..rule("**")
+            }],
+            None,
+        );
+        let relaxed = make_policy(
+            vec![PolicyRule {
+                min_score: Some(0),
+                ..rule("**")
+            }],
+            Some(strict.id()),
+        );
+        let v = eval(vec![
+            strict,
+            relaxed,
+            make_record("a.rs", Kind::Pass, 20, None),
+        ]);
+        assert!(v.is_empty(), "only the relaxed policy applies: {v:?}");
+    }
+
+    #[test]
+    fn test_policy_subject_is_not_scored() {
+        let policy = make_policy(
+            vec![PolicyRule {
+                min_score: Some(10),
+                ..rule("**")
+            }],
+            None,
+        );
+        let v = eval(vec![policy, make_record("a.rs", Kind::Pass, 20, None)]);
+        assert!(v.is_empty(), "the policy's own subject is not an artifact");
+    }
+
+    #[test]
+    fn test_invalid_glob_is_error() {
+        let qfs = vec![QualFile {
+            path: PathBuf::from(".qual"),
+            subject: "./".into(),
+            records: vec![make_policy(vec![rule("src/[")], None)],
+        }];
+        let at = scoring::Evaluation::at(Utc::now());
+        assert!(evaluate(&qfs, &[], &HashMap::new(), &at).is_err());
+    }
+}
@@ -1,7 +1,11 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+use serde::Serialize;
+
 use crate::attestation::{Attestation, Record};
 
 /// A parsed `.qual` file.
@@ -42,6 +46,104 @@ pub fn parse(path: &Path) -> crate::Result<QualFile> {
     })
 }
 
+/// The category of a malformed `.qual` line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// Not valid JSON.
+    Syntax,
+    /// Valid JSON that is not a valid record (e.g. a missing field).
+    Data,
+    /// The line ends in the middle of a JSON value.
+    Eof,
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticKind::Syntax => write!(f, "syntax"),
+            DiagnosticKind::Data => write!(f, "data"),
+            DiagnosticKind::Eof => write!(f, "eof"),
+        }
+    }
+}
+
+/// A malformed line found while parsing leniently.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// The `.qual` file.
+    pub path: PathBuf,
+    /// 1-indexed line number.
+    pub line: usize,
+    /// 1-indexed column within the line.
+    pub column: usize,
+    /// What kind of error it is.
+    pub kind: DiagnosticKind,
+    /// The parser's message.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {} error: {}",
+            self.path.display(),
+            self.line,
+            self.column,
+            self.kind,
+            self.message
+        )
+    }
+}
+
+/// Parse a `.qual` file from disk, skipping malformed lines.
+///
+/// Returns every valid record plus one [`Diagnostic`] per malformed line.
+/// Only I/O failures are errors.
+pub fn parse_lenient(path: &Path) -> crate::Result<(QualFile, Vec<Diagnostic>)> {
+    let content = fs::read_to_string(path)?;
+    let mut records = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+        match serde_json::from_str::<Record>(trimmed) {
+            Ok(record) => records.push(record),
+            Err(e) => {
+                let indent = line.len() - line.trim_start().len();
+                let kind = match e.classify() {
+                    serde_json::error::Category::Data => DiagnosticKind::Data,
+                    serde_json::error::Category::Eof => DiagnosticKind::Eof,
+                    _ => DiagnosticKind::Syntax,
+                };
+                let message = e.to_string();
+                let message = match message.rfind(" at line ") {
+                    Some(at) => message[..at].to_string(),
+                    None => message,
+                };
+                diagnostics.push(Diagnostic {
+                    path: path.to_path_buf(),
+                    line: line_no + 1,
+                    column: e.column().max(1) + indent,
+                    kind,
+                    message,
+                });
+            }
+        }
+    }
+
+    let qual_file = QualFile {
+        path: path.to_path_buf(),
+        subject: subject_name(path),
+        records,
+    };
+    Ok((qual_file, diagnostics))
+}
+
 /// Parse records from a string (for testing or in-memory use).
 pub fn parse_str(content: &str) -> crate::Result<Vec<Record>> {
     let mut records = Vec::new();
@@ -60,23 +162,222 @@ pub fn parse_str(content: &str) -> crate::Result<Vec<Record>> {
 /// Append a record to a `.qual` file.
 ///
 /// Creates the file if it doesn't exist. Always appends with a trailing newline.
+/// Holds the file's [`lock`] for the duration of the write.
 pub fn append(path: &Path, record: &Record) -> crate::Result<()> {
-    let mut file = fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(path)?;
-    let json = serde_json::to_string(record)?;
-    writeln!(file, "{json}")?;
-    Ok(())
+    lock(path)?.append(record)
 }
 
 /// Write a complete `.qual` file (used by compaction).
+///
+/// Holds the file's [`lock`] for the duration of the write.
 pub fn write_all(path: &Path, records: &[Record]) -> crate::Result<()> {
-    let mut file = fs::File::create(path)?;
-    for record in records {
-        let json = serde_json::to_string(record)?;
-        writeln!(file, "{json}")?;
+    lock(path)?.write_all(records)
+}
+
+/// An exclusive advisory lock on a `.qual` file, released on drop.
+///
+/// Every qualifier write takes this lock, so a read-validate-write sequence
+/// (supersession checks before an append, or a compaction rewrite) never
+/// interleaves with another qualifier process writing the same file. The
+/// lock is advisory: it does not stop other programs from writing.
+#[derive(Debug)]
+pub struct QualLock {
+    file: fs::File,
+    path: PathBuf,
+}
+
+/// Open `path`, creating it if needed, and block until this process holds
+/// its exclusive lock.
+pub fn lock(path: &Path) -> crate::Result<QualLock> {
+    loop {
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        file.lock()?;
+        // Another process may have removed or replaced the file while we
+        // waited; a lock on the old inode would protect nothing.
+        if is_same_file(&file, path)? {
+            return Ok(QualLock {
+                file,
+                path: path.to_path_buf(),
+            });
+        }
+    }
+}
+
+/// [`lock`] every path in `paths`, in sorted order, so two processes
+/// locking overlapping sets of files never wait on each other.
+pub fn lock_all(paths: BTreeSet<PathBuf>) -> crate::Result<BTreeMap<PathBuf, QualLock>> {
+    paths
+        .into_iter()
+        .map(|path| Ok((path.clone(), lock(&path)?)))
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_same_file(file: &fs::File, path: &Path) -> crate::Result<bool> {
+    use std::os::unix::fs::MetadataExt;
+
+    let held = file.metadata()?;
+    match fs::metadata(path) {
+        Ok(current) => Ok(held.dev() == current.dev() && held.ino() == current.ino()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
     }
+}
+
+#[cfg(not(unix))]
+fn is_same_file(_file: &fs::File, _path: &Path) -> crate::Result<bool> {
+    // Files cannot be removed or replaced while open on these platforms.
+    Ok(true)
+}
+
+impl QualLock {
+    /// The locked `.qual` file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Parse the locked file. A file created by [`lock`] has no records.
+    pub fn parse(&self) -> crate::Result<QualFile> {
+        parse(&self.path)
+    }
+
+    /// Append a record in a single write, so readers never see a partial line.
+    pub fn append(&mut self, record: &Record) -> crate::Result<()> {
+        let line = format!("{}\n", serde_json::to_string(record)?);
+        self.file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Replace the file's contents with `records`. See [`QualLock::replace`].
+    pub fn write_all(self, records: &[Record]) -> crate::Result<()> {
+        self.replace(records, false, |_| Ok(()))
+    }
+
+    /// Atomically replace the file's contents with `records`.
+    ///
+    /// The records are written to a temporary file in the same directory,
+    /// which is fsynced and parsed back. Only if `verify` accepts the parsed
+    /// file is it renamed over the original, so a crash or a failed check
+    /// leaves the original untouched. With `backup`, the original is first
+    /// copied to [`backup_path`].
+    pub fn replace(
+        self,
+        records: &[Record],
+        backup: bool,
+        verify: impl FnOnce(&QualFile) -> crate::Result<()>,
+    ) -> crate::Result<()> {
+        let mut content = String::new();
+        for record in records {
+            content.push_str(&serde_json::to_string(record)?);
+            content.push('\n');
+        }
+
+        let staged = Staged::create(&self.path)?;
+        let mut file = &staged.file;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+
+        let mut written = parse(&staged.path)?;
+        written.path = self.path.clone();
+        written.subject = subject_name(&self.path);
+        if written.records.len() != records.len() {
+            return Err(crate::Error::Validation(format!(
+                "{}: wrote {} records but read back {}",
+                self.path.display(),
+                records.len(),
+                written.records.len()
+            )));
+        }
+        verify(&written)?;
+
+        if backup {
+            fs::copy(&self.path, backup_path(&self.path))?;
+        }
+        staged.commit(&self.path)
+    }
+
+    /// Atomically replace the file's contents with `content`, which is not
+    /// parsed. Used for files that share the `.qual` locking discipline but
+    /// not its format, such as the dependency graph file.
+    pub fn replace_content(self, content: &str) -> crate::Result<()> {
+        let staged = Staged::create(&self.path)?;
+        let mut file = &staged.file;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        staged.commit(&self.path)
+    }
+}
+
+/// Where `--backup` keeps the previous contents of `path`: `src/.qual`
+/// becomes `src/.qual.bak`.
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+/// A locked temporary file next to its destination, removed on drop unless
+/// committed.
+struct Staged {
+    file: fs::File,
+    path: PathBuf,
+    committed: bool,
+}
+
+impl Staged {
+    fn create(dest: &Path) -> crate::Result<Self> {
+        let name = dest
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let path = dest.with_file_name(format!("{name}.tmp-{}", std::process::id()));
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        // Lock before the rename, so writers that open the new file wait
+        // for the old lock holder to finish.
+        file.lock()?;
+        Ok(Staged {
+            file,
+            path,
+            committed: false,
+        })
+    }
+
+    fn commit(mut self, dest: &Path) -> crate::Result<()> {
+        fs::rename(&self.path, dest)?;
+        self.committed = true;
+        sync_parent(dest)
+    }
+}
+
+impl Drop for Staged {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Make a rename in `path`'s directory durable.
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> crate::Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> crate::Result<()> {
     Ok(())
 }
 
@@ -99,30 +400,35 @@ pub fn resolve_qual_path(subject: &str, explicit_path: Option<&Path>) -> crate::
         return Ok(p.to_path_buf());
     }
 
+    let qual_path = default_qual_path(subject);
+
+    // Create parent directories if needed
+    if let Some(dir) = qual_path.parent()
+        && !dir.as_os_str().is_empty()
+        && !dir.exists()
+    {
+        fs::create_dir_all(dir)?;
+    }
+
+    Ok(qual_path)
+}
+
+/// The `.qual` file [`resolve_qual_path`] would pick for `subject` without an
+/// explicit path, without creating any directories.
+pub fn default_qual_path(subject: &str) -> PathBuf {
     // 1. Check for existing 1:1 file
     let one_to_one = PathBuf::from(format!("{subject}.qual"));
     if one_to_one.exists() {
-        return Ok(one_to_one);
+        return one_to_one;
     }
 
     // 2. Default to directory-level .qual
-    let subject_path = Path::new(subject);
-    let parent = subject_path.parent().unwrap_or(Path::new("."));
-    let dir_qual = if parent.as_os_str().is_empty() {
+    let parent = Path::new(subject).parent().unwrap_or(Path::new("."));
+    if parent.as_os_str().is_empty() {
         PathBuf::from(".qual")
     } else {
         parent.join(".qual")
-    };
-
-    // Create parent directories if needed
-    if let Some(dir) = dir_qual.parent()
-        && !dir.as_os_str().is_empty()
-        && !dir.exists()
-    {
-        fs::create_dir_all(dir)?;
     }
-
-    Ok(dir_qual)
 }
 
 /// Find all records for a given subject across all discovered `.qual` files.
@@ -181,6 +487,105 @@ pub fn find_qual_file_for(subject: &str) -> Option<PathBuf> {
 ///
 /// Returns them sorted by path for determinism.
 pub fn discover(root: &Path, respect_ignore: bool) -> crate::Result<Vec<QualFile>> {
+    discover_paths(root, respect_ignore)?
+        .iter()
+        .map(|path| parse(path))
+        .collect()
+}
+
+/// Discover and leniently parse all `.qual` files under a root directory.
+///
+/// Like [`discover`], but malformed lines are skipped and reported as
+/// diagnostics instead of aborting the walk.
+pub fn discover_lenient(
+    root: &Path,
+    respect_ignore: bool,
+) -> crate::Result<(Vec<QualFile>, Vec<Diagnostic>)> {
+    let mut qual_files = Vec::new();
+    let mut diagnostics = Vec::new();
+    for path in discover_paths(root, respect_ignore)? {
+        let (qual_file, found) = parse_lenient(&path)?;
+        qual_files.push(qual_file);
+        diagnostics.extend(found);
+    }
+    Ok((qual_files, diagnostics))
+}
+
+/// Find the `.qual` files [`discover`] would parse, without parsing them.
+///
+/// Paths are sorted.
+pub fn discover_paths(root: &Path, respect_ignore: bool) -> crate::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in walk(root, respect_ignore) {
+        let entry = entry.map_err(|e| crate::Error::Io(std::io::Error::other(e)))?;
+        let path = entry.path();
+        if path.is_file() && is_qual_path(path) {
+            paths.push(path.to_path_buf());
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Find the files under `root` that can be subjects, as subject names
+/// relative to `root`.
+///
+/// Walks the same tree as [`discover`], skipping `.qual` files, the files
+/// qualifier keeps beside them (see [`is_qual_companion`]), and every path
+/// in `managed`, such as the graph file, config, and keys. Names are sorted.
+pub fn discover_subjects(
+    root: &Path,
+    respect_ignore: bool,
+    managed: &[PathBuf],
+) -> crate::Result<Vec<String>> {
+    let managed: Vec<String> = managed.iter().map(|p| relative_name(p, root)).collect();
+    let mut subjects = Vec::new();
+    for entry in walk(root, respect_ignore) {
+        let entry = entry.map_err(|e| crate::Error::Io(std::io::Error::other(e)))?;
+        let path = entry.path();
+        if !path.is_file() || is_qual_path(path) || is_qual_companion(path) {
+            continue;
+        }
+        let name = relative_name(path, root);
+        if !managed.contains(&name) {
+            subjects.push(name);
+        }
+    }
+    subjects.sort();
+    Ok(subjects)
+}
+
+/// Returns true if `path` is a file qualifier writes next to a `.qual`
+/// file: its signature sidecar (`.qual.sig`), its `--backup` copy
+/// (`.qual.bak`), or a staged replacement of either (`.tmp-<pid>`).
+pub fn is_qual_companion(path: &Path) -> bool {
+    let Some((base, suffix)) = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|n| n.rsplit_once('.'))
+    else {
+        return false;
+    };
+    if suffix != "sig" && suffix != "bak" && !suffix.starts_with("tmp-") {
+        return false;
+    }
+    let base = path.with_file_name(base);
+    is_qual_path(&base) || is_qual_companion(&base)
+}
+
+/// `path` relative to `root`, with `/` separators and no `./` prefix.
+fn relative_name(path: &Path, root: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let names: Vec<String> = relative
+        .components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    names.join("/")
+}
+
+/// Walk `root` the way [`discover`] does.
+fn walk(root: &Path, respect_ignore: bool) -> ignore::Walk {
     use ignore::WalkBuilder;
 
     let mut builder = WalkBuilder::new(root);
@@ -208,20 +613,13 @@ pub fn discover(root: &Path, respect_ignore: bool) -> crate::Result<Vec<QualFile
         }
         true
     });
+    builder.build()
+}
 
-    let mut qual_files = Vec::new();
-    for entry in builder.build() {
-        let entry = entry.map_err(|e| crate::Error::Io(std::io::Error::other(e)))?;
-        let path = entry.path();
-        if path.is_file()
-            && (path.extension().and_then(|e| e.to_str()) == Some("qual")
-                || path.file_name().and_then(|f| f.to_str()) == Some(".qual"))
-        {
-            qual_files.push(parse(path)?);
-        }
-    }
-    qual_files.sort_by(|a, b| a.path.cmp(&b.path));
-    Ok(qual_files)
+/// Returns true if `path` names a `.qual` file (`.qual` or `*.qual`).
+pub fn is_qual_path(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("qual")
+        || path.file_name().and_then(|f| f.to_str()) == Some(".qual")
 }
 
 /// Derive the subject name from a `.qual` file path.
@@ -316,6 +714,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind,
                 r#ref: None,
                 score,
@@ -323,6 +724,7 @@ mod tests {
                 suggested_fix: None,
                 summary: summary.into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         })
@@ -389,6 +791,43 @@ mod tests {
         assert_eq!(parsed.records.len(), 1);
     }
 
+    #[test]
+    fn test_parse_lenient_collects_diagnostics() {
+        let dir = tempfile::tempdir().unwrap();
+        let qual_path = dir.path().join(".qual");
+
+        let first = serde_json::to_string(&make_attestation("a.rs", Kind::Pass, 10, "ok")).unwrap();
+        let second =
+            serde_json::to_string(&make_attestation("b.rs", Kind::Praise, 30, "nice")).unwrap();
+        fs::write(
+            &qual_path,
+            format!("{first}\n<<<<<<< HEAD\n  {{\"metabox\":\"1\"}}\n{{\"metabox\":\n{second}\n"),
+        )
+        .unwrap();
+
+        assert!(parse(&qual_path).is_err());
+
+        let (parsed, diagnostics) = parse_lenient(&qual_path).unwrap();
+        assert_eq!(parsed.records.len(), 2);
+        let found: Vec<(usize, usize, DiagnosticKind)> = diagnostics
+            .iter()
+            .map(|d| (d.line, d.column, d.kind))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (2, 1, DiagnosticKind::Syntax),
+                (3, 3, DiagnosticKind::Data),
+                (4, 11, DiagnosticKind::Eof),
+            ]
+        );
+        assert!(diagnostics[1].to_string().contains(".qual:3:3: data error"));
+
+        let (files, all) = discover_lenient(dir.path(), false).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(all, diagnostics);
+    }
+
     #[test]
     fn test_discover() {
         let dir = tempfile::tempdir().unwrap();
@@ -408,6 +847,33 @@ mod tests {
         assert_eq!(found.len(), 2);
     }
 
+    #[test]
+    fn test_discover_subjects_skips_managed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.rs"), "fn main() {}").unwrap();
+        fs::write(src.join("b.rs"), "").unwrap();
+        append(
+            &src.join(".qual"),
+            &make_record("src/a.rs", Kind::Pass, 10, "ok"),
+        )
+        .unwrap();
+        for companion in [
+            ".qual.sig",
+            ".qual.bak",
+            ".qual.tmp-42",
+            "a.rs.qual.sig.tmp-42",
+        ] {
+            fs::write(src.join(companion), "").unwrap();
+        }
+        fs::write(dir.path().join("qualifier.graph.jsonl"), "").unwrap();
+
+        let managed = vec![dir.path().join("qualifier.graph.jsonl")];
+        let subjects = discover_subjects(dir.path(), true, &managed).unwrap();
+        assert_eq!(subjects, vec!["src/a.rs", "src/b.rs"]);
+    }
+
     #[test]
     fn test_discover_skips_hidden_dirs() {
         let dir = tempfile::tempdir().unwrap();
@@ -469,6 +935,98 @@ mod tests {
         assert_eq!(parsed.records[1].id(), id2);
     }
 
+    #[test]
+    fn test_locked_appends_survive_concurrent_rewrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let qual_path = dir.path().join(".qual");
+
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let path = qual_path.clone();
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        let record = make_record("a.rs", Kind::Pass, 1, &format!("w{t}-{i}"));
+                        append(&path, &record).unwrap();
+                    }
+                })
+            })
+            .collect();
+        // Replace the file, as compaction does, while appends race.
+        for _ in 0..25 {
+            let lock = lock(&qual_path).unwrap();
+            let records = lock.parse().unwrap().records;
+            lock.write_all(&records).unwrap();
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(parse(&qual_path).unwrap().records.len(), 100);
+    }
+
+    #[test]
+    fn test_replace_is_all_or_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let qual_path = dir.path().join(".qual");
+        let r1 = make_record("a.rs", Kind::Praise, 40, "Good");
+        let r2 = make_record("a.rs", Kind::Concern, -20, "Bad");
+        write_all(&qual_path, &[r1.clone(), r2]).unwrap();
+        let original = fs::read_to_string(&qual_path).unwrap();
+
+        let err = lock(&qual_path)
+            .unwrap()
+            .replace(std::slice::from_ref(&r1), true, |_| {
+                Err(crate::Error::Validation("rejected".into()))
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("rejected"));
+        assert_eq!(fs::read_to_string(&qual_path).unwrap(), original);
+        assert!(!backup_path(&qual_path).exists());
+
+        lock(&qual_path)
+            .unwrap()
+            .replace(std::slice::from_ref(&r1), true, |written| {
+                assert_eq!(written.path, qual_path);
+                assert_eq!(written.records.len(), 1);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(parse(&qual_path).unwrap().records.len(), 1);
+        assert_eq!(
+            fs::read_to_string(backup_path(&qual_path)).unwrap(),
+            original
+        );
+
+        // No temporary files are left behind either way.
+        let mut names: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec![".qual", ".qual.bak"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_lock_follows_replaced_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let qual_path = dir.path().join(".qual");
+        let held = lock(&qual_path).unwrap();
+
+        let path = qual_path.clone();
+        let waiter = std::thread::spawn(move || {
+            append(&path, &make_record("a.rs", Kind::Pass, 1, "after")).unwrap();
+        });
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        // Replace the file while the waiter is blocked on the old one.
+        fs::remove_file(&qual_path).unwrap();
+        fs::write(&qual_path, "").unwrap();
+        drop(held);
+        waiter.join().unwrap();
+
+        assert_eq!(parse(&qual_path).unwrap().records.len(), 1);
+    }
+
     #[test]
     fn test_find_project_root() {
         let dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,511 @@
+//! Subject renames (SPEC §2.3.1).
+//!
+//! Record IDs are content-addressed over the `subject` field, so records
+//! cannot simply be edited to carry a new name. Instead, the old subject's
+//! scored history is folded into one epoch under the new name whose `refs`
+//! list the old record IDs, and dependency records are re-issued with the
+//! new name. Attestations an epoch cannot stand in for, such as those that
+//! have not yet expired, are re-issued with the new name as well.
+
+use std::collections::HashSet;
+
+use crate::attestation::{self, Epoch, EpochBody, IssuerType, Record};
+use crate::compact;
+use crate::qual_file::QualFile;
+use crate::scoring;
+
+/// Issuer of the epochs written by a rename.
+pub const RENAME_ISSUER: &str = "urn:qualifier:rename";
+
+/// The planned effect of renaming a subject across a set of `.qual` files.
+#[derive(Debug, Clone, Default)]
+pub struct Rename {
+    /// Rewritten contents of every `.qual` file the rename touches, in
+    /// discovery order. A file may end up with no records.
+    pub files: Vec<QualFile>,
+    /// Records to append under the new subject: the carry-over epoch (if the
+    /// old subject had scored records to fold), then re-issued attestations,
+    /// then re-issued dependency records.
+    pub carried: Vec<Record>,
+    /// IDs of the old subject's scored records folded into the epoch.
+    pub folded: Vec<String>,
+    /// Number of dependency records rewritten or re-issued.
+    pub rewired: usize,
+}
+
+impl Rename {
+    /// Returns true if no record refers to the old subject.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// The carry-over epoch, if one was created.
+    pub fn epoch(&self) -> Option<&Epoch> {
+        self.carried.iter().find_map(|r| r.as_epoch())
+    }
+}
+
+/// Plan the rename of `old` to `new` across `qual_files`.
+///
+/// - Attestations and epochs about `old` are removed and replaced by a single
+///   epoch about `new` with the same raw score under `eval` and `refs`
+///   pointing at every removed record.
+/// - Active attestations whose contribution is more than their score (see
+///   [`compact::keeps_own_weight`]) are re-issued about `new` instead of
+///   folded, keeping their `created_at` and `expires_at`.
+/// - Dependency records about `old` are re-issued about `new`.
+/// - Dependency records that depend on `old` are rewritten in place to depend
+///   on `new`.
+/// - Revoked dependency records are left as they are, so a rename does not
+///   bring back edges removed with `dep rm`.
+/// - Revocations about `old` whose targets were all removed are dropped
+///   with them (see [`compact::is_spent_revocation`]).
+///
+/// Policies and unknown record types are left untouched. Nothing is written;
+/// the caller applies the returned plan.
+pub fn rename(
+    qual_files: &[QualFile],
+    old: &str,
+    new: &str,
+    eval: &scoring::Evaluation,
+) -> crate::Result<Rename> {
+    if old == new {
+        return Err(crate::Error::Validation(format!(
+            "'{old}' is already named '{new}'"
+        )));
+    }
+    if new.is_empty() {
+        return Err(crate::Error::Validation(
+            "new subject name must not be empty".into(),
+        ));
+    }
+
+    let inactive = scoring::inactive_ids(qual_files.iter().flat_map(|qf| &qf.records));
+    let removed: Vec<Record> = qual_files
+        .iter()
+        .flat_map(|qf| &qf.records)
+        .filter(|r| r.is_scored() && r.subject() == old)
+        .cloned()
+        .collect();
+    let mut plan = Rename::default();
+    let mut scored: Vec<Record> = Vec::new();
+    let mut reissued: Vec<Record> = Vec::new();
+
+    for qf in qual_files {
+        let mut changed = false;
+        let mut records = Vec::with_capacity(qf.records.len());
+
+        for record in &qf.records {
+            match record {
+                r if r.is_scored() && r.subject() == old => {
+                    scored.push(with_subject(r, new));
+                    changed = true;
+                }
+                r if r.subject() == old && compact::is_spent_revocation(r, &removed) => {
+                    changed = true;
+                }
+                Record::Dependency(dep) if inactive.contains(dep.id.as_str()) => {
+                    records.push(record.clone());
+                }
+                Record::Dependency(dep) if dep.subject == old => {
+                    let mut moved = dep.clone();
+                    moved.subject = new.to_string();
+                    rewire(&mut moved.body.depends_on, old, new);
+                    reissued.push(attestation::finalize_record(Record::Dependency(moved)));
+                    plan.rewired += 1;
+                    changed = true;
+                }
+                Record::Dependency(dep) if dep.body.depends_on.iter().any(|d| d == old) => {
+                    let mut updated = dep.clone();
+                    rewire(&mut updated.body.depends_on, old, new);
+                    records.push(attestation::finalize_record(Record::Dependency(updated)));
+                    plan.rewired += 1;
+                    changed = true;
+                }
+                other => records.push(other.clone()),
+            }
+        }
+
+        if changed {
+            plan.files.push(QualFile {
+                path: qf.path.clone(),
+                subject: qf.subject.clone(),
+                records,
+            });
+        }
+    }
+
+    // Decide what to fold as if the records were already about `new`, so
+    // the new subject's trust rules apply. IDs are still the old ones.
+    let scored_refs: Vec<&Record> = scored.iter().collect();
+    let (folded, kept) = compact::split_fold(&scored_refs, &inactive, eval, RENAME_ISSUER);
+    if !folded.is_empty() {
+        let active: Vec<&Record> = folded
+            .iter()
+            .copied()
+            .filter(|r| !inactive.contains(r.id()))
+            .collect();
+        let score = scoring::raw_score_from_refs(&active, eval);
+        plan.folded = folded.iter().map(|r| r.id().to_string()).collect();
+        let epoch = attestation::finalize_epoch(Epoch {
+            metabox: "1".into(),
+            record_type: "epoch".into(),
+            subject: new.to_string(),
+            issuer: RENAME_ISSUER.into(),
+            issuer_type: Some(IssuerType::Tool),
+            created_at: eval.at,
+            id: String::new(),
+            body: EpochBody {
+                refs: plan.folded.clone(),
+                score,
+                span: None,
+                summary: format!("Renamed from {old} ({} records)", folded.len()),
+            },
+        });
+        plan.carried.push(Record::Epoch(epoch));
+    }
+    plan.carried.extend(reissue(&scored, &kept));
+    plan.carried.extend(reissued);
+
+    Ok(plan)
+}
+
+/// A copy of a scored record with its subject set to `new` and its ID left
+/// as it was.
+fn with_subject(record: &Record, new: &str) -> Record {
+    let mut moved = record.clone();
+    match &mut moved {
+        Record::Attestation(a) => a.subject = new.to_string(),
+        Record::Epoch(e) => e.subject = new.to_string(),
+        _ => {}
+    }
+    moved
+}
+
+/// Re-issue the `kept` records among `scored`, in order, under their new
+/// subject. Whatever they superseded was folded, so the link is dropped.
+fn reissue(scored: &[Record], kept: &HashSet<&str>) -> Vec<Record> {
+    scored
+        .iter()
+        .filter(|r| kept.contains(r.id()))
+        .filter_map(|r| match r {
+            Record::Attestation(att) => {
+                let mut att = att.clone();
+                att.body.supersedes = None;
+                Some(Record::Attestation(Box::new(attestation::finalize(*att))))
+            }
+            Record::Epoch(epoch) => Some(Record::Epoch(attestation::finalize_epoch(epoch.clone()))),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Replace `old` with `new` in a `depends_on` list, dropping duplicates.
+fn rewire(depends_on: &mut Vec<String>, old: &str, new: &str) {
+    let mut seen = std::collections::HashSet::new();
+    let renamed: Vec<String> = depends_on
+        .iter()
+        .map(|d| if d == old { new.to_string() } else { d.clone() })
+        .filter(|d| seen.insert(d.clone()))
+        .collect();
+    *depends_on = renamed;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::fixtures;
+    use crate::attestation::{
+        Attestation, AttestationBody, DependencyBody, DependencyRecord, Kind,
+    };
+    use chrono::{DateTime, Utc};
+    use std::path::PathBuf;
+
+    fn now() -> DateTime<Utc> {
+        chrono::DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn make_record(subject: &str, kind: Kind, score: i32, supersedes: Option<&str>) -> Record {
+        Record::Attestation(Box::new(attestation::finalize(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: chrono::DateTime::parse_from_rfc3339("2026-02-24T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
+                kind,
+                r#ref: None,
+                score,
+                span: None,
+                suggested_fix: None,
+                summary: format!("scored {score}"),
+                supersedes: supersedes.map(Into::into),
+                symbol: None,
+                tags: vec![],
+            },
+        })))
+    }
+
+    fn make_dep(subject: &str, on: &[&str]) -> Record {
+        attestation::finalize_record(Record::Dependency(DependencyRecord {
+            metabox: "1".into(),
+            record_type: "dependency".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: now(),
+            id: String::new(),
+            body: DependencyBody {
+                depends_on: on.iter().map(|s| s.to_string()).collect(),
+            },
+        }))
+    }
+
+    fn make_revocation(subject: &str, revokes: &str) -> Record {
+        attestation::finalize_record(Record::Revocation(attestation::RevocationRecord {
+            metabox: "1".into(),
+            record_type: "revocation".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: now(),
+            id: String::new(),
+            body: attestation::RevocationBody {
+                revokes: vec![revokes.into()],
+                summary: "wrong".into(),
+            },
+        }))
+    }
+
+    fn qual(path: &str, records: Vec<Record>) -> QualFile {
+        QualFile {
+            path: PathBuf::from(path),
+            subject: String::new(),
+            records,
+        }
+    }
+
+    #[test]
+    fn test_rename_folds_history_into_traceable_epoch() {
+        let first = make_record("src/old.rs", Kind::Concern, -30, None);
+        let fix = make_record("src/old.rs", Kind::Pass, 20, Some(first.id()));
+        let other = make_record("src/keep.rs", Kind::Praise, 40, None);
+        let files = vec![qual(
+            "src/.qual",
+            vec![first.clone(), other.clone(), fix.clone()],
+        )];
+
+        let plan = rename(
+            &files,
+            "src/old.rs",
+            "src/new.rs",
+            &scoring::Evaluation::at(now()),
+        )
+        .unwrap();
+
+        assert_eq!(plan.files.len(), 1);
+        assert_eq!(plan.files[0].records.len(), 1);
+        assert_eq!(plan.files[0].records[0].id(), other.id());
+
+        let epoch = plan.epoch().unwrap();
+        assert_eq!(epoch.subject, "src/new.rs");
+        assert_eq!(epoch.issuer, RENAME_ISSUER);
+        assert_eq!(epoch.body.score, 20);
+        assert_eq!(
+            epoch.body.refs,
+            vec![first.id().to_string(), fix.id().to_string()]
+        );
+        assert_eq!(epoch.id, attestation::generate_epoch_id(epoch));
+    }
+
+    #[test]
+    fn test_rename_rewires_dependency_records() {
+        let files = vec![
+            qual("src/.qual", vec![make_dep("src/old.rs", &["lib/a"])]),
+            qual(
+                "app/.qual",
+                vec![make_dep("app/main.rs", &["src/old.rs", "src/new.rs"])],
+            ),
+            qual("lib/.qual", vec![make_dep("lib/a", &["lib/b"])]),
+        ];
+
+        let plan = rename(
+            &files,
+            "src/old.rs",
+            "src/new.rs",
+            &scoring::Evaluation::at(now()),
+        )
+        .unwrap();
+
+        assert_eq!(plan.rewired, 2);
+        assert_eq!(plan.files.len(), 2, "lib/.qual is untouched");
+        assert!(plan.files[0].records.is_empty());
+        assert!(plan.epoch().is_none());
+
+        let Record::Dependency(moved) = &plan.carried[0] else {
+            panic!("expected a dependency record");
+        };
+        assert_eq!(moved.subject, "src/new.rs");
+        assert_eq!(moved.body.depends_on, vec!["lib/a"]);
+
+        let Record::Dependency(app) = &plan.files[1].records[0] else {
+            panic!("expected a dependency record");
+        };
+        assert_eq!(app.body.depends_on, vec!["src/new.rs"]);
+        assert_eq!(app.id, attestation::generate_dependency_id(app));
+    }
+
+    #[test]
+    fn test_rename_drops_spent_revocations() {
+        let keep = make_record("src/old.rs", Kind::Pass, 20, None);
+        let wrong = make_record("src/old.rs", Kind::Blocker, -80, None);
+        let revocation = make_revocation("src/old.rs", wrong.id());
+        let dep = make_dep("src/old.rs", &["lib/a"]);
+        let dep_rm = make_revocation("src/old.rs", dep.id());
+        let files = vec![qual(
+            "src/.qual",
+            vec![keep, wrong, revocation, dep.clone(), dep_rm.clone()],
+        )];
+
+        let eval = scoring::Evaluation::at(now());
+        let plan = rename(&files, "src/old.rs", "src/new.rs", &eval).unwrap();
+
+        let left: Vec<&str> = plan.files[0].records.iter().map(|r| r.id()).collect();
+        assert_eq!(
+            left,
+            vec![dep.id(), dep_rm.id()],
+            "the dep rm stays in force"
+        );
+        assert_eq!(plan.epoch().unwrap().body.score, 20);
+    }
+
+    #[test]
+    fn test_rename_unknown_subject_is_empty() {
+        let files = vec![qual(
+            ".qual",
+            vec![make_record("a.rs", Kind::Pass, 20, None)],
+        )];
+        let plan = rename(&files, "b.rs", "c.rs", &scoring::Evaluation::at(now())).unwrap();
+        assert!(plan.is_empty());
+        assert!(plan.carried.is_empty());
+    }
+
+    #[test]
+    fn test_rename_to_same_name_fails() {
+        assert!(rename(&[], "a.rs", "a.rs", &scoring::Evaluation::at(now())).is_err());
+    }
+
+    #[test]
+    fn test_rename_reissues_attestations_that_have_not_expired() {
+        let concern = make_record("src/old.rs", Kind::Concern, -30, None);
+        let waiver = fixtures::record(
+            "src/old.rs",
+            AttestationBody {
+                kind: Kind::Waiver,
+                score: 40,
+                summary: "until the next release".into(),
+                expires_at: Some(now() + chrono::Duration::days(30)),
+                ..Default::default()
+            },
+        );
+        let files = vec![qual("src/.qual", vec![concern.clone(), waiver.clone()])];
+
+        let eval = scoring::Evaluation::at(now());
+        let plan = rename(&files, "src/old.rs", "src/new.rs", &eval).unwrap();
+
+        assert_eq!(plan.folded, vec![concern.id().to_string()]);
+        assert_eq!(plan.carried.len(), 2);
+        assert_eq!(plan.epoch().unwrap().body.score, -30);
+        let carried = plan.carried[1].as_attestation().unwrap();
+        assert_eq!(carried.subject, "src/new.rs");
+        assert_eq!(carried.body.expires_at, waiver.expires_at());
+        assert_eq!(
+            carried.created_at,
+            waiver.as_attestation().unwrap().created_at
+        );
+        assert_eq!(carried.id, attestation::generate_id(carried));
+
+        assert_eq!(scoring::raw_score(&plan.carried, &eval), 10);
+        let later = scoring::Evaluation::at(now() + chrono::Duration::days(31));
+        assert_eq!(scoring::raw_score(&plan.carried, &later), -30);
+    }
+
+    #[test]
+    fn test_rename_folds_nothing_when_the_epoch_would_be_weighted() {
+        let concern = make_record("src/old.rs", Kind::Concern, -30, None);
+        let pass = make_record("src/old.rs", Kind::Pass, 10, None);
+        let files = vec![qual("src/.qual", vec![concern.clone(), pass.clone()])];
+
+        let trust = crate::trust::Trust::new(crate::trust::TrustModel {
+            subjects: vec![crate::trust::SubjectTrust {
+                pattern: "src/**".into(),
+                weight: None,
+                allow: vec!["mailto:test@test.com".into()],
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        let eval = scoring::Evaluation::at(now()).with_trust(trust);
+        let plan = rename(&files, "src/old.rs", "src/new.rs", &eval).unwrap();
+
+        assert!(plan.epoch().is_none());
+        assert_eq!(plan.carried.len(), 2);
+        assert!(plan.carried.iter().all(|r| r.subject() == "src/new.rs"));
+        assert_eq!(
+            scoring::raw_score(&plan.carried, &eval),
+            scoring::raw_score(&files[0].records, &eval)
+        );
+    }
+
+    #[test]
+    fn test_rename_reissues_decaying_attestations() {
+        let mut praise = fixtures::attestation(
+            "src/old.rs",
+            AttestationBody {
+                kind: Kind::Praise,
+                score: 80,
+                summary: "fast".into(),
+                ..Default::default()
+            },
+        );
+        praise.created_at = chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let praise = Record::Attestation(Box::new(attestation::finalize(praise)));
+        let pass = make_record("src/old.rs", Kind::Pass, 10, None);
+        let files = vec![qual("src/.qual", vec![praise.clone(), pass.clone()])];
+
+        let mut decay = scoring::DecayModel::default();
+        decay.0.insert(
+            "praise".into(),
+            scoring::DecayRule {
+                half_life_days: Some(180),
+                ..Default::default()
+            },
+        );
+        let eval = scoring::Evaluation::at(now()).with_decay(decay);
+        let plan = rename(&files, "src/old.rs", "src/new.rs", &eval).unwrap();
+
+        assert_eq!(plan.folded, vec![pass.id().to_string()]);
+        assert_eq!(plan.epoch().unwrap().body.score, 10);
+        let carried = plan.carried[1].as_attestation().unwrap();
+        assert_eq!(
+            carried.created_at,
+            praise.as_attestation().unwrap().created_at
+        );
+        assert_eq!(
+            scoring::raw_score(&plan.carried, &eval),
+            scoring::raw_score(&files[0].records, &eval)
+        );
+    }
+}
@@ -0,0 +1,851 @@
+//! SARIF v2.1.0 interchange (SPEC §5.2, §5.3).
+//!
+//! Only the subset of SARIF that maps onto qualifier records is modeled;
+//! unrecognized SARIF properties are ignored on input.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::attestation::{
+    self, Attestation, AttestationBody, IssuerType, Kind, Position, Record, Span,
+};
+use crate::scoring;
+
+// ─── SARIF object model ─────────────────────────────────────────────────────
+
+/// A SARIF log file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    pub version: String,
+    #[serde(default)]
+    pub runs: Vec<Run>,
+}
+
+/// One invocation of one analysis tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Run {
+    pub tool: Tool,
+    /// Files the run analyzed, including those with no results.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<Artifact>,
+    #[serde(default)]
+    pub results: Vec<SarifResult>,
+}
+
+/// A file a run analyzed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Artifact {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<ArtifactLocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tool {
+    pub driver: Driver,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Driver {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub information_uri: Option<String>,
+}
+
+/// A single finding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>,
+    /// `error`, `warning`, `note`, or `none`. SARIF defaults to `warning`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    pub message: Message,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<Location>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixes: Vec<Fix>,
+}
+
+/// A proposed fix. Qualifier fixes are prose, so only the description is
+/// emitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fix {
+    pub description: Message,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Message {
+    #[serde(default)]
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub physical_location: Option<PhysicalLocation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<u32>,
+}
+
+// ─── Level-to-score mapping ─────────────────────────────────────────────────
+
+/// Scores assigned to each SARIF level on import.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LevelScores {
+    pub error: i32,
+    pub warning: i32,
+    pub note: i32,
+    pub none: i32,
+}
+
+impl Default for LevelScores {
+    fn default() -> Self {
+        LevelScores {
+            error: -20,
+            warning: -10,
+            note: -5,
+            none: 0,
+        }
+    }
+}
+
+impl LevelScores {
+    /// Score for a SARIF level. An absent level means `warning`.
+    pub fn score(&self, level: Option<&str>) -> crate::Result<i32> {
+        match level.unwrap_or("warning") {
+            "error" => Ok(self.error),
+            "warning" => Ok(self.warning),
+            "note" => Ok(self.note),
+            "none" => Ok(self.none),
+            other => Err(crate::Error::Validation(format!(
+                "unknown SARIF level: '{other}'"
+            ))),
+        }
+    }
+
+    /// The SARIF level for a score: the most severe level whose configured
+    /// score is at or above `score`, `note` for other negative scores, and
+    /// `none` otherwise. Scores produced by [`LevelScores::score`] map back
+    /// to the level they came from.
+    pub fn level(&self, score: i32) -> &'static str {
+        if score <= self.error {
+            "error"
+        } else if score <= self.warning {
+            "warning"
+        } else if score <= self.note || score < 0 {
+            "note"
+        } else {
+            "none"
+        }
+    }
+}
+
+// ─── Import ─────────────────────────────────────────────────────────────────
+
+/// Parse a SARIF log, rejecting versions other than 2.1.0.
+pub fn parse_str(content: &str) -> crate::Result<SarifLog> {
+    let log: SarifLog = serde_json::from_str(content)?;
+    if log.version != "2.1.0" {
+        return Err(crate::Error::Validation(format!(
+            "unsupported SARIF version: '{}' (expected 2.1.0)",
+            log.version
+        )));
+    }
+    Ok(log)
+}
+
+/// The issuer URI for a SARIF tool driver. Names that are already URIs are
+/// kept; anything else becomes `urn:tool:<name>`.
+pub fn tool_issuer(driver_name: &str) -> String {
+    if driver_name.contains(':') {
+        driver_name.to_string()
+    } else {
+        format!("urn:tool:{}", driver_name.replace(char::is_whitespace, "-"))
+    }
+}
+
+/// Convert an artifact URI to a subject name relative to `root`.
+fn subject_from_uri(uri: &str, root: &Path) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let path = Path::new(path);
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .to_string_lossy()
+        .trim_start_matches("./")
+        .to_string()
+}
+
+fn region_span(region: &Region) -> Option<Span> {
+    let start = Position {
+        line: region.start_line?,
+        col: region.start_column,
+    };
+    let end = Position {
+        line: region.end_line.unwrap_or(start.line),
+        col: region.end_column,
+    };
+    Some(Span {
+        start,
+        end: Some(end),
+    })
+}
+
+/// Convert SARIF results into attestations.
+///
+/// Results without a physical location are skipped; the second element of
+/// the returned tuple counts them. `root` is stripped from absolute artifact
+/// URIs. IDs are left unset until [`supersede_previous`] links the new
+/// attestations to earlier ones.
+pub fn to_attestations(
+    log: &SarifLog,
+    scores: &LevelScores,
+    root: &Path,
+    created_at: DateTime<Utc>,
+) -> crate::Result<(Vec<Attestation>, usize)> {
+    let mut out = Vec::new();
+    let mut skipped = 0;
+    for run in &log.runs {
+        let issuer = tool_issuer(&run.tool.driver.name);
+        for result in &run.results {
+            let Some(location) = result
+                .locations
+                .first()
+                .and_then(|l| l.physical_location.as_ref())
+            else {
+                skipped += 1;
+                continue;
+            };
+
+            let kind: Kind = match result.rule_id.as_deref() {
+                Some(rule) => rule.parse().unwrap_or_else(|e| match e {}),
+                None => Kind::Concern,
+            };
+            let summary = if result.message.text.is_empty() {
+                kind.to_string()
+            } else {
+                result.message.text.clone()
+            };
+
+            out.push(Attestation {
+                metabox: "1".into(),
+                record_type: "attestation".into(),
+                subject: subject_from_uri(&location.artifact_location.uri, root),
+                issuer: issuer.clone(),
+                issuer_type: Some(IssuerType::Tool),
+                created_at,
+                id: String::new(),
+                body: AttestationBody {
+                    detail: None,
+                    digest: None,
+                    expires_at: None,
+                    fingerprint: None,
+                    kind,
+                    r#ref: None,
+                    score: scores.score(result.level.as_deref())?,
+                    span: location.region.as_ref().and_then(region_span),
+                    suggested_fix: None,
+                    summary,
+                    supersedes: None,
+                    symbol: None,
+                    tags: vec![],
+                },
+            });
+        }
+    }
+    Ok((out, skipped))
+}
+
+/// Link each new attestation to an active earlier attestation from the same
+/// tool and rule on the same subject, then finalize the new attestations.
+///
+/// Within each (subject, issuer, kind) group, an earlier finding with the
+/// same span is preferred; remaining findings are paired in file order.
+/// Earlier findings with no counterpart in the new batch are left to
+/// [`resolve_unmatched`].
+pub fn supersede_previous(new: Vec<Attestation>, existing: &[Record]) -> Vec<Attestation> {
+    let mut candidates: Vec<&Attestation> = scoring::filter_superseded(existing)
+        .into_iter()
+        .filter_map(|r| r.as_attestation())
+        .collect();
+
+    let same_group = |a: &Attestation, b: &Attestation| {
+        a.subject == b.subject && a.issuer == b.issuer && a.body.kind == b.body.kind
+    };
+
+    // First pass: same span. Second pass: any remaining in the group.
+    let mut targets: Vec<Option<String>> = vec![None; new.len()];
+    for exact in [true, false] {
+        for (i, att) in new.iter().enumerate() {
+            if targets[i].is_some() {
+                continue;
+            }
+            if let Some(pos) = candidates
+                .iter()
+                .position(|old| same_group(att, old) && (!exact || old.body.span == att.body.span))
+            {
+                targets[i] = Some(candidates.remove(pos).id.clone());
+            }
+        }
+    }
+
+    new.into_iter()
+        .zip(targets)
+        .map(|(mut att, target)| {
+            att.body.supersedes = target;
+            attestation::finalize(att)
+        })
+        .collect()
+}
+
+/// The (issuer, subject) pairs each run in `log` analyzed: the files in
+/// `run.artifacts` and those its results point at.
+fn covered(log: &SarifLog, root: &Path) -> HashSet<(String, String)> {
+    let mut covered = HashSet::new();
+    for run in &log.runs {
+        let issuer = tool_issuer(&run.tool.driver.name);
+        let listed = run.artifacts.iter().filter_map(|a| a.location.as_ref());
+        let reported = run.results.iter().filter_map(|r| {
+            r.locations
+                .first()
+                .and_then(|l| l.physical_location.as_ref())
+                .map(|p| &p.artifact_location)
+        });
+        for location in listed.chain(reported) {
+            covered.insert((issuer.clone(), subject_from_uri(&location.uri, root)));
+        }
+    }
+    covered
+}
+
+/// Neutral attestations resolving the active findings of the tools in `log`
+/// that `imported` does not supersede, on the files those tools analyzed.
+///
+/// A SARIF run lists everything its tool currently reports on the files it
+/// scanned, so an earlier finding from the same tool on such a file with no
+/// counterpart in the import has been fixed. Files the run did not cover,
+/// listed neither in `run.artifacts` nor by a result, are left alone, so a
+/// partial scan resolves nothing elsewhere. Each resolved finding is
+/// superseded by an attestation of the same kind and span with score 0, so
+/// a finding that reappears later supersedes the resolution in turn.
+/// Findings already scored 0 are left alone, which also keeps re-imports
+/// from resolving the same finding twice. `root` is stripped from absolute
+/// artifact URIs, as in [`to_attestations`].
+pub fn resolve_unmatched(
+    log: &SarifLog,
+    imported: &[Attestation],
+    existing: &[Record],
+    root: &Path,
+    created_at: DateTime<Utc>,
+) -> Vec<Attestation> {
+    let covered = covered(log, root);
+    let matched: Vec<&str> = imported
+        .iter()
+        .filter_map(|a| a.body.supersedes.as_deref())
+        .collect();
+
+    scoring::filter_superseded(existing)
+        .into_iter()
+        .filter_map(|r| r.as_attestation())
+        .filter(|old| {
+            covered.contains(&(old.issuer.clone(), old.subject.clone()))
+                && old.body.score != 0
+                && !matched.contains(&old.id.as_str())
+        })
+        .map(|old| {
+            attestation::finalize(Attestation {
+                metabox: "1".into(),
+                record_type: "attestation".into(),
+                subject: old.subject.clone(),
+                issuer: old.issuer.clone(),
+                issuer_type: Some(IssuerType::Tool),
+                created_at,
+                id: String::new(),
+                body: AttestationBody {
+                    detail: None,
+                    digest: None,
+                    expires_at: None,
+                    fingerprint: None,
+                    kind: old.body.kind.clone(),
+                    r#ref: None,
+                    score: 0,
+                    span: old.body.span.clone(),
+                    suggested_fix: None,
+                    summary: format!("No longer reported: {}", old.body.summary),
+                    supersedes: Some(old.id.clone()),
+                    symbol: None,
+                    tags: vec![],
+                },
+            })
+        })
+        .collect()
+}
+
+// ─── Export ─────────────────────────────────────────────────────────────────
+
+/// Attestation kinds exported as SARIF results.
+const EXPORTED_KINDS: [Kind; 4] = [Kind::Concern, Kind::Blocker, Kind::Fail, Kind::Suggestion];
+
+/// The SARIF driver name for an issuer; inverse of [`tool_issuer`].
+fn driver_name(issuer: &str) -> String {
+    issuer
+        .strip_prefix("urn:tool:")
+        .unwrap_or(issuer)
+        .to_string()
+}
+
+fn span_region(span: &Span) -> Region {
+    let end = span.end.as_ref().unwrap_or(&span.start);
+    Region {
+        start_line: Some(span.start.line),
+        start_column: span.start.col,
+        end_line: Some(end.line),
+        end_column: end.col,
+    }
+}
+
+/// Build a SARIF log from the active `concern`, `blocker`, `fail`, and
+/// `suggestion` attestations in `records`, with one run per issuer.
+///
+/// Runs are ordered by issuer and results keep record order, so the output
+/// is deterministic.
+pub fn from_records(records: &[Record], levels: &LevelScores) -> SarifLog {
+    let mut runs: std::collections::BTreeMap<&str, Vec<SarifResult>> = Default::default();
+    for record in scoring::filter_superseded(records) {
+        let Some(att) = record.as_attestation() else {
+            continue;
+        };
+        if !EXPORTED_KINDS.contains(&att.body.kind) {
+            continue;
+        }
+        runs.entry(&att.issuer).or_default().push(SarifResult {
+            rule_id: Some(att.body.kind.to_string()),
+            level: Some(levels.level(att.body.score).to_string()),
+            message: Message {
+                text: att.body.summary.clone(),
+            },
+            locations: vec![Location {
+                physical_location: Some(PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: att.subject.clone(),
+                    },
+                    region: att.body.span.as_ref().map(span_region),
+                }),
+            }],
+            fixes: att
+                .body
+                .suggested_fix
+                .iter()
+                .map(|text| Fix {
+                    description: Message { text: text.clone() },
+                })
+                .collect(),
+        });
+    }
+
+    SarifLog {
+        schema: Some(SCHEMA.into()),
+        version: "2.1.0".into(),
+        runs: runs
+            .into_iter()
+            .map(|(issuer, results)| Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: driver_name(issuer),
+                        information_uri: None,
+                    },
+                },
+                artifacts: Vec::new(),
+                results,
+            })
+            .collect(),
+    }
+}
+
+/// JSON schema URI for SARIF v2.1.0.
+pub const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIPPY: &str = r#"{
+      "version": "2.1.0",
+      "runs": [{
+        "tool": {"driver": {"name": "clippy"}},
+        "results": [
+          {
+            "ruleId": "clippy::unwrap_used",
+            "level": "warning",
+            "message": {"text": "used `unwrap()` on a `Result` value"},
+            "locations": [{"physicalLocation": {
+              "artifactLocation": {"uri": "src/parser.rs"},
+              "region": {"startLine": 42, "startColumn": 5, "endColumn": 20}
+            }}]
+          },
+          {
+            "ruleId": "clippy::panic",
+            "level": "error",
+            "message": {"text": "`panic` should not be present"},
+            "locations": [{"physicalLocation": {
+              "artifactLocation": {"uri": "file:///repo/src/lib.rs"}
+            }}]
+          },
+          {
+            "ruleId": "clippy::todo",
+            "message": {"text": "no location"}
+          }
+        ]
+      }]
+    }"#;
+
+    fn ts() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn import(content: &str, existing: &[Record]) -> Vec<Attestation> {
+        let log = parse_str(content).unwrap();
+        let (atts, _) =
+            to_attestations(&log, &LevelScores::default(), Path::new("/repo"), ts()).unwrap();
+        supersede_previous(atts, existing)
+    }
+
+    #[test]
+    fn test_sarif_mapping() {
+        let log = parse_str(CLIPPY).unwrap();
+        let (atts, skipped) =
+            to_attestations(&log, &LevelScores::default(), Path::new("/repo"), ts()).unwrap();
+        assert_eq!(skipped, 1);
+        assert_eq!(atts.len(), 2);
+
+        let unwrap = &atts[0];
+        assert_eq!(unwrap.subject, "src/parser.rs");
+        assert_eq!(unwrap.issuer, "urn:tool:clippy");
+        assert_eq!(unwrap.issuer_type, Some(IssuerType::Tool));
+        assert_eq!(unwrap.body.kind, Kind::Custom("clippy::unwrap_used".into()));
+        assert_eq!(unwrap.body.score, -10);
+        assert_eq!(unwrap.body.summary, "used `unwrap()` on a `Result` value");
+        let span = unwrap.body.span.as_ref().unwrap();
+        assert_eq!(
+            span.start,
+            Position {
+                line: 42,
+                col: Some(5)
+            }
+        );
+        assert_eq!(
+            span.end,
+            Some(Position {
+                line: 42,
+                col: Some(20)
+            })
+        );
+
+        let panic = &atts[1];
+        assert_eq!(panic.subject, "src/lib.rs");
+        assert_eq!(panic.body.score, -20);
+        assert!(panic.body.span.is_none());
+    }
+
+    #[test]
+    fn test_level_overrides() {
+        let scores = LevelScores {
+            warning: -3,
+            ..Default::default()
+        };
+        assert_eq!(scores.score(None).unwrap(), -3);
+        assert_eq!(scores.score(Some("error")).unwrap(), -20);
+        assert!(scores.score(Some("fatal")).is_err());
+    }
+
+    #[test]
+    fn test_rejects_other_versions() {
+        assert!(parse_str(r#"{"version": "2.0.0", "runs": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_reimport_supersedes_same_tool_and_rule() {
+        let first = import(CLIPPY, &[]);
+        assert!(first.iter().all(|a| a.body.supersedes.is_none()));
+        let existing: Vec<Record> = first
+            .iter()
+            .cloned()
+            .map(|a| Record::Attestation(Box::new(a)))
+            .collect();
+
+        let second = import(CLIPPY, &existing);
+        assert_eq!(
+            second[0].body.supersedes.as_deref(),
+            Some(first[0].id.as_str())
+        );
+        assert_eq!(
+            second[1].body.supersedes.as_deref(),
+            Some(first[1].id.as_str())
+        );
+
+        let mut all = existing;
+        all.extend(second.into_iter().map(|a| Record::Attestation(Box::new(a))));
+        assert_eq!(scoring::filter_superseded(&all).len(), 2);
+    }
+
+    fn unwrap_findings(lines: &[u32]) -> String {
+        let results: Vec<serde_json::Value> = lines
+            .iter()
+            .map(|line| {
+                serde_json::json!({
+                    "ruleId": "clippy::unwrap_used",
+                    "message": {"text": "used `unwrap()`"},
+                    "locations": [{"physicalLocation": {
+                        "artifactLocation": {"uri": "src/parser.rs"},
+                        "region": {"startLine": line}
+                    }}]
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "version": "2.1.0",
+            "runs": [{"tool": {"driver": {"name": "clippy"}}, "results": results}]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_reimport_prefers_matching_span() {
+        let first = import(&unwrap_findings(&[10, 20]), &[]);
+        let existing: Vec<Record> = first
+            .iter()
+            .cloned()
+            .map(|a| Record::Attestation(Box::new(a)))
+            .collect();
+
+        // Only the line-20 finding remains; it replaces its own predecessor
+        // and the line-10 finding is resolved.
+        let second = import(&unwrap_findings(&[20]), &existing);
+        assert_eq!(
+            second[0].body.supersedes.as_deref(),
+            Some(first[1].id.as_str())
+        );
+    }
+
+    #[test]
+    fn test_reimport_resolves_findings_no_longer_reported() {
+        let log = parse_str(&unwrap_findings(&[10, 20])).unwrap();
+        let first = import(&unwrap_findings(&[10, 20]), &[]);
+        let mut all: Vec<Record> = first
+            .iter()
+            .cloned()
+            .map(|a| Record::Attestation(Box::new(a)))
+            .collect();
+        assert!(resolve_unmatched(&log, &first, &[], Path::new("/repo"), ts()).is_empty());
+
+        let log = parse_str(&unwrap_findings(&[20])).unwrap();
+        let second = import(&unwrap_findings(&[20]), &all);
+        let resolved = resolve_unmatched(&log, &second, &all, Path::new("/repo"), ts());
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].body.score, 0);
+        assert_eq!(resolved[0].body.kind, first[0].body.kind);
+        assert_eq!(
+            resolved[0].body.supersedes.as_deref(),
+            Some(first[0].id.as_str())
+        );
+        assert!(attestation::validate(&resolved[0]).is_empty());
+        all.extend(
+            second
+                .iter()
+                .chain(&resolved)
+                .cloned()
+                .map(|a| Record::Attestation(Box::new(a))),
+        );
+        assert_eq!(
+            scoring::raw_score(&all, &scoring::Evaluation::at(ts())),
+            -10
+        );
+
+        // Importing the same run again resolves nothing new.
+        let third = import(&unwrap_findings(&[20]), &all);
+        assert!(resolve_unmatched(&log, &third, &all, Path::new("/repo"), ts()).is_empty());
+
+        // Other tools' findings are left alone.
+        let other = parse_str(
+            r#"{"version": "2.1.0", "runs": [{"tool": {"driver": {"name": "eslint"}}}]}"#,
+        )
+        .unwrap();
+        assert!(resolve_unmatched(&other, &[], &all, Path::new("/repo"), ts()).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_unmatched_only_on_covered_files() {
+        let findings = |subjects: &[&str]| {
+            let results: Vec<serde_json::Value> = subjects
+                .iter()
+                .map(|subject| {
+                    serde_json::json!({
+                        "ruleId": "clippy::unwrap_used",
+                        "message": {"text": "used `unwrap()`"},
+                        "locations": [{"physicalLocation": {
+                            "artifactLocation": {"uri": subject},
+                            "region": {"startLine": 10}
+                        }}]
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "version": "2.1.0",
+                "runs": [{
+                    "tool": {"driver": {"name": "clippy"}},
+                    "artifacts": [{"location": {"uri": "src/c.rs"}}],
+                    "results": results
+                }]
+            })
+            .to_string()
+        };
+        let all: Vec<Record> = import(&findings(&["src/a.rs", "src/b.rs", "src/c.rs"]), &[])
+            .into_iter()
+            .map(|a| Record::Attestation(Box::new(a)))
+            .collect();
+
+        // A scan of a.rs (plus c.rs, listed as analyzed with no results)
+        // says nothing about b.rs.
+        let content = findings(&["src/a.rs"]);
+        let log = parse_str(&content).unwrap();
+        let second = import(&content, &all);
+        let resolved = resolve_unmatched(&log, &second, &all, Path::new("/repo"), ts());
+        let subjects: Vec<&str> = resolved.iter().map(|a| a.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["src/c.rs"]);
+    }
+
+    fn make_att(issuer: &str, kind: Kind, score: i32, supersedes: Option<String>) -> Record {
+        Record::Attestation(Box::new(attestation::finalize(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: "src/parser.rs".into(),
+            issuer: issuer.into(),
+            issuer_type: None,
+            created_at: ts(),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
+                kind,
+                r#ref: None,
+                score,
+                span: Some(Span {
+                    start: Position {
+                        line: 42,
+                        col: None,
+                    },
+                    end: Some(Position {
+                        line: 58,
+                        col: None,
+                    }),
+                }),
+                suggested_fix: Some("Propagate the error".into()),
+                summary: format!("score {score}"),
+                supersedes,
+                symbol: None,
+                tags: vec![],
+            },
+        })))
+    }
+
+    #[test]
+    fn test_score_to_level() {
+        let levels = LevelScores::default();
+        assert_eq!(levels.level(-50), "error");
+        assert_eq!(levels.level(-20), "error");
+        assert_eq!(levels.level(-10), "warning");
+        assert_eq!(levels.level(-5), "note");
+        assert_eq!(levels.level(-1), "note");
+        assert_eq!(levels.level(0), "none");
+        for level in ["error", "warning", "note", "none"] {
+            assert_eq!(levels.level(levels.score(Some(level)).unwrap()), level);
+        }
+    }
+
+    #[test]
+    fn test_export_one_run_per_issuer() {
+        let old = make_att("mailto:bob@example.com", Kind::Concern, -10, None);
+        let records = vec![
+            make_att("mailto:alice@example.com", Kind::Blocker, -50, None),
+            make_att("mailto:alice@example.com", Kind::Praise, 40, None),
+            old.clone(),
+            make_att(
+                "mailto:bob@example.com",
+                Kind::Concern,
+                -30,
+                Some(old.id().to_string()),
+            ),
+            make_att("urn:tool:clippy", Kind::Suggestion, -5, None),
+        ];
+        let log = from_records(&records, &LevelScores::default());
+
+        let names: Vec<&str> = log
+            .runs
+            .iter()
+            .map(|r| r.tool.driver.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "mailto:alice@example.com",
+                "mailto:bob@example.com",
+                "clippy"
+            ]
+        );
+
+        // Praise is not exported.
+        let alice = &log.runs[0].results;
+        assert_eq!(alice.len(), 1);
+        assert_eq!(alice[0].rule_id.as_deref(), Some("blocker"));
+        assert_eq!(alice[0].level.as_deref(), Some("error"));
+        assert_eq!(alice[0].fixes[0].description.text, "Propagate the error");
+        let region = alice[0].locations[0]
+            .physical_location
+            .as_ref()
+            .unwrap()
+            .region
+            .as_ref()
+            .unwrap();
+        assert_eq!((region.start_line, region.end_line), (Some(42), Some(58)));
+
+        // Only the superseding concern is exported.
+        let bob = &log.runs[1].results;
+        assert_eq!(bob.len(), 1);
+        assert_eq!(bob[0].message.text, "score -30");
+    }
+}
@@ -1,8 +1,157 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use crate::attestation::{Record, clamp_score};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::attestation::{Record, RevocationRecord, clamp_score};
 use crate::graph::DependencyGraph;
 use crate::qual_file::QualFile;
+use crate::trust::Trust;
+
+/// How an attestation kind loses weight with age. Both parts are optional
+/// and may be combined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecayRule {
+    /// The contribution halves every this many days. `0` disables halving.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub half_life_days: Option<u32>,
+    /// The contribution drops to zero once the record is this many days old.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_after_days: Option<u32>,
+}
+
+/// Decay rules keyed by attestation kind (e.g. `"praise"`). Kinds without a
+/// rule, and epochs, do not decay.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DecayModel(pub BTreeMap<String, DecayRule>);
+
+/// Records whose pinned content has changed (see [`crate::pin`]) and the
+/// share of their score they keep.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Staleness {
+    /// IDs of the stale records.
+    pub ids: HashSet<String>,
+    /// Percentage of its score a stale record still contributes; `0`
+    /// excludes it.
+    pub weight: u8,
+}
+
+/// The point in time and rules under which scores are evaluated.
+///
+/// Scoring is a pure function of the records and the `Evaluation`, so
+/// passing the same timestamp always reproduces the same scores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    /// The evaluation timestamp; record ages are measured up to this instant.
+    pub at: DateTime<Utc>,
+    /// Per-kind decay rules. Empty means no decay.
+    pub decay: DecayModel,
+    /// Stale records and their weight. Empty means staleness is ignored.
+    pub stale: Staleness,
+    /// Issuer trust weights. Empty means every issuer counts in full.
+    pub trust: Trust,
+    /// Score only attestations tagged with this dimension. `None` is the
+    /// overall score.
+    pub dimension: Option<String>,
+}
+
+impl Evaluation {
+    /// Evaluate at `at` with no decay.
+    pub fn at(at: DateTime<Utc>) -> Self {
+        Evaluation {
+            at,
+            decay: DecayModel::default(),
+            stale: Staleness::default(),
+            trust: Trust::default(),
+            dimension: None,
+        }
+    }
+
+    /// Use `decay` as the decay model.
+    pub fn with_decay(mut self, decay: DecayModel) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Use `stale` to down-weight records whose pinned content changed.
+    pub fn with_staleness(mut self, stale: Staleness) -> Self {
+        self.stale = stale;
+        self
+    }
+
+    /// Use `trust` to weight attestations by issuer and subject.
+    pub fn with_trust(mut self, trust: Trust) -> Self {
+        self.trust = trust;
+        self
+    }
+
+    /// Score only attestations tagged `dimension`.
+    pub fn with_dimension(mut self, dimension: impl Into<String>) -> Self {
+        self.dimension = Some(dimension.into());
+        self
+    }
+
+    /// The decay rule that applies to `record`, if any.
+    pub fn decay_rule(&self, record: &Record) -> Option<&DecayRule> {
+        self.decay.0.get(&record.kind()?.to_string())
+    }
+}
+
+/// Apply `rule` to `score` for a record `age_days` old, using integer
+/// arithmetic only.
+///
+/// After `n` whole half-lives the score is `score / 2^n` (truncating toward
+/// zero); within a half-life it falls linearly toward the next halving.
+pub fn decay_score(score: i32, age_days: i64, rule: &DecayRule) -> i32 {
+    let age = age_days.max(0);
+    if let Some(expiry) = rule.expires_after_days
+        && age >= i64::from(expiry)
+    {
+        return 0;
+    }
+    let Some(half_life) = rule.half_life_days.filter(|&h| h > 0) else {
+        return score;
+    };
+    let half_life = i64::from(half_life);
+    let halvings = age / half_life;
+    if halvings >= 31 {
+        return 0;
+    }
+    let halved = i64::from(score) / (1i64 << halvings);
+    let into = age % half_life;
+    (halved - halved * into / (2 * half_life)) as i32
+}
+
+/// The score a record contributes at evaluation time, or `None` for
+/// unscored record types. Expired records, and records outside
+/// `eval.dimension` when one is set, contribute `0`; stale records
+/// keep `eval.stale.weight` percent of their (decayed) score, and the
+/// result is then weighted by `eval.trust`.
+pub fn contribution(record: &Record, eval: &Evaluation) -> Option<i32> {
+    let score = record.score()?;
+    if record.is_expired(eval.at) {
+        return Some(0);
+    }
+    if let Some(dimension) = &eval.dimension
+        && !record.tags().contains(dimension)
+    {
+        return Some(0);
+    }
+    let score = match eval.decay_rule(record) {
+        Some(rule) => {
+            let age_days = (eval.at - record.created_at()?).num_days();
+            decay_score(score, age_days, rule)
+        }
+        None => score,
+    };
+    let score = if eval.stale.ids.contains(record.id()) {
+        score * i32::from(eval.stale.weight) / 100
+    } else {
+        score
+    };
+    Some(eval.trust.weigh(record).apply(score))
+}
 
 /// Score report for a single artifact.
 #[derive(Debug, Clone)]
@@ -17,32 +166,81 @@ pub struct ScoreReport {
 
 /// Compute the raw score for a set of records (single artifact).
 ///
-/// Filters out superseded records, sums scores of scored types
-/// (attestations and epochs), and clamps to [-100, 100].
-pub fn raw_score(records: &[Record]) -> i32 {
+/// Filters out superseded and revoked records, sums the contributions of scored types
+/// (attestations and epochs) at `eval`, and clamps to [-100, 100].
+pub fn raw_score(records: &[Record], eval: &Evaluation) -> i32 {
     let active = filter_superseded(records);
     let sum = active
         .iter()
-        .filter_map(|r| r.score())
+        .filter_map(|r| contribution(r, eval))
         .fold(0i32, |acc, s| acc.saturating_add(s));
     clamp_score(sum)
 }
 
-/// Filter out superseded records, returning only the active ones.
+/// IDs of the records in `records` that are inactive: revoked by a
+/// `revocation` record, or superseded by a record that is not itself
+/// revoked.
+///
+/// Revoking a replacement therefore reactivates the record it superseded.
+pub fn inactive_ids<'a, I>(records: I) -> HashSet<&'a str>
+where
+    I: IntoIterator<Item = &'a Record>,
+    I::IntoIter: Clone,
+{
+    let records = records.into_iter();
+    let revoked: HashSet<&str> = records
+        .clone()
+        .flat_map(|r| r.revokes())
+        .map(String::as_str)
+        .collect();
+    let superseded: Vec<&str> = records
+        .filter(|r| !revoked.contains(r.id()))
+        .filter_map(|r| r.supersedes())
+        .collect();
+    let mut inactive = revoked;
+    inactive.extend(superseded);
+    inactive
+}
+
+/// Map each revoked record ID to the revocation that withdrew it.
+pub fn revoked_by(records: &[Record]) -> HashMap<&str, &RevocationRecord> {
+    let mut revoked = HashMap::new();
+    for revocation in records.iter().filter_map(|r| r.as_revocation()) {
+        for id in &revocation.body.revokes {
+            revoked.entry(id.as_str()).or_insert(revocation);
+        }
+    }
+    revoked
+}
+
+/// Filter out superseded and revoked records, returning only the active ones.
 ///
 /// A record is superseded if any other record's `supersedes` field
-/// points to its ID. Only attestations can supersede or be superseded.
-/// Non-attestation records always pass through.
+/// points to its ID, and revoked if a `revocation` record lists its ID (see
+/// [`inactive_ids`]). Records that are neither always pass through,
+/// including the revocations themselves.
 pub fn filter_superseded(records: &[Record]) -> Vec<&Record> {
-    // Collect all IDs that are superseded by something
-    let superseded_ids: HashSet<&str> = records.iter().filter_map(|r| r.supersedes()).collect();
+    let inactive = inactive_ids(records);
 
     records
         .iter()
-        .filter(|r| !superseded_ids.contains(r.id()))
+        .filter(|r| !inactive.contains(r.id()))
         .collect()
 }
 
+/// Active records whose `expires_at` is at or before `before`, soonest first.
+///
+/// Pass the evaluation time to find expired records, or a later instant to
+/// find the ones due for re-review.
+pub fn expiring(records: &[Record], before: DateTime<Utc>) -> Vec<&Record> {
+    let mut expiring: Vec<&Record> = filter_superseded(records)
+        .into_iter()
+        .filter(|r| r.expires_at().is_some_and(|e| e <= before))
+        .collect();
+    expiring.sort_by_key(|r| r.expires_at());
+    expiring
+}
+
 /// Compute effective scores for all artifacts in the graph.
 ///
 /// Uses topological ordering to propagate scores from leaves to roots.
@@ -54,11 +252,16 @@ pub fn filter_superseded(records: &[Record]) -> Vec<&Record> {
 pub fn effective_scores(
     graph: &DependencyGraph,
     qual_files: &[QualFile],
+    eval: &Evaluation,
 ) -> HashMap<String, ScoreReport> {
     // Build a map of subject -> records
     let mut subject_records: HashMap<&str, Vec<&Record>> = HashMap::new();
     for qf in qual_files {
         for record in &qf.records {
+            // Policies describe the project, not an artifact
+            if matches!(record, Record::Policy(_)) {
+                continue;
+            }
             subject_records
                 .entry(record.subject())
                 .or_default()
@@ -69,7 +272,7 @@ pub fn effective_scores(
     // Compute raw scores for all known subjects
     let mut raw_scores: HashMap<String, i32> = HashMap::new();
     for (subject, records) in &subject_records {
-        raw_scores.insert(subject.to_string(), raw_score_from_refs(records));
+        raw_scores.insert(subject.to_string(), raw_score_from_refs(records, eval));
     }
 
     // Include graph artifacts with no records (raw score = 0)
@@ -180,19 +383,109 @@ pub fn effective_scores(
     reports
 }
 
+/// Effective scores in `eval.dimension`, for the subjects the dimension
+/// covers: those with an active attestation tagged with it, and those whose
+/// score a dependency in the dimension pulls down. Without a dimension this
+/// is [`effective_scores`].
+pub fn dimension_scores(
+    graph: &DependencyGraph,
+    qual_files: &[QualFile],
+    eval: &Evaluation,
+) -> HashMap<String, ScoreReport> {
+    let mut reports = effective_scores(graph, qual_files, eval);
+    let Some(dimension) = &eval.dimension else {
+        return reports;
+    };
+    let records: Vec<&Record> = qual_files.iter().flat_map(|qf| &qf.records).collect();
+    let inactive = inactive_ids(records.iter().copied());
+    let tagged: HashSet<&str> = records
+        .iter()
+        .filter(|r| !inactive.contains(r.id()) && r.tags().contains(dimension))
+        .map(|r| r.subject())
+        .collect();
+    reports.retain(|subject, report| {
+        tagged.contains(subject.as_str()) || report.limiting_path.is_some()
+    });
+    reports
+}
+
 /// Compute raw score from a slice of record references.
-pub fn raw_score_from_refs(records: &[&Record]) -> i32 {
-    let superseded_ids: HashSet<&str> = records.iter().filter_map(|r| r.supersedes()).collect();
+pub fn raw_score_from_refs(records: &[&Record], eval: &Evaluation) -> i32 {
+    let inactive = inactive_ids(records.iter().copied());
 
     let sum = records
         .iter()
-        .filter(|r| !superseded_ids.contains(r.id()))
-        .filter_map(|r| r.score())
+        .filter(|r| !inactive.contains(r.id()))
+        .filter_map(|r| contribution(r, eval))
         .fold(0i32, |acc, s| acc.saturating_add(s));
 
     clamp_score(sum)
 }
 
+/// A line range within a subject where one or more spans overlap.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpanRegion {
+    /// First line of the region (inclusive).
+    pub start_line: u32,
+    /// Last line of the region (inclusive).
+    pub end_line: u32,
+    /// Sum of the contributions of the records in this region, clamped to
+    /// [-100, 100].
+    pub raw: i32,
+    /// IDs of the active records whose spans fall in this region.
+    pub records: Vec<String>,
+}
+
+/// Compute per-region raw scores for the span-addressed records of a single
+/// subject.
+///
+/// Active records with a span are grouped by line range: spans that share at
+/// least one line merge into a single region. Columns are ignored. Records
+/// without a span address the whole subject and belong to no region. The
+/// subject's own raw score is unaffected. Regions are returned in line order.
+pub fn span_regions(records: &[Record], eval: &Evaluation) -> Vec<SpanRegion> {
+    let mut spanned: Vec<(u32, u32, &Record)> = filter_superseded(records)
+        .into_iter()
+        .filter(|r| r.is_scored())
+        .filter_map(|r| {
+            let span = r.span()?;
+            let start = span.start.line;
+            let end = span.end_or_start().line.max(start);
+            Some((start, end, r))
+        })
+        .collect();
+    spanned.sort_by_key(|&(start, end, _)| (start, end));
+
+    let mut regions: Vec<(SpanRegion, i32)> = Vec::new();
+    for (start, end, record) in spanned {
+        let score = contribution(record, eval).unwrap_or(0);
+        match regions.last_mut() {
+            Some((region, sum)) if start <= region.end_line => {
+                region.end_line = region.end_line.max(end);
+                region.records.push(record.id().to_string());
+                *sum = sum.saturating_add(score);
+            }
+            _ => regions.push((
+                SpanRegion {
+                    start_line: start,
+                    end_line: end,
+                    raw: 0,
+                    records: vec![record.id().to_string()],
+                },
+                score,
+            )),
+        }
+    }
+
+    regions
+        .into_iter()
+        .map(|(mut region, sum)| {
+            region.raw = clamp_score(sum);
+            region
+        })
+        .collect()
+}
+
 /// Describe the status of a score for display purposes.
 ///
 /// Score severity takes priority; "limited" is appended when the effective
@@ -237,6 +530,15 @@ mod tests {
     use chrono::Utc;
     use std::path::PathBuf;
 
+    /// Evaluation without decay, at a fixed instant.
+    fn nominal() -> Evaluation {
+        Evaluation::at(
+            chrono::DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
     fn make_att(subject: &str, kind: Kind, score: i32, summary: &str) -> Attestation {
         attestation::finalize(Attestation {
             metabox: "1".into(),
@@ -250,6 +552,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind,
                 r#ref: None,
                 score,
@@ -257,6 +562,7 @@ mod tests {
                 suggested_fix: None,
                 summary: summary.into(),
                 supersedes: None,
+                symbol: None,
                 tags: vec![],
             },
         })
@@ -279,6 +585,9 @@ mod tests {
             id: String::new(),
             body: AttestationBody {
                 detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
                 kind: Kind::Pass,
                 r#ref: None,
                 score,
@@ -286,6 +595,7 @@ mod tests {
                 suggested_fix: None,
                 summary: "updated".into(),
                 supersedes: Some(supersedes_id.into()),
+                symbol: None,
                 tags: vec![],
             },
         })))
@@ -297,12 +607,12 @@ mod tests {
             make_record("x", Kind::Praise, 40, "good"),
             make_record("x", Kind::Concern, -30, "bad"),
         ];
-        assert_eq!(raw_score(&records), 10);
+        assert_eq!(raw_score(&records, &nominal()), 10);
     }
 
     #[test]
     fn test_raw_score_empty() {
-        assert_eq!(raw_score(&[]), 0);
+        assert_eq!(raw_score(&[], &nominal()), 0);
     }
 
     #[test]
@@ -311,7 +621,7 @@ mod tests {
             make_record("x", Kind::Praise, 80, "great"),
             make_record("x", Kind::Praise, 80, "also great"),
         ];
-        assert_eq!(raw_score(&records), 100); // clamped
+        assert_eq!(raw_score(&records, &nominal()), 100); // clamped
     }
 
     #[test]
@@ -320,7 +630,7 @@ mod tests {
             make_record("x", Kind::Fail, -80, "bad"),
             make_record("x", Kind::Fail, -80, "worse"),
         ];
-        assert_eq!(raw_score(&records), -100); // clamped
+        assert_eq!(raw_score(&records, &nominal()), -100); // clamped
     }
 
     #[test]
@@ -329,7 +639,7 @@ mod tests {
         let replacement = make_superseding("x", 10, original.id());
         let records = vec![original, replacement];
         // Original (-30) is superseded, only replacement (10) counts
-        assert_eq!(raw_score(&records), 10);
+        assert_eq!(raw_score(&records, &nominal()), 10);
     }
 
     #[test]
@@ -359,7 +669,7 @@ mod tests {
             records: vec![make_record("x", Kind::Praise, 50, "good")],
         };
 
-        let scores = effective_scores(&graph, &[qf]);
+        let scores = effective_scores(&graph, &[qf], &nominal());
         let report = scores.get("x").unwrap();
         assert_eq!(report.raw, 50);
         assert_eq!(report.effective, 50);
@@ -384,7 +694,7 @@ mod tests {
             records: vec![make_record("lib", Kind::Concern, -20, "bad lib")],
         };
 
-        let scores = effective_scores(&g, &[qf_app, qf_lib]);
+        let scores = effective_scores(&g, &[qf_app, qf_lib], &nominal());
 
         let app_report = scores.get("app").unwrap();
         assert_eq!(app_report.raw, 80);
@@ -423,7 +733,7 @@ mod tests {
             },
         ];
 
-        let scores = effective_scores(&g, &qfs);
+        let scores = effective_scores(&g, &qfs, &nominal());
 
         assert_eq!(scores["leaf"].effective, -50);
         assert_eq!(scores["mid"].effective, -50);
@@ -436,7 +746,7 @@ mod tests {
 {"subject":"lib","depends_on":[]}
 "#;
         let g = graph::parse_graph(graph_str).unwrap();
-        let scores = effective_scores(&g, &[]);
+        let scores = effective_scores(&g, &[], &nominal());
 
         assert_eq!(scores["app"].raw, 0);
         assert_eq!(scores["app"].effective, 0);
@@ -531,16 +841,16 @@ mod tests {
     #[test]
     fn test_raw_score_exact_boundaries() {
         let records = vec![make_record("x", Kind::Fail, -100, "terrible")];
-        assert_eq!(raw_score(&records), -100);
+        assert_eq!(raw_score(&records, &nominal()), -100);
 
         let records = vec![make_record("x", Kind::Praise, 100, "perfect")];
-        assert_eq!(raw_score(&records), 100);
+        assert_eq!(raw_score(&records, &nominal()), 100);
 
         let records = vec![
             make_record("x", Kind::Praise, 30, "good"),
             make_record("x", Kind::Concern, -30, "bad"),
         ];
-        assert_eq!(raw_score(&records), 0);
+        assert_eq!(raw_score(&records, &nominal()), 0);
     }
 
     #[test]
@@ -555,7 +865,7 @@ mod tests {
             subject: "app".into(),
             records: vec![make_record("app", Kind::Praise, 50, "good")],
         }];
-        let scores = effective_scores(&g, &qfs);
+        let scores = effective_scores(&g, &qfs, &nominal());
         assert_eq!(scores["app"].effective, 0);
         assert_eq!(scores["lib"].effective, 0);
     }
@@ -592,10 +902,324 @@ mod tests {
             },
         ];
 
-        let scores = effective_scores(&g, &qfs);
+        let scores = effective_scores(&g, &qfs, &nominal());
         assert_eq!(scores["leaf1"].effective, -100);
         assert_eq!(scores["leaf2"].effective, 80);
         assert_eq!(scores["mid"].effective, -100);
         assert_eq!(scores["app"].effective, -100);
     }
+
+    fn decaying(kind: &str, rule: DecayRule, days_later: i64) -> Evaluation {
+        let created = chrono::DateTime::parse_from_rfc3339("2026-02-24T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut model = DecayModel::default();
+        model.0.insert(kind.into(), rule);
+        Evaluation::at(created + chrono::Duration::days(days_later)).with_decay(model)
+    }
+
+    const HALF_LIFE_180: DecayRule = DecayRule {
+        half_life_days: Some(180),
+        expires_after_days: None,
+    };
+
+    #[test]
+    fn test_decay_score_half_life() {
+        assert_eq!(decay_score(40, 0, &HALF_LIFE_180), 40);
+        assert_eq!(decay_score(40, 90, &HALF_LIFE_180), 30);
+        assert_eq!(decay_score(40, 180, &HALF_LIFE_180), 20);
+        assert_eq!(decay_score(40, 360, &HALF_LIFE_180), 10);
+        assert_eq!(decay_score(-30, 180, &HALF_LIFE_180), -15);
+        assert_eq!(decay_score(-30, 270, &HALF_LIFE_180), -12);
+        assert_eq!(decay_score(40, 180 * 40, &HALF_LIFE_180), 0);
+        // Records from the future do not gain weight.
+        assert_eq!(decay_score(40, -10, &HALF_LIFE_180), 40);
+    }
+
+    #[test]
+    fn test_decay_score_expiry() {
+        let rule = DecayRule {
+            half_life_days: None,
+            expires_after_days: Some(365),
+        };
+        assert_eq!(decay_score(20, 364, &rule), 20);
+        assert_eq!(decay_score(20, 365, &rule), 0);
+    }
+
+    #[test]
+    fn test_raw_score_applies_decay_per_kind() {
+        let records = vec![
+            make_record("a.rs", Kind::Praise, 40, "great"),
+            make_record("a.rs", Kind::Concern, -10, "meh"),
+        ];
+        assert_eq!(raw_score(&records, &nominal()), 30);
+        assert_eq!(
+            raw_score(&records, &decaying("praise", HALF_LIFE_180, 180)),
+            10
+        );
+        // A rule for another kind leaves these records alone.
+        assert_eq!(
+            raw_score(&records, &decaying("pass", HALF_LIFE_180, 180)),
+            30
+        );
+    }
+
+    #[test]
+    fn test_effective_scores_use_evaluation() {
+        let qf = QualFile {
+            path: PathBuf::from("lib.rs.qual"),
+            subject: "lib.rs".into(),
+            records: vec![make_record("lib.rs", Kind::Pass, 20, "ok")],
+        };
+        let expire = DecayRule {
+            half_life_days: None,
+            expires_after_days: Some(365),
+        };
+
+        let fresh = effective_scores(
+            &graph::DependencyGraph::empty(),
+            std::slice::from_ref(&qf),
+            &decaying("pass", expire, 30),
+        );
+        assert_eq!(fresh["lib.rs"].raw, 20);
+
+        let stale = effective_scores(
+            &graph::DependencyGraph::empty(),
+            &[qf],
+            &decaying("pass", expire, 400),
+        );
+        assert_eq!(stale["lib.rs"].raw, 0);
+    }
+
+    fn make_spanned(start: u32, end: u32, score: i32, summary: &str) -> Record {
+        let mut att = make_att("a.rs", Kind::Concern, score, summary);
+        att.body.span = Some(attestation::Span {
+            start: attestation::Position {
+                line: start,
+                col: None,
+            },
+            end: Some(attestation::Position {
+                line: end,
+                col: None,
+            }),
+        });
+        Record::Attestation(Box::new(attestation::finalize(att)))
+    }
+
+    #[test]
+    fn test_span_regions_merge_overlapping_spans() {
+        let records = vec![
+            make_record("a.rs", Kind::Praise, 40, "whole file"),
+            make_spanned(50, 60, -20, "late"),
+            make_spanned(40, 52, -30, "early"),
+            make_spanned(61, 70, -5, "adjacent, not overlapping"),
+            make_spanned(100, 100, 10, "single line"),
+        ];
+
+        let regions = span_regions(&records, &nominal());
+        let ranges: Vec<(u32, u32, i32, usize)> = regions
+            .iter()
+            .map(|r| (r.start_line, r.end_line, r.raw, r.records.len()))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![(40, 60, -50, 2), (61, 70, -5, 1), (100, 100, 10, 1)]
+        );
+        // The subject score still counts everything.
+        assert_eq!(raw_score(&records, &nominal()), -5);
+    }
+
+    #[test]
+    fn test_span_regions_skip_superseded() {
+        let old = make_spanned(10, 20, -40, "old");
+        let mut newer = make_att("a.rs", Kind::Concern, -10, "better now");
+        newer.body.span = old.span().cloned();
+        newer.body.supersedes = Some(old.id().to_string());
+        let newer = Record::Attestation(Box::new(attestation::finalize(newer)));
+
+        let regions = span_regions(&[old, newer.clone()], &nominal());
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].raw, -10);
+        assert_eq!(regions[0].records, vec![newer.id().to_string()]);
+    }
+
+    #[test]
+    fn test_span_regions_empty_without_spans() {
+        let records = vec![make_record("a.rs", Kind::Pass, 20, "ok")];
+        assert!(span_regions(&records, &nominal()).is_empty());
+    }
+
+    fn make_revocation(subject: &str, revokes: &[&str]) -> Record {
+        attestation::finalize_record(Record::Revocation(attestation::RevocationRecord {
+            metabox: "1".into(),
+            record_type: "revocation".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: nominal().at,
+            id: String::new(),
+            body: attestation::RevocationBody {
+                revokes: revokes.iter().map(|s| s.to_string()).collect(),
+                summary: "wrong".into(),
+            },
+        }))
+    }
+
+    #[test]
+    fn test_revoked_records_are_inactive() {
+        let good = make_record("a.rs", Kind::Praise, 40, "great");
+        let bad = make_record("a.rs", Kind::Blocker, -50, "not actually broken");
+        let revocation = make_revocation("a.rs", &[bad.id()]);
+        let records = vec![good.clone(), bad, revocation.clone()];
+
+        let active = filter_superseded(&records);
+        let ids: Vec<&str> = active.iter().map(|r| r.id()).collect();
+        assert_eq!(ids, vec![good.id(), revocation.id()]);
+        assert_eq!(raw_score(&records, &nominal()), 40);
+
+        let refs: Vec<&Record> = records.iter().collect();
+        assert_eq!(raw_score_from_refs(&refs, &nominal()), 40);
+    }
+
+    #[test]
+    fn test_revoking_replacement_reactivates_original() {
+        let original = make_record("a.rs", Kind::Concern, -20, "slow");
+        let replacement = make_superseding("a.rs", 30, original.id());
+        let revocation = make_revocation("a.rs", &[replacement.id()]);
+
+        let records = vec![original.clone(), replacement.clone()];
+        assert_eq!(raw_score(&records, &nominal()), 30);
+
+        let records = vec![original, replacement, revocation];
+        assert_eq!(raw_score(&records, &nominal()), -20);
+    }
+
+    #[test]
+    fn test_expired_records_contribute_nothing() {
+        let mut waiver = make_att("a.rs", Kind::Waiver, 10, "accepted until Q2");
+        waiver.body.expires_at = Some(nominal().at + chrono::Duration::days(30));
+        let waiver = Record::Attestation(Box::new(attestation::finalize(waiver)));
+        let concern = make_record("a.rs", Kind::Concern, -20, "slow");
+        let records = vec![waiver.clone(), concern];
+
+        assert_eq!(raw_score(&records, &nominal()), -10);
+        let later = Evaluation::at(nominal().at + chrono::Duration::days(30));
+        assert_eq!(raw_score(&records, &later), -20);
+        assert_eq!(contribution(&waiver, &later), Some(0));
+    }
+
+    #[test]
+    fn test_expiring_lists_active_records_soonest_first() {
+        let in_days = |days: i64, summary: &str| {
+            let mut att = make_att("a.rs", Kind::Pass, 10, summary);
+            att.body.expires_at = Some(nominal().at + chrono::Duration::days(days));
+            Record::Attestation(Box::new(attestation::finalize(att)))
+        };
+        let late = in_days(60, "late");
+        let soon = in_days(10, "soon");
+        let expired = in_days(-5, "expired");
+        let replaced = in_days(5, "replaced");
+        let replacement = make_superseding("a.rs", 10, replaced.id());
+        let records = vec![
+            late,
+            soon.clone(),
+            expired.clone(),
+            replaced,
+            replacement,
+            make_record("a.rs", Kind::Praise, 20, "no expiry"),
+        ];
+
+        let ids: Vec<&str> = expiring(&records, nominal().at)
+            .iter()
+            .map(|r| r.id())
+            .collect();
+        assert_eq!(ids, vec![expired.id()]);
+
+        let ids: Vec<&str> = expiring(&records, nominal().at + chrono::Duration::days(30))
+            .iter()
+            .map(|r| r.id())
+            .collect();
+        assert_eq!(ids, vec![expired.id(), soon.id()]);
+    }
+
+    #[test]
+    fn test_stale_records_keep_their_weight() {
+        let pass = make_record("a.rs", Kind::Pass, 30, "reviewed");
+        let concern = make_record("a.rs", Kind::Concern, -15, "slow");
+        let records = vec![pass.clone(), concern.clone()];
+        let stale = |weight| {
+            nominal().with_staleness(Staleness {
+                ids: [pass.id().to_string(), concern.id().to_string()].into(),
+                weight,
+            })
+        };
+
+        assert_eq!(raw_score(&records, &nominal()), 15);
+        assert_eq!(raw_score(&records, &stale(100)), 15);
+        assert_eq!(raw_score(&records, &stale(50)), 8);
+        assert_eq!(contribution(&concern, &stale(50)), Some(-7));
+        assert_eq!(raw_score(&records, &stale(0)), 0);
+    }
+
+    fn make_tagged(subject: &str, score: i32, tags: &[&str]) -> Record {
+        let mut att = make_att(subject, Kind::Concern, score, &format!("{tags:?}"));
+        att.body.tags = tags.iter().map(|t| t.to_string()).collect();
+        Record::Attestation(Box::new(attestation::finalize(att)))
+    }
+
+    #[test]
+    fn test_dimension_scores_propagate_independently() {
+        let g = graph::parse_graph(
+            r#"{"subject":"app","depends_on":["lib"]}
+{"subject":"lib","depends_on":[]}
+{"subject":"docs","depends_on":[]}
+"#,
+        )
+        .unwrap();
+        let qual_files = vec![
+            QualFile {
+                path: PathBuf::from("app.qual"),
+                subject: "app".into(),
+                records: vec![make_tagged("app", 40, &["perf"])],
+            },
+            QualFile {
+                path: PathBuf::from("lib.qual"),
+                subject: "lib".into(),
+                records: vec![
+                    make_tagged("lib", -30, &["security"]),
+                    make_tagged("lib", 20, &["perf", "tests"]),
+                ],
+            },
+            QualFile {
+                path: PathBuf::from("docs.qual"),
+                subject: "docs".into(),
+                records: vec![make_tagged("docs", 10, &[])],
+            },
+        ];
+
+        let overall = dimension_scores(&g, &qual_files, &nominal());
+        assert_eq!(overall["app"].effective, -10);
+        assert_eq!(overall["lib"].raw, -10);
+        assert!(overall.contains_key("docs"));
+
+        let security = dimension_scores(&g, &qual_files, &nominal().with_dimension("security"));
+        assert_eq!(security["lib"].raw, -30);
+        assert_eq!(security["app"].raw, 0);
+        assert_eq!(security["app"].effective, -30);
+        assert!(!security.contains_key("docs"));
+
+        let perf = dimension_scores(&g, &qual_files, &nominal().with_dimension("perf"));
+        assert_eq!(perf["app"].raw, 40);
+        assert_eq!(perf["app"].effective, 20);
+        assert_eq!(perf["lib"].raw, 20);
+    }
+
+    #[test]
+    fn test_dimension_respects_supersession_of_untagged_records() {
+        let tagged = make_tagged("a.rs", -20, &["security"]);
+        let fixed = make_superseding("a.rs", 10, tagged.id());
+        let records = vec![tagged, fixed];
+        let eval = nominal().with_dimension("security");
+        assert_eq!(raw_score(&records, &eval), 0);
+    }
 }
@@ -0,0 +1,499 @@
+//! Detached Ed25519 signatures over records.
+//!
+//! A signature covers the DSSE pre-authentication encoding (PAE) of a
+//! record's Metabox Canonical Form — the same bytes [`generate_id`] hashes,
+//! with `id` set to `""` — so a signature binds every field of the record
+//! except the stored `id`. That ID is bound only indirectly: it is the hash
+//! of the signed bytes, so [`verify_record`] recomputes the MCF from the
+//! record's content rather than trusting it.
+//!
+//! Signatures live in a sidecar next to the `.qual` file
+//! (`src/parser.rs.qual.sig`), one JSON object per line. Keeping them out of
+//! the record means signing never changes a record ID, and union merges of
+//! the sidecar behave like union merges of the `.qual` file itself.
+//!
+//! Public keys are looked up in a keyring file mapping issuer URIs to
+//! Ed25519 public keys. Everything here works offline.
+//!
+//! [`generate_id`]: crate::attestation::generate_id
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::attestation::{Record, canonical_form};
+
+/// DSSE payload type for a record in Metabox Canonical Form.
+pub const PAYLOAD_TYPE: &str = "application/vnd.qualifier.record+json";
+
+/// A detached signature over one record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signature {
+    /// ID of the signed record, used to find its signatures. It is not part
+    /// of the signed bytes.
+    pub record_id: String,
+    /// Identifier of the signing key (see [`key_id`]).
+    pub keyid: String,
+    /// Base64-encoded Ed25519 signature over the PAE of the record's MCF.
+    pub sig: String,
+}
+
+/// A keyring entry binding an issuer URI to a public key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyEntry {
+    /// Issuer URI, matched exactly against a record's `issuer`.
+    pub issuer: String,
+    /// Base64-encoded 32-byte Ed25519 public key.
+    pub public_key: String,
+}
+
+/// A set of trusted public keys, indexed by issuer.
+///
+/// An issuer may have several keys (e.g. one per machine, or during
+/// rotation); a signature by any of them is accepted.
+#[derive(Debug, Clone, Default)]
+pub struct Keyring {
+    keys: Vec<(String, VerifyingKey)>,
+}
+
+/// The result of verifying one record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// A signature by a keyring key for the record's issuer verified.
+    Verified { keyid: String },
+    /// No signature exists for this record.
+    Unsigned,
+    /// The record is signed, but the keyring has no key for its issuer.
+    UnknownIssuer,
+    /// A signature exists but does not verify against any key for the issuer.
+    Invalid { reason: String },
+}
+
+/// Compute the DSSE pre-authentication encoding of `payload`.
+///
+/// `PAE(type, body) = "DSSEv1" SP LEN(type) SP type SP LEN(body) SP body`
+pub fn pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut out = format!(
+        "DSSEv1 {} {} {} ",
+        payload_type.len(),
+        payload_type,
+        payload.len()
+    )
+    .into_bytes();
+    out.extend_from_slice(payload);
+    out
+}
+
+/// Short, stable identifier for a public key: the first 16 hex characters
+/// of the BLAKE3 hash of its bytes.
+pub fn key_id(key: &VerifyingKey) -> String {
+    blake3::hash(key.as_bytes()).to_hex()[..16].to_string()
+}
+
+/// Base64-encode a public key for a keyring entry.
+pub fn encode_public_key(key: &VerifyingKey) -> String {
+    BASE64.encode(key.as_bytes())
+}
+
+/// Decode a base64-encoded 32-byte Ed25519 public key.
+pub fn decode_public_key(encoded: &str) -> crate::Result<VerifyingKey> {
+    let bytes = decode_32(encoded, "public key")?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| crate::Error::Validation(format!("invalid public key: {e}")))
+}
+
+/// Base64-encode a signing key's 32-byte seed for storage on disk.
+pub fn encode_signing_key(key: &SigningKey) -> String {
+    BASE64.encode(key.to_bytes())
+}
+
+/// Decode a base64-encoded 32-byte Ed25519 seed.
+pub fn decode_signing_key(encoded: &str) -> crate::Result<SigningKey> {
+    Ok(SigningKey::from_bytes(&decode_32(encoded, "signing key")?))
+}
+
+fn decode_32(encoded: &str, what: &str) -> crate::Result<[u8; 32]> {
+    let bytes = BASE64
+        .decode(encoded.trim())
+        .map_err(|e| crate::Error::Validation(format!("invalid {what}: {e}")))?;
+    bytes.try_into().map_err(|b: Vec<u8>| {
+        crate::Error::Validation(format!(
+            "invalid {what}: expected 32 bytes, got {}",
+            b.len()
+        ))
+    })
+}
+
+/// Sign a record. Returns `None` for unknown record types, which have no MCF.
+pub fn sign_record(record: &Record, key: &SigningKey) -> Option<Signature> {
+    let canonical = canonical_form(record)?;
+    let sig = key.sign(&pae(PAYLOAD_TYPE, canonical.as_bytes()));
+    Some(Signature {
+        record_id: record.id().to_string(),
+        keyid: key_id(&key.verifying_key()),
+        sig: BASE64.encode(sig.to_bytes()),
+    })
+}
+
+/// Verify `record` against the signatures in `signatures` that name it.
+///
+/// The record's MCF is recomputed from its content, so a record edited
+/// after signing fails verification even if its stored `id` was kept.
+pub fn verify_record(record: &Record, signatures: &[Signature], keyring: &Keyring) -> Verification {
+    let sigs: Vec<&Signature> = signatures
+        .iter()
+        .filter(|s| s.record_id == record.id())
+        .collect();
+    if sigs.is_empty() {
+        return Verification::Unsigned;
+    }
+
+    let Some(issuer) = record.issuer() else {
+        return Verification::Invalid {
+            reason: "record has no issuer".into(),
+        };
+    };
+    let keys = keyring.keys_for(issuer);
+    if keys.is_empty() {
+        return Verification::UnknownIssuer;
+    }
+    let Some(canonical) = canonical_form(record) else {
+        return Verification::Invalid {
+            reason: "unknown record type".into(),
+        };
+    };
+    let message = pae(PAYLOAD_TYPE, canonical.as_bytes());
+
+    let mut reason = "signed by a key not registered for this issuer".to_string();
+    for sig in sigs {
+        let Some(key) = keys.iter().find(|k| key_id(k) == sig.keyid) else {
+            continue;
+        };
+        let parsed = BASE64
+            .decode(&sig.sig)
+            .ok()
+            .and_then(|b| ed25519_dalek::Signature::from_slice(&b).ok());
+        match parsed {
+            Some(s) if key.verify(&message, &s).is_ok() => {
+                return Verification::Verified {
+                    keyid: sig.keyid.clone(),
+                };
+            }
+            _ => reason = format!("signature by key {} does not verify", sig.keyid),
+        }
+    }
+    Verification::Invalid { reason }
+}
+
+impl Keyring {
+    /// Parse a keyring from JSONL content. Blank lines and `//` comments are
+    /// skipped.
+    pub fn parse_str(content: &str) -> crate::Result<Keyring> {
+        let mut keyring = Keyring::default();
+        for (line_no, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with("//") {
+                continue;
+            }
+            let entry: KeyEntry = serde_json::from_str(trimmed)
+                .map_err(|e| crate::Error::Validation(format!("line {}: {}", line_no + 1, e)))?;
+            let key = decode_public_key(&entry.public_key)
+                .map_err(|e| crate::Error::Validation(format!("line {}: {}", line_no + 1, e)))?;
+            keyring.insert(entry.issuer, key);
+        }
+        Ok(keyring)
+    }
+
+    /// Load a keyring file. A missing file yields an empty keyring.
+    pub fn load(path: &Path) -> crate::Result<Keyring> {
+        if !path.exists() {
+            return Ok(Keyring::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Keyring::parse_str(&content)
+            .map_err(|e| crate::Error::Validation(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Register `key` for `issuer`. Duplicate entries are ignored.
+    pub fn insert(&mut self, issuer: String, key: VerifyingKey) {
+        if !self.keys.iter().any(|(i, k)| *i == issuer && *k == key) {
+            self.keys.push((issuer, key));
+        }
+    }
+
+    /// All keys registered for `issuer`.
+    pub fn keys_for(&self, issuer: &str) -> Vec<VerifyingKey> {
+        self.keys
+            .iter()
+            .filter(|(i, _)| i == issuer)
+            .map(|(_, k)| *k)
+            .collect()
+    }
+
+    /// Whether the keyring has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Path of the signature sidecar for a `.qual` file.
+pub fn sig_path(qual_path: &Path) -> PathBuf {
+    let mut path = qual_path.as_os_str().to_owned();
+    path.push(".sig");
+    PathBuf::from(path)
+}
+
+/// Read the signature sidecar for a `.qual` file. A missing sidecar yields
+/// no signatures.
+pub fn read_signatures(qual_path: &Path) -> crate::Result<Vec<Signature>> {
+    let path = sig_path(qual_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    let mut sigs = Vec::new();
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+        let sig: Signature = serde_json::from_str(trimmed).map_err(|e| {
+            crate::Error::Validation(format!("{}:{}: {}", path.display(), line_no + 1, e))
+        })?;
+        sigs.push(sig);
+    }
+    Ok(sigs)
+}
+
+/// Append signatures to the sidecar for a `.qual` file.
+pub fn append_signatures(qual_path: &Path, signatures: &[Signature]) -> crate::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(sig_path(qual_path))?;
+    for sig in signatures {
+        writeln!(file, "{}", serde_json::to_string(sig)?)?;
+    }
+    Ok(())
+}
+
+/// Replace the sidecar for a `.qual` file with `signatures`, or remove it
+/// if there are none. The sidecar is written to a temporary file and renamed
+/// over the original, so readers never see a partial sidecar.
+pub fn write_signatures(qual_path: &Path, signatures: &[Signature]) -> crate::Result<()> {
+    let path = sig_path(qual_path);
+    if signatures.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        return Ok(());
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".tmp-{}", std::process::id()));
+    let tmp = PathBuf::from(tmp);
+    let mut file = fs::File::create(&tmp)?;
+    for sig in signatures {
+        writeln!(file, "{}", serde_json::to_string(sig)?)?;
+    }
+    file.sync_all()?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// The signatures among `signatures` that cover one of `records`, in order
+/// and without duplicates. Signatures are keyed by record ID, so this is
+/// how they follow records moved between `.qual` files.
+pub fn signatures_for(signatures: &[Signature], records: &[Record]) -> Vec<Signature> {
+    let ids: std::collections::HashSet<&str> = records.iter().map(|r| r.id()).collect();
+    let mut kept: Vec<Signature> = Vec::new();
+    for sig in signatures {
+        if ids.contains(sig.record_id.as_str()) && !kept.contains(sig) {
+            kept.push(sig.clone());
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{self, Attestation, AttestationBody, Kind};
+
+    fn make_record(issuer: &str, score: i32) -> Record {
+        attestation::finalize_record(Record::Attestation(Box::new(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: "src/parser.rs".into(),
+            issuer: issuer.into(),
+            issuer_type: None,
+            created_at: chrono::DateTime::parse_from_rfc3339("2026-02-24T10:00:00Z")
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
+                kind: Kind::Concern,
+                r#ref: None,
+                score,
+                span: None,
+                suggested_fix: None,
+                summary: "Panics on malformed input".into(),
+                supersedes: None,
+                symbol: None,
+                tags: vec![],
+            },
+        })))
+    }
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn keyring_for(issuer: &str, key: &SigningKey) -> Keyring {
+        let mut keyring = Keyring::default();
+        keyring.insert(issuer.into(), key.verifying_key());
+        keyring
+    }
+
+    #[test]
+    fn test_pae_format() {
+        assert_eq!(pae("t", b"abc"), b"DSSEv1 1 t 3 abc".to_vec());
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let record = make_record("mailto:alice@example.com", -30);
+        let key = key(1);
+        let sig = sign_record(&record, &key).unwrap();
+        assert_eq!(sig.record_id, record.id());
+
+        let keyring = keyring_for("mailto:alice@example.com", &key);
+        assert_eq!(
+            verify_record(&record, std::slice::from_ref(&sig), &keyring),
+            Verification::Verified { keyid: sig.keyid }
+        );
+    }
+
+    #[test]
+    fn test_verify_unsigned() {
+        let record = make_record("mailto:alice@example.com", -30);
+        let keyring = keyring_for("mailto:alice@example.com", &key(1));
+        assert_eq!(
+            verify_record(&record, &[], &keyring),
+            Verification::Unsigned
+        );
+    }
+
+    #[test]
+    fn test_verify_unknown_issuer() {
+        let record = make_record("mailto:alice@example.com", -30);
+        let sig = sign_record(&record, &key(1)).unwrap();
+        let keyring = keyring_for("mailto:bob@example.com", &key(1));
+        assert_eq!(
+            verify_record(&record, &[sig], &keyring),
+            Verification::UnknownIssuer
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_spoofed_issuer() {
+        // Mallory signs a record claiming to be Alice with her own key.
+        let record = make_record("mailto:alice@example.com", -30);
+        let sig = sign_record(&record, &key(2)).unwrap();
+        let keyring = keyring_for("mailto:alice@example.com", &key(1));
+        assert!(matches!(
+            verify_record(&record, &[sig], &keyring),
+            Verification::Invalid { .. }
+        ));
+    }
+
+    #[test]
+    fn test_verify_detects_tampering() {
+        let record = make_record("mailto:alice@example.com", -30);
+        let key = key(1);
+        let sig = sign_record(&record, &key).unwrap();
+
+        // Change the score but keep the stored ID.
+        let mut tampered = record.clone();
+        if let Record::Attestation(ref mut a) = tampered {
+            a.body.score = 50;
+        }
+        let keyring = keyring_for("mailto:alice@example.com", &key);
+        assert!(matches!(
+            verify_record(&tampered, &[sig], &keyring),
+            Verification::Invalid { .. }
+        ));
+    }
+
+    #[test]
+    fn test_keyring_multiple_keys_per_issuer() {
+        let content = format!(
+            "{}\n// rotated\n{}\n",
+            serde_json::json!({"issuer": "mailto:alice@example.com", "public_key": encode_public_key(&key(1).verifying_key())}),
+            serde_json::json!({"issuer": "mailto:alice@example.com", "public_key": encode_public_key(&key(2).verifying_key())}),
+        );
+        let keyring = Keyring::parse_str(&content).unwrap();
+        assert_eq!(keyring.keys_for("mailto:alice@example.com").len(), 2);
+
+        let record = make_record("mailto:alice@example.com", -30);
+        let sig = sign_record(&record, &key(2)).unwrap();
+        assert!(matches!(
+            verify_record(&record, &[sig], &keyring),
+            Verification::Verified { .. }
+        ));
+    }
+
+    #[test]
+    fn test_keyring_rejects_bad_key() {
+        let err = Keyring::parse_str(r#"{"issuer":"x","public_key":"AAAA"}"#).unwrap_err();
+        assert!(err.to_string().contains("expected 32 bytes"));
+    }
+
+    #[test]
+    fn test_signing_key_roundtrip() {
+        let key = key(7);
+        let decoded = decode_signing_key(&encode_signing_key(&key)).unwrap();
+        assert_eq!(decoded.to_bytes(), key.to_bytes());
+    }
+
+    #[test]
+    fn test_sidecar_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let qual = dir.path().join("foo.rs.qual");
+        assert!(read_signatures(&qual).unwrap().is_empty());
+
+        let record = make_record("mailto:alice@example.com", -30);
+        let sig = sign_record(&record, &key(1)).unwrap();
+        append_signatures(&qual, std::slice::from_ref(&sig)).unwrap();
+        assert_eq!(sig_path(&qual), dir.path().join("foo.rs.qual.sig"));
+        assert_eq!(read_signatures(&qual).unwrap(), vec![sig]);
+    }
+
+    #[test]
+    fn test_signatures_follow_their_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let qual = dir.path().join("foo.rs.qual");
+        let a = make_record("mailto:alice@example.com", -30);
+        let b = make_record("mailto:alice@example.com", 10);
+        let sig_a = sign_record(&a, &key(1)).unwrap();
+        let sig_b = sign_record(&b, &key(1)).unwrap();
+        let all = vec![sig_a.clone(), sig_b.clone(), sig_a.clone()];
+
+        let for_b = signatures_for(&all, std::slice::from_ref(&b));
+        assert_eq!(for_b, vec![sig_b]);
+        assert_eq!(signatures_for(&all, &[a.clone(), b]).len(), 2);
+
+        write_signatures(&qual, std::slice::from_ref(&sig_a)).unwrap();
+        assert_eq!(read_signatures(&qual).unwrap(), vec![sig_a]);
+        write_signatures(&qual, &[]).unwrap();
+        assert!(!sig_path(&qual).exists());
+    }
+}
@@ -0,0 +1,348 @@
+//! Symbol-addressed spans for Rust sources (SPEC §2.4.5).
+//!
+//! An attestation's `symbol` names a Rust item in its subject file, such as
+//! `parse_span`, `Record::subject`, `impl Record::subject`, or
+//! `impl Display for Kind::fmt`. The symbol is resolved against the current
+//! source to find the item's live line range, so the attestation follows
+//! the item as the file changes.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use syn::spanned::Spanned;
+use syn::{ImplItem, Item, TraitItem, Type};
+
+use crate::attestation::{Position, Record, Span};
+
+/// A parsed symbol: an optional `impl` qualifier and a `::` path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SymbolPath {
+    /// `Some` for `impl ...` symbols; holds the trait name for
+    /// `impl Trait for Type::item`.
+    imp: Option<Option<String>>,
+    segments: Vec<String>,
+}
+
+fn parse_symbol(symbol: &str) -> crate::Result<SymbolPath> {
+    let invalid = |why: &str| crate::Error::Validation(format!("invalid symbol '{symbol}': {why}"));
+    let (imp, path) = match symbol.trim().strip_prefix("impl ") {
+        Some(rest) => match rest.split_once(" for ") {
+            Some((tr, path)) => (Some(Some(tr.trim().to_string())), path.trim()),
+            None => (Some(None), rest.trim()),
+        },
+        None => (None, symbol.trim()),
+    };
+    let segments: Vec<String> = path.split("::").map(|s| s.trim().to_string()).collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        return Err(invalid("empty path segment"));
+    }
+    if imp.is_some() && segments.len() < 2 {
+        return Err(invalid("expected `impl Type::item`"));
+    }
+    Ok(SymbolPath { imp, segments })
+}
+
+/// Last identifier of a type path, ignoring generics and references.
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(r) => type_name(&r.elem),
+        Type::Paren(p) => type_name(&p.elem),
+        Type::Group(g) => type_name(&g.elem),
+        _ => None,
+    }
+}
+
+/// Name of a top-level item, for the kinds of items a symbol can name.
+fn item_name(item: &Item) -> Option<String> {
+    let ident = match item {
+        Item::Const(i) => &i.ident,
+        Item::Enum(i) => &i.ident,
+        Item::Fn(i) => &i.sig.ident,
+        Item::Macro(i) => i.ident.as_ref()?,
+        Item::Mod(i) => &i.ident,
+        Item::Static(i) => &i.ident,
+        Item::Struct(i) => &i.ident,
+        Item::Trait(i) => &i.ident,
+        Item::TraitAlias(i) => &i.ident,
+        Item::Type(i) => &i.ident,
+        Item::Union(i) => &i.ident,
+        _ => return None,
+    };
+    Some(ident.to_string())
+}
+
+fn impl_item_name(item: &ImplItem) -> Option<String> {
+    match item {
+        ImplItem::Const(i) => Some(i.ident.to_string()),
+        ImplItem::Fn(i) => Some(i.sig.ident.to_string()),
+        ImplItem::Type(i) => Some(i.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn trait_item_name(item: &TraitItem) -> Option<String> {
+    match item {
+        TraitItem::Const(i) => Some(i.ident.to_string()),
+        TraitItem::Fn(i) => Some(i.sig.ident.to_string()),
+        TraitItem::Type(i) => Some(i.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Items of the inline `mod name { ... }` in `items`, if any.
+fn inline_mod<'a>(items: &'a [Item], name: &str) -> Option<&'a [Item]> {
+    items.iter().find_map(|item| match item {
+        Item::Mod(m) if m.ident == name => m.content.as_ref().map(|(_, items)| &items[..]),
+        _ => None,
+    })
+}
+
+/// `name` within the impl blocks for `ty` in `items`, optionally only those
+/// implementing `tr`.
+fn find_in_impls(
+    items: &[Item],
+    ty: &str,
+    tr: Option<&str>,
+    name: &str,
+) -> Option<proc_macro2::Span> {
+    items.iter().find_map(|item| {
+        let Item::Impl(imp) = item else {
+            return None;
+        };
+        if type_name(&imp.self_ty).as_deref() != Some(ty) {
+            return None;
+        }
+        if let Some(tr) = tr {
+            let implemented = imp.trait_.as_ref()?.1.segments.last()?.ident.to_string();
+            if implemented != tr {
+                return None;
+            }
+        }
+        imp.items
+            .iter()
+            .find(|i| impl_item_name(i).as_deref() == Some(name))
+            .map(|i| i.span())
+    })
+}
+
+fn find(items: &[Item], path: &SymbolPath) -> Option<proc_macro2::Span> {
+    let segments = &path.segments;
+    if let Some(tr) = &path.imp {
+        let (mods, rest) = segments.split_at(segments.len() - 2);
+        let mut scope = items;
+        for m in mods {
+            scope = inline_mod(scope, m)?;
+        }
+        return find_in_impls(scope, &rest[0], tr.as_deref(), &rest[1]);
+    }
+
+    let (first, rest) = segments.split_first()?;
+    if rest.is_empty() {
+        return items
+            .iter()
+            .find(|item| item_name(item).as_deref() == Some(first))
+            .map(|item| item.span());
+    }
+    if let Some(scope) = inline_mod(items, first) {
+        let nested = SymbolPath {
+            imp: None,
+            segments: rest.to_vec(),
+        };
+        if let Some(span) = find(scope, &nested) {
+            return Some(span);
+        }
+    }
+    let [member] = rest else {
+        return None;
+    };
+    find_in_impls(items, first, None, member).or_else(|| {
+        items.iter().find_map(|item| match item {
+            Item::Trait(t) if t.ident == first => t
+                .items
+                .iter()
+                .find(|i| trait_item_name(i).as_deref() == Some(member))
+                .map(|i| i.span()),
+            Item::Enum(e) if e.ident == first => e
+                .variants
+                .iter()
+                .find(|v| v.ident == member)
+                .map(|v| v.span()),
+            _ => None,
+        })
+    })
+}
+
+fn line_span(span: proc_macro2::Span) -> Span {
+    Span {
+        start: Position {
+            line: span.start().line as u32,
+            col: None,
+        },
+        end: Some(Position {
+            line: span.end().line as u32,
+            col: None,
+        }),
+    }
+}
+
+/// Resolve `symbol` in Rust `source` to the line range of the item,
+/// including its attributes and doc comments. `Ok(None)` means the source
+/// parses but has no such item.
+pub fn resolve(source: &str, symbol: &str) -> crate::Result<Option<Span>> {
+    let path = parse_symbol(symbol)?;
+    let file = syn::parse_file(source)
+        .map_err(|e| crate::Error::Validation(format!("cannot parse Rust source: {e}")))?;
+    Ok(find(&file.items, &path).map(line_span))
+}
+
+/// The module name a file defines: its stem, or its directory's name for
+/// `mod.rs`.
+fn module_name(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    if stem == "mod" {
+        return Some(path.parent()?.file_name()?.to_str()?.to_string());
+    }
+    Some(stem.to_string())
+}
+
+/// Resolve `symbol` in the Rust file at `path`. A leading segment naming the
+/// file's own module (e.g. `attestation::parse_span` in `attestation.rs`)
+/// is accepted.
+pub fn resolve_file(path: &Path, symbol: &str) -> crate::Result<Option<Span>> {
+    let source = std::fs::read_to_string(path)?;
+    let parsed = parse_symbol(symbol)?;
+    let file = syn::parse_file(&source).map_err(|e| {
+        crate::Error::Validation(format!("cannot parse {} as Rust: {e}", path.display()))
+    })?;
+    if let Some(span) = find(&file.items, &parsed) {
+        return Ok(Some(line_span(span)));
+    }
+    let module = module_name(path);
+    if parsed.segments.len() > 1 && module.as_deref() == Some(parsed.segments[0].as_str()) {
+        let rest = SymbolPath {
+            imp: parsed.imp,
+            segments: parsed.segments[1..].to_vec(),
+        };
+        if rest.imp.is_none() || rest.segments.len() >= 2 {
+            return Ok(find(&file.items, &rest).map(line_span));
+        }
+    }
+    Ok(None)
+}
+
+/// Live spans of the active symbol-addressed attestations in `records`,
+/// keyed by record ID. `None` marks a symbol that no longer resolves, or a
+/// subject under `root` that is missing or does not parse.
+pub fn live_spans<'a>(root: &Path, records: &'a [Record]) -> HashMap<&'a str, Option<Span>> {
+    records
+        .iter()
+        .filter_map(|record| {
+            let att = record.as_attestation()?;
+            let symbol = att.body.symbol.as_deref()?;
+            let span = resolve_file(&root.join(&att.subject), symbol)
+                .ok()
+                .flatten();
+            Some((att.id.as_str(), span))
+        })
+        .collect()
+}
+
+/// `records` with each resolved symbol's live span in place of its stored
+/// span. Record IDs are left as they were.
+pub fn with_live_spans(records: &[Record], live: &HashMap<&str, Option<Span>>) -> Vec<Record> {
+    records
+        .iter()
+        .map(|record| match (record, live.get(record.id())) {
+            (Record::Attestation(att), Some(Some(span))) => {
+                let mut att = att.clone();
+                att.body.span = Some(span.clone());
+                Record::Attestation(att)
+            }
+            _ => record.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"use std::fmt;
+
+/// Parse a span.
+pub fn parse_span(s: &str) -> Option<u32> {
+    s.parse().ok()
+}
+
+pub enum Kind {
+    Pass,
+    Fail,
+}
+
+impl Kind {
+    pub fn score(&self) -> i32 {
+        0
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "kind")
+    }
+}
+
+mod inner {
+    pub struct Record;
+
+    impl Record {
+        pub fn subject(&self) -> &str {
+            ""
+        }
+    }
+}
+"#;
+
+    fn lines(symbol: &str) -> Option<(u32, u32)> {
+        resolve(SOURCE, symbol)
+            .unwrap()
+            .map(|s| (s.start.line, s.end_or_start().line))
+    }
+
+    #[test]
+    fn test_resolve_items_methods_and_variants() {
+        assert_eq!(lines("parse_span"), Some((3, 6)));
+        assert_eq!(lines("Kind"), Some((8, 11)));
+        assert_eq!(lines("Kind::Fail"), Some((10, 10)));
+        assert_eq!(lines("Kind::score"), Some((14, 16)));
+        assert_eq!(lines("impl Kind::score"), Some((14, 16)));
+        assert_eq!(lines("Kind::fmt"), Some((20, 22)));
+        assert_eq!(lines("impl Display for Kind::fmt"), Some((20, 22)));
+        assert_eq!(lines("impl Debug for Kind::fmt"), None);
+        assert_eq!(lines("inner::Record"), Some((26, 26)));
+        assert_eq!(lines("impl inner::Record::subject"), Some((29, 31)));
+        assert_eq!(lines("inner::Record::subject"), Some((29, 31)));
+        assert_eq!(lines("missing"), None);
+        assert_eq!(lines("Kind::missing"), None);
+    }
+
+    #[test]
+    fn test_resolve_rejects_bad_input() {
+        assert!(resolve(SOURCE, "impl Kind").is_err());
+        assert!(resolve(SOURCE, "Kind::").is_err());
+        assert!(resolve("fn broken(", "broken").is_err());
+    }
+
+    #[test]
+    fn test_resolve_file_accepts_own_module_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("attestation.rs");
+        std::fs::write(&path, SOURCE).unwrap();
+
+        let span = resolve_file(&path, "attestation::parse_span")
+            .unwrap()
+            .unwrap();
+        assert_eq!(span.start.line, 3);
+        assert!(resolve_file(&path, "other::parse_span").unwrap().is_none());
+    }
+}
@@ -0,0 +1,358 @@
+//! Issuer trust weights and subject allowlists (SPEC §4.8).
+//!
+//! A [`TrustModel`] scales each scored record's contribution by a percentage
+//! chosen from its issuer URI or issuer type, then by every subject rule
+//! whose glob matches. Subject rules may also restrict which issuers count
+//! at all. The result is a pure function of the record and the model.
+
+use std::collections::BTreeMap;
+
+use globset::GlobMatcher;
+use serde::{Deserialize, Serialize};
+
+use crate::attestation::{IssuerType, Record};
+use crate::policy::compile_glob;
+
+/// The largest weight, in percent, a rule may assign, and the largest
+/// combined weight a record can get.
+pub const MAX_WEIGHT: u32 = 1000;
+
+/// Trust configuration: the `[trust]` table of `.qualifier.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustModel {
+    /// Weight in percent for issuers no issuer or issuer-type rule names.
+    /// Unset means `100`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<u32>,
+    /// Weights keyed by issuer URI. These take precedence over
+    /// `issuer_types`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub issuers: BTreeMap<String, u32>,
+    /// Weights keyed by issuer type (`human`, `ai`, `tool`, `unknown`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub issuer_types: BTreeMap<String, u32>,
+    /// Rules for subjects matching a glob, applied in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subjects: Vec<SubjectTrust>,
+}
+
+/// A `[[trust.subjects]]` rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubjectTrust {
+    /// Glob over subject names, e.g. `src/crypto/**`.
+    pub pattern: String,
+    /// Weight in percent applied on top of the issuer's weight.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+    /// If non-empty, only these issuer URIs count for matching subjects;
+    /// everyone else is weighted `0`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+}
+
+/// How much of a record's score counts, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Weighting {
+    /// Percentage of the score that counts.
+    pub percent: u32,
+    /// The rules that set `percent`, in the order they were applied.
+    pub reasons: Vec<String>,
+}
+
+impl Weighting {
+    fn full() -> Self {
+        Weighting {
+            percent: 100,
+            reasons: Vec::new(),
+        }
+    }
+
+    /// Apply this weighting to `score`, truncating toward zero.
+    pub fn apply(&self, score: i32) -> i32 {
+        (i64::from(score) * i64::from(self.percent) / 100) as i32
+    }
+}
+
+/// A validated [`TrustModel`] with its subject globs compiled.
+#[derive(Debug, Clone, Default)]
+pub struct Trust {
+    model: TrustModel,
+    matchers: Vec<GlobMatcher>,
+}
+
+impl PartialEq for Trust {
+    fn eq(&self, other: &Self) -> bool {
+        self.model == other.model
+    }
+}
+
+impl Eq for Trust {}
+
+impl Trust {
+    /// Validate `model` and compile its subject globs.
+    pub fn new(model: TrustModel) -> crate::Result<Self> {
+        let weights = model
+            .default
+            .iter()
+            .chain(model.issuers.values())
+            .chain(model.issuer_types.values())
+            .chain(model.subjects.iter().filter_map(|s| s.weight.as_ref()));
+        for &weight in weights {
+            if weight > MAX_WEIGHT {
+                return Err(crate::Error::Validation(format!(
+                    "trust weight {weight} is out of range [0, {MAX_WEIGHT}]"
+                )));
+            }
+        }
+        for issuer_type in model.issuer_types.keys() {
+            issuer_type
+                .parse::<crate::attestation::IssuerType>()
+                .map_err(|e| crate::Error::Validation(format!("trust: {e}")))?;
+        }
+        let matchers = model
+            .subjects
+            .iter()
+            .map(|s| compile_glob(&s.pattern))
+            .collect::<crate::Result<_>>()?;
+        Ok(Trust { model, matchers })
+    }
+
+    /// The configuration this was built from.
+    pub fn model(&self) -> &TrustModel {
+        &self.model
+    }
+
+    /// Whether every record counts at full weight.
+    pub fn is_empty(&self) -> bool {
+        self.model == TrustModel::default()
+    }
+
+    /// The weighting for `record`. Attestations and epochs are weighted
+    /// alike; unscored records always count in full.
+    pub fn weigh(&self, record: &Record) -> Weighting {
+        if !record.is_scored() {
+            return Weighting::full();
+        }
+        self.weigh_issuer(
+            record.subject(),
+            record.issuer().unwrap_or_default(),
+            record.issuer_type(),
+        )
+    }
+
+    /// The weighting for a scored record about `subject` by `issuer`.
+    ///
+    /// Subject rules multiply, and the product is capped at [`MAX_WEIGHT`].
+    pub fn weigh_issuer(
+        &self,
+        subject: &str,
+        issuer: &str,
+        issuer_type: Option<&IssuerType>,
+    ) -> Weighting {
+        if self.is_empty() {
+            return Weighting::full();
+        }
+
+        let mut weighting = Weighting::full();
+        if let Some(&weight) = self.model.issuers.get(issuer) {
+            weighting.percent = weight;
+            weighting
+                .reasons
+                .push(format!("issuer {issuer}: {weight}%"));
+        } else if let Some(t) = issuer_type
+            && let Some(&weight) = self.model.issuer_types.get(&t.to_string())
+        {
+            weighting.percent = weight;
+            weighting
+                .reasons
+                .push(format!("issuer_type {t}: {weight}%"));
+        } else if let Some(weight) = self.model.default {
+            weighting.percent = weight;
+            weighting.reasons.push(format!("default: {weight}%"));
+        }
+
+        for (rule, matcher) in self.model.subjects.iter().zip(&self.matchers) {
+            if !matcher.is_match(subject) {
+                continue;
+            }
+            if !rule.allow.is_empty() && !rule.allow.iter().any(|a| a == issuer) {
+                weighting.percent = 0;
+                weighting
+                    .reasons
+                    .push(format!("{}: issuer not allowed", rule.pattern));
+                break;
+            }
+            if let Some(weight) = rule.weight {
+                let percent = u64::from(weighting.percent) * u64::from(weight) / 100;
+                weighting.percent = percent.min(u64::from(MAX_WEIGHT)) as u32;
+                weighting
+                    .reasons
+                    .push(format!("{}: {weight}%", rule.pattern));
+            }
+        }
+        weighting
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{self, Attestation, AttestationBody, IssuerType, Kind};
+    use chrono::Utc;
+
+    fn record(subject: &str, issuer: &str, issuer_type: Option<IssuerType>) -> Record {
+        Record::Attestation(Box::new(attestation::finalize(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: subject.into(),
+            issuer: issuer.into(),
+            issuer_type,
+            created_at: Utc::now(),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
+                kind: Kind::Praise,
+                r#ref: None,
+                score: 40,
+                span: None,
+                suggested_fix: None,
+                summary: "good".into(),
+                supersedes: None,
+                symbol: None,
+                tags: vec![],
+            },
+        })))
+    }
+
+    fn model() -> TrustModel {
+        TrustModel {
+            default: Some(80),
+            issuers: BTreeMap::from([("mailto:lead@example.com".into(), 150)]),
+            issuer_types: BTreeMap::from([("ai".into(), 50)]),
+            subjects: vec![
+                SubjectTrust {
+                    pattern: "src/crypto/**".into(),
+                    weight: None,
+                    allow: vec!["mailto:lead@example.com".into()],
+                },
+                SubjectTrust {
+                    pattern: "vendor/**".into(),
+                    weight: Some(10),
+                    allow: vec![],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_empty_model_is_full_weight() {
+        let trust = Trust::default();
+        let w = trust.weigh(&record("src/a.rs", "mailto:a@example.com", None));
+        assert_eq!(w.percent, 100);
+        assert!(w.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_issuer_beats_issuer_type_beats_default() {
+        let trust = Trust::new(model()).unwrap();
+        let lead = trust.weigh(&record(
+            "src/a.rs",
+            "mailto:lead@example.com",
+            Some(IssuerType::Ai),
+        ));
+        assert_eq!(lead.percent, 150);
+        assert_eq!(lead.apply(40), 60);
+
+        let agent = trust.weigh(&record(
+            "src/a.rs",
+            "mailto:bot@example.com",
+            Some(IssuerType::Ai),
+        ));
+        assert_eq!(agent.percent, 50);
+        assert_eq!(agent.reasons, vec!["issuer_type ai: 50%"]);
+
+        let other = trust.weigh(&record("src/a.rs", "mailto:x@example.com", None));
+        assert_eq!(other.percent, 80);
+    }
+
+    #[test]
+    fn test_subject_rules_weight_and_allowlist() {
+        let trust = Trust::new(model()).unwrap();
+        let vendored = trust.weigh(&record("vendor/lib.rs", "mailto:x@example.com", None));
+        assert_eq!(vendored.percent, 8);
+        assert_eq!(vendored.reasons, vec!["default: 80%", "vendor/**: 10%"]);
+
+        let outsider = trust.weigh(&record("src/crypto/aes.rs", "mailto:x@example.com", None));
+        assert_eq!(outsider.percent, 0);
+        assert_eq!(outsider.apply(40), 0);
+
+        let lead = trust.weigh(&record(
+            "src/crypto/aes.rs",
+            "mailto:lead@example.com",
+            None,
+        ));
+        assert_eq!(lead.percent, 150);
+    }
+
+    #[test]
+    fn test_epochs_are_weighted_like_attestations() {
+        let trust = Trust::new(model()).unwrap();
+        let epoch = Record::Epoch(attestation::finalize_epoch(attestation::Epoch {
+            metabox: "1".into(),
+            record_type: "epoch".into(),
+            subject: "src/crypto/aes.rs".into(),
+            issuer: "urn:qualifier:compact".into(),
+            issuer_type: Some(IssuerType::Tool),
+            created_at: chrono::Utc::now(),
+            id: String::new(),
+            body: attestation::EpochBody {
+                refs: vec![],
+                score: 40,
+                span: None,
+                summary: "Compacted from 2 records".into(),
+            },
+        }));
+        let w = trust.weigh(&epoch);
+        assert_eq!(w.percent, 0);
+        assert_eq!(
+            w.reasons,
+            vec!["default: 80%", "src/crypto/**: issuer not allowed"]
+        );
+    }
+
+    #[test]
+    fn test_combined_weight_is_capped() {
+        let rule = SubjectTrust {
+            pattern: "**".into(),
+            weight: Some(MAX_WEIGHT),
+            allow: vec![],
+        };
+        let trust = Trust::new(TrustModel {
+            default: Some(MAX_WEIGHT),
+            subjects: vec![rule; 8],
+            ..Default::default()
+        })
+        .unwrap();
+        let w = trust.weigh(&record("src/a.rs", "mailto:a@example.com", None));
+        assert_eq!(w.percent, MAX_WEIGHT);
+        assert_eq!(w.apply(100), 1000);
+    }
+
+    #[test]
+    fn test_new_rejects_bad_models() {
+        let mut bad = model();
+        bad.default = Some(MAX_WEIGHT + 1);
+        assert!(Trust::new(bad).is_err());
+
+        let mut bad = model();
+        bad.issuer_types.insert("robot".into(), 10);
+        assert!(Trust::new(bad).is_err());
+
+        let mut bad = model();
+        bad.subjects[0].pattern = "src/[".into();
+        assert!(Trust::new(bad).is_err());
+    }
+}
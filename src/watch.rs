@@ -0,0 +1,483 @@
+//! Incremental re-scoring for long-running watchers.
+//!
+//! [`IncrementalScores`] holds the parsed `.qual` files and the current score
+//! reports. When files change, only those files are re-parsed and only the
+//! changed subjects and their transitive dependents are re-scored. Changes to
+//! the dependency graph (the graph file or `dependency` records) rebuild the
+//! graph and re-score everything.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::attestation::Record;
+use crate::graph::{self, DependencyGraph};
+use crate::qual_file::{self, QualFile};
+use crate::scoring::{self, Evaluation, ScoreReport};
+
+/// A change in one subject's score.
+#[derive(Debug, Clone)]
+pub struct ScoreDelta {
+    pub subject: String,
+    /// The report before the change (`None` for a newly scored subject).
+    pub before: Option<ScoreReport>,
+    /// The report after the change (`None` once a subject has no records).
+    pub after: Option<ScoreReport>,
+}
+
+/// Returns true if moving from `old` to `new` can change the score of a
+/// subject no record of which changed.
+fn rescores_everything(old: &Evaluation, new: &Evaluation, qual_files: &[QualFile]) -> bool {
+    let same_rules = Evaluation {
+        at: new.at,
+        ..old.clone()
+    } == *new;
+    if !same_rules {
+        return true;
+    }
+    let (from, to) = (old.at.min(new.at), old.at.max(new.at));
+    from != to
+        && (!new.decay.0.is_empty()
+            || qual_files
+                .iter()
+                .flat_map(|qf| &qf.records)
+                .any(|r| r.expires_at().is_some_and(|e| e > from && e <= to)))
+}
+
+/// Score state that can be updated one file at a time.
+#[derive(Debug, Clone)]
+pub struct IncrementalScores {
+    files: BTreeMap<PathBuf, QualFile>,
+    graph_file: Option<PathBuf>,
+    graph: DependencyGraph,
+    eval: Evaluation,
+    reports: HashMap<String, ScoreReport>,
+}
+
+impl IncrementalScores {
+    /// Score `qual_files` (and the graph file, if any) from scratch under
+    /// `eval`. [`update`](Self::update) keeps this evaluation;
+    /// [`update_with`](Self::update_with) replaces it.
+    pub fn new(
+        qual_files: Vec<QualFile>,
+        graph_file: Option<PathBuf>,
+        eval: Evaluation,
+    ) -> crate::Result<Self> {
+        let graph = graph::build(graph_file.as_deref(), &qual_files)?;
+        let reports = scoring::effective_scores(&graph, &qual_files, &eval);
+        Ok(IncrementalScores {
+            files: qual_files
+                .into_iter()
+                .map(|qf| (qf.path.clone(), qf))
+                .collect(),
+            graph_file,
+            graph,
+            eval,
+            reports,
+        })
+    }
+
+    /// The current score report of every subject.
+    pub fn reports(&self) -> &HashMap<String, ScoreReport> {
+        &self.reports
+    }
+
+    /// Returns true if `path` is a tracked `.qual` file.
+    pub fn is_tracked(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    /// Number of tracked `.qual` files.
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Re-read the files at `changed` and return the resulting score deltas,
+    /// sorted by subject.
+    ///
+    /// A `.qual` path that no longer exists is dropped; an untracked one is
+    /// added. The graph file may be among `changed`. If any file fails to
+    /// parse, or the new graph has a cycle, the error is returned and the
+    /// state is left unchanged.
+    pub fn update(&mut self, changed: &[PathBuf]) -> crate::Result<Vec<ScoreDelta>> {
+        let eval = self.eval.clone();
+        self.update_with(changed, |_| Ok(eval))
+    }
+
+    /// Like [`update`](Self::update), but re-score under the evaluation
+    /// `eval` builds from the updated files, e.g. the current time and
+    /// staleness. If the new evaluation can change a score the changed
+    /// files do not touch — its rules differ, or time passed and records
+    /// decay or expire — every subject is re-scored.
+    pub fn update_with(
+        &mut self,
+        changed: &[PathBuf],
+        eval: impl FnOnce(&[QualFile]) -> crate::Result<Evaluation>,
+    ) -> crate::Result<Vec<ScoreDelta>> {
+        // Parse everything up front so a half-written file changes nothing.
+        let mut parsed = Vec::new();
+        let mut graph_changed = false;
+        for path in changed {
+            if self.graph_file.as_deref() == Some(path.as_path()) {
+                graph_changed = true;
+            } else if path.exists() {
+                parsed.push((path.clone(), Some(qual_file::parse(path)?)));
+            } else {
+                parsed.push((path.clone(), None));
+            }
+        }
+
+        let mut files = self.files.clone();
+        let mut touched: HashSet<String> = HashSet::new();
+        for (path, new) in parsed {
+            let old = match new {
+                Some(qf) => files.insert(path.clone(), qf),
+                None => files.remove(&path),
+            };
+            let old_records = old.as_ref().map_or(&[][..], |qf| &qf.records[..]);
+            let new_records = files.get(&path).map_or(&[][..], |qf| &qf.records[..]);
+            if dependency_ids(old_records) != dependency_ids(new_records) {
+                graph_changed = true;
+            }
+            touched.extend(
+                old_records
+                    .iter()
+                    .chain(new_records)
+                    .filter(|r| !matches!(r, Record::Policy(_)))
+                    .map(|r| r.subject().to_string()),
+            );
+        }
+
+        let all: Vec<QualFile> = files.values().cloned().collect();
+        let eval = eval(&all)?;
+        if graph_changed || rescores_everything(&self.eval, &eval, &all) {
+            let graph = graph::build(self.graph_file.as_deref(), &all)?;
+            let reports = scoring::effective_scores(&graph, &all, &eval);
+            let mut subjects: HashSet<String> = self.reports.keys().cloned().collect();
+            subjects.extend(reports.keys().cloned());
+
+            let old_reports = std::mem::replace(&mut self.reports, reports);
+            self.files = files;
+            self.graph = graph;
+            self.eval = eval;
+            return Ok(self.diff(&old_reports, subjects));
+        }
+
+        self.files = files;
+        self.eval = eval;
+        let affected = self.rescore(&touched);
+        let old_reports = self.reports.clone();
+        for (subject, report) in affected {
+            match report {
+                Some(report) => self.reports.insert(subject, report),
+                None => self.reports.remove(&subject),
+            };
+        }
+        let subjects = old_reports
+            .keys()
+            .chain(self.reports.keys())
+            .cloned()
+            .collect();
+        Ok(self.diff(&old_reports, subjects))
+    }
+
+    /// Recompute the reports of `touched` subjects and their transitive
+    /// dependents, in dependency order. `None` means the subject is no
+    /// longer scored.
+    fn rescore(&self, touched: &HashSet<String>) -> Vec<(String, Option<ScoreReport>)> {
+        let mut affected: HashSet<&str> = HashSet::new();
+        let mut stack: Vec<&str> = touched.iter().map(String::as_str).collect();
+        while let Some(subject) = stack.pop() {
+            if affected.insert(subject) {
+                stack.extend(self.graph.dependents(subject));
+            }
+        }
+
+        // Subjects outside the graph have no dependencies or dependents.
+        let mut order: Vec<&str> = match self.graph.toposort() {
+            Ok(topo) => topo.into_iter().filter(|a| affected.contains(a)).collect(),
+            Err(_) => Vec::new(),
+        };
+        let mut loose: Vec<&str> = affected
+            .iter()
+            .copied()
+            .filter(|s| !self.graph.contains(s))
+            .collect();
+        loose.sort();
+        order.extend(loose);
+
+        let mut updated: HashMap<String, Option<ScoreReport>> = HashMap::new();
+        let mut results = Vec::new();
+        for subject in order {
+            let records: Vec<&Record> = self
+                .files
+                .values()
+                .flat_map(|qf| qf.records.iter())
+                .filter(|r| r.subject() == subject && !matches!(r, Record::Policy(_)))
+                .collect();
+            if records.is_empty() && !self.graph.contains(subject) {
+                updated.insert(subject.to_string(), None);
+                results.push((subject.to_string(), None));
+                continue;
+            }
+
+            let raw = scoring::raw_score_from_refs(&records, &self.eval);
+            let mut effective = raw;
+            let mut limiting_path: Option<Vec<String>> = None;
+            for dep in self.graph.dependencies(subject) {
+                let dep_report = match updated.get(dep) {
+                    Some(report) => report.as_ref(),
+                    None => self.reports.get(dep),
+                };
+                let dep_eff = dep_report.map_or(0, |r| r.effective);
+                if dep_eff < effective {
+                    effective = dep_eff;
+                    let mut path = vec![dep.to_string()];
+                    if let Some(dep_path) = dep_report.and_then(|r| r.limiting_path.as_ref()) {
+                        path.extend(dep_path.iter().cloned());
+                    }
+                    limiting_path = Some(path);
+                }
+            }
+
+            let report = ScoreReport {
+                raw,
+                effective,
+                limiting_path,
+            };
+            updated.insert(subject.to_string(), Some(report.clone()));
+            results.push((subject.to_string(), Some(report)));
+        }
+        results
+    }
+
+    /// Deltas between `old` and the current reports for `subjects`.
+    fn diff(
+        &self,
+        old: &HashMap<String, ScoreReport>,
+        subjects: HashSet<String>,
+    ) -> Vec<ScoreDelta> {
+        let mut subjects: Vec<String> = subjects.into_iter().collect();
+        subjects.sort();
+        subjects
+            .into_iter()
+            .filter_map(|subject| {
+                let before = old.get(&subject).cloned();
+                let after = self.reports.get(&subject).cloned();
+                let same = match (&before, &after) {
+                    (Some(b), Some(a)) => {
+                        b.raw == a.raw
+                            && b.effective == a.effective
+                            && b.limiting_path == a.limiting_path
+                    }
+                    (None, None) => true,
+                    _ => false,
+                };
+                (!same).then_some(ScoreDelta {
+                    subject,
+                    before,
+                    after,
+                })
+            })
+            .collect()
+    }
+}
+
+/// IDs of the records in `records` that can change the graph: dependency
+/// records and the revocations that may withdraw them.
+fn dependency_ids(records: &[Record]) -> HashSet<&str> {
+    records
+        .iter()
+        .filter(|r| matches!(r, Record::Dependency(_) | Record::Revocation(_)))
+        .map(|r| r.id())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attestation::{
+        self, Attestation, AttestationBody, DependencyBody, DependencyRecord, Kind, fixtures,
+    };
+    use chrono::Utc;
+
+    fn nominal() -> Evaluation {
+        Evaluation::at(
+            chrono::DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    fn make_record(subject: &str, score: i32, summary: &str) -> Record {
+        Record::Attestation(Box::new(attestation::finalize(Attestation {
+            metabox: "1".into(),
+            record_type: "attestation".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: chrono::DateTime::parse_from_rfc3339("2026-02-24T10:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            id: String::new(),
+            body: AttestationBody {
+                detail: None,
+                digest: None,
+                expires_at: None,
+                fingerprint: None,
+                kind: Kind::Concern,
+                r#ref: None,
+                score,
+                span: None,
+                suggested_fix: None,
+                summary: summary.into(),
+                supersedes: None,
+                symbol: None,
+                tags: vec![],
+            },
+        })))
+    }
+
+    fn make_dep(subject: &str, on: &str) -> Record {
+        attestation::finalize_record(Record::Dependency(DependencyRecord {
+            metabox: "1".into(),
+            record_type: "dependency".into(),
+            subject: subject.into(),
+            issuer: "mailto:test@test.com".into(),
+            issuer_type: None,
+            created_at: nominal().at,
+            id: String::new(),
+            body: DependencyBody {
+                depends_on: vec![on.into()],
+            },
+        }))
+    }
+
+    fn write(dir: &Path, name: &str, records: &[Record]) -> PathBuf {
+        let path = dir.join(name);
+        qual_file::write_all(&path, records).unwrap();
+        path
+    }
+
+    /// The incremental state must always match a from-scratch computation.
+    fn assert_matches_full(state: &IncrementalScores, dir: &Path) {
+        let files = qual_file::discover(dir, false).unwrap();
+        let graph = graph::build(None, &files).unwrap();
+        let full = scoring::effective_scores(&graph, &files, &nominal());
+        assert_eq!(state.reports().len(), full.len());
+        for (subject, report) in &full {
+            let got = &state.reports()[subject];
+            assert_eq!(got.raw, report.raw, "{subject} raw");
+            assert_eq!(got.effective, report.effective, "{subject} effective");
+            assert_eq!(got.limiting_path, report.limiting_path, "{subject} path");
+        }
+    }
+
+    #[test]
+    fn test_update_propagates_to_dependents() {
+        let dir = tempfile::tempdir().unwrap();
+        let crypto = write(
+            dir.path(),
+            "crypto.qual",
+            &[make_record("crypto", 40, "ok")],
+        );
+        write(
+            dir.path(),
+            "auth.qual",
+            &[make_record("auth", 60, "good"), make_dep("auth", "crypto")],
+        );
+        let files = qual_file::discover(dir.path(), false).unwrap();
+        let mut state = IncrementalScores::new(files, None, nominal()).unwrap();
+        assert_eq!(state.reports()["auth"].effective, 40);
+
+        write(
+            dir.path(),
+            "crypto.qual",
+            &[
+                make_record("crypto", 40, "ok"),
+                make_record("crypto", -60, "timing leak"),
+            ],
+        );
+        let deltas = state.update(&[crypto]).unwrap();
+
+        let subjects: Vec<&str> = deltas.iter().map(|d| d.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["auth", "crypto"]);
+        let auth = deltas[0].after.as_ref().unwrap();
+        assert_eq!(auth.raw, 60);
+        assert_eq!(auth.effective, -20);
+        assert_eq!(auth.limiting_path, Some(vec!["crypto".to_string()]));
+        assert_eq!(deltas[0].before.as_ref().unwrap().effective, 40);
+        assert_matches_full(&state, dir.path());
+    }
+
+    #[test]
+    fn test_update_handles_added_removed_and_rewired_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = write(dir.path(), "a.qual", &[make_record("a", 50, "fine")]);
+        let files = qual_file::discover(dir.path(), false).unwrap();
+        let mut state = IncrementalScores::new(files, None, nominal()).unwrap();
+
+        // A new file with a dependency edge rebuilds the graph.
+        let b = write(
+            dir.path(),
+            "b.qual",
+            &[make_record("b", -10, "meh"), make_dep("a", "b")],
+        );
+        let deltas = state.update(std::slice::from_ref(&b)).unwrap();
+        assert_eq!(deltas.len(), 2);
+        assert!(deltas[1].before.is_none());
+        assert_eq!(state.reports()["a"].effective, -10);
+        assert!(state.is_tracked(&b));
+        assert_matches_full(&state, dir.path());
+
+        // Unchanged content produces no deltas.
+        assert!(state.update(std::slice::from_ref(&a)).unwrap().is_empty());
+
+        std::fs::remove_file(&b).unwrap();
+        let deltas = state.update(std::slice::from_ref(&b)).unwrap();
+        assert!(deltas.iter().any(|d| d.subject == "b" && d.after.is_none()));
+        assert_eq!(state.reports()["a"].effective, 50);
+        assert_eq!(state.file_count(), 1);
+        assert_matches_full(&state, dir.path());
+    }
+
+    #[test]
+    fn test_update_parse_error_leaves_state_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = write(dir.path(), "a.qual", &[make_record("a", 50, "fine")]);
+        let files = qual_file::discover(dir.path(), false).unwrap();
+        let mut state = IncrementalScores::new(files, None, nominal()).unwrap();
+
+        std::fs::write(&a, "{not json\n").unwrap();
+        assert!(state.update(std::slice::from_ref(&a)).is_err());
+        assert_eq!(state.reports()["a"].raw, 50);
+    }
+
+    #[test]
+    fn test_update_with_a_later_evaluation_lapses_expired_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let waiver = fixtures::record(
+            "api",
+            AttestationBody {
+                kind: Kind::Waiver,
+                score: 30,
+                summary: "until the release".into(),
+                expires_at: Some(nominal().at + chrono::Duration::days(7)),
+                ..Default::default()
+            },
+        );
+        write(dir.path(), "api.qual", &[waiver]);
+        let other = write(dir.path(), "db.qual", &[make_record("db", 10, "ok")]);
+        let files = qual_file::discover(dir.path(), false).unwrap();
+        let mut state = IncrementalScores::new(files, None, nominal()).unwrap();
+        assert_eq!(state.reports()["api"].raw, 30);
+
+        // Only db.qual changed, but by now the waiver on api has expired.
+        let later = Evaluation::at(nominal().at + chrono::Duration::days(8));
+        let deltas = state
+            .update_with(std::slice::from_ref(&other), |_| Ok(later))
+            .unwrap();
+        let subjects: Vec<&str> = deltas.iter().map(|d| d.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["api"]);
+        assert_eq!(state.reports()["api"].raw, 0);
+    }
+}
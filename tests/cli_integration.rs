@@ -258,6 +258,147 @@ fn test_check_passes_empty_project() {
     assert_eq!(code, 0, "check on empty project should pass (no artifacts)");
 }
 
+#[test]
+fn test_dimension_scores_and_thresholds() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    for (subject, score, tag) in [
+        ("lib.rs", "-30", "security"),
+        ("lib.rs", "50", "perf"),
+        ("app.rs", "40", "security"),
+    ] {
+        let (_, stderr, code) = run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                subject,
+                "--kind",
+                "pass",
+                "--score",
+                score,
+                "--summary",
+                "signal",
+                "--tag",
+                tag,
+                "--issuer",
+                "mailto:test@test.com",
+            ],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+    run_qualifier(dir.path(), &["dep", "add", "app.rs", "--on", "lib.rs"]);
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["score", "--format", "json"]);
+    assert_eq!(code, 0);
+    let overall: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let lib = overall
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["subject"] == "lib.rs")
+        .unwrap();
+    assert_eq!(lib["raw_score"], 20);
+
+    let (stdout, _, code) = run_qualifier(
+        dir.path(),
+        &["score", "--dimension", "security", "--format", "json"],
+    );
+    assert_eq!(code, 0);
+    let security: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let app = security
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["subject"] == "app.rs")
+        .unwrap();
+    assert_eq!(app["raw_score"], 40);
+    assert_eq!(app["effective_score"], -30);
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["check"]);
+    assert_eq!(code, 0, "overall scores pass: {stdout}");
+
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "[dimensions.security]\nmin_score = 0\n",
+    )
+    .unwrap();
+    let (_, stderr, code) = run_qualifier(dir.path(), &["check"]);
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("FAIL: app.rs [security] effective=-30 raw=40 (limited by lib.rs)"),
+        "got: {stderr}"
+    );
+    assert!(
+        stderr.contains("2 artifact(s) below minimum security score of 0"),
+        "got: {stderr}"
+    );
+
+    let (stdout, stderr, code) =
+        run_qualifier(dir.path(), &["check", "--dimension", "security=-50"]);
+    assert_eq!(code, 0, "flag overrides config: {stderr}");
+    assert!(stdout.contains("(security >= -50)"), "got: {stdout}");
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["check", "--dimension", "security"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("TAG=MIN"), "got: {stderr}");
+}
+
+#[test]
+fn test_lenient_parsing_and_check_reports_every_malformed_line() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--kind",
+            "praise",
+            "--score",
+            "30",
+            "--summary",
+            "clean",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "attest failed: {stderr}");
+    let qual = dir.path().join(".qual");
+    let mut content = std::fs::read_to_string(&qual).unwrap();
+    content.push_str("<<<<<<< HEAD\n");
+    std::fs::write(&qual, content).unwrap();
+    std::fs::create_dir(dir.path().join("lib")).unwrap();
+    std::fs::write(dir.path().join("lib/.qual"), "{\"metabox\":\"1\"}\n").unwrap();
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["score"]);
+    assert_ne!(code, 0, "strict by default");
+    assert!(stderr.contains(".qual:2"), "got: {stderr}");
+
+    let (stdout, stderr, code) =
+        run_qualifier(dir.path(), &["score", "--lenient", "--format", "json"]);
+    assert_eq!(code, 0, "lenient score failed: {stderr}");
+    assert!(stderr.contains("warning:"), "got: {stderr}");
+    assert!(
+        stderr.contains("skipped 2 malformed line(s)"),
+        "got: {stderr}"
+    );
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed[0]["raw_score"], 30);
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["check"]);
+    assert_ne!(code, 0);
+    assert_eq!(stderr.matches("MALFORMED:").count(), 2, "got: {stderr}");
+    assert!(stderr.contains(".qual:2:1: syntax error"), "got: {stderr}");
+    assert!(stderr.contains("data error"), "got: {stderr}");
+    assert!(
+        stderr.contains("2 malformed line(s) in .qual files"),
+        "got: {stderr}"
+    );
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["check", "--lenient"]);
+    assert_eq!(code, 0, "lenient check failed: {stderr}");
+}
+
 // --- qualifier attest --kind blocker uses default score ---
 
 #[test]
@@ -375,6 +516,281 @@ fn test_show_json_output() {
     assert_eq!(parsed["records"].as_array().unwrap().len(), 1);
 }
 
+// --- decay ---
+
+#[test]
+fn test_show_reports_decayed_contribution() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "[decay.praise]\nhalf_life_days = 180\n",
+    )
+    .unwrap();
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--kind",
+            "praise",
+            "--score",
+            "40",
+            "--summary",
+            "clean API",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "attest failed: {stderr}");
+
+    // Far enough ahead that the record is at least one half-life old.
+    let at = (chrono::Utc::now() + chrono::Duration::days(180)).to_rfc3339();
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &["show", "api.rs", "--format", "json", "--at", &at],
+    );
+    assert_eq!(code, 0, "show failed: {stderr}");
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["raw_score"], 20);
+    assert_eq!(parsed["contributions"][0]["nominal"], 40);
+    assert_eq!(parsed["contributions"][0]["decayed"], 20);
+
+    let (stdout, _, _) = run_qualifier(dir.path(), &["show", "api.rs", "--at", &at]);
+    assert!(stdout.contains("(decayed to 20)"), "got: {stdout}");
+
+    // Without --at the record is fresh.
+    let (stdout, _, _) = run_qualifier(dir.path(), &["score", "--format", "json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed[0]["raw_score"], 40);
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["score", "--at", "yesterday"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--at"), "got: {stderr}");
+}
+
+// --- custom kinds ---
+
+#[test]
+fn test_declared_kinds_set_defaults_and_rules() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "[kinds.security-review]\ndefault_score = 20\nrange = [0, 50]\n\n[kinds.flaky]\ndefault_score = -15\nsign = \"negative\"\n",
+    )
+    .unwrap();
+    let attest = |kind: &str, score: Option<&str>| {
+        let mut args = vec![
+            "attest",
+            "api.rs",
+            "--kind",
+            kind,
+            "--summary",
+            "signal",
+            "--issuer",
+            "mailto:test@test.com",
+        ];
+        if let Some(score) = score {
+            args.extend(["--score", score]);
+        }
+        run_qualifier(dir.path(), &args)
+    };
+
+    let (stdout, stderr, code) = attest("security-review", None);
+    assert_eq!(code, 0, "attest failed: {stderr}");
+    assert!(stdout.contains("[20] security-review"), "got: {stdout}");
+
+    let (_, stderr, code) = attest("flaky", Some("5"));
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("requires a negative score"),
+        "got: {stderr}"
+    );
+
+    let (_, stderr, code) = attest("security-review", Some("80"));
+    assert_ne!(code, 0);
+    assert!(stderr.contains("out of range [0, 50]"), "got: {stderr}");
+
+    let (_, stderr, code) = attest("security-reveiw", None);
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("did you mean 'security-review'?"),
+        "got: {stderr}"
+    );
+
+    let (_, stderr, code) = attest("perf-regression", Some("-10"));
+    assert_eq!(code, 0, "attest failed: {stderr}");
+    assert!(
+        stderr.contains("warning: kind 'perf-regression' is not declared"),
+        "got: {stderr}"
+    );
+
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "strict_kinds = true\n\n[kinds.flaky]\ndefault_score = -15\nsign = \"negative\"\n",
+    )
+    .unwrap();
+    let (_, stderr, code) = attest("perf-regression", Some("-10"));
+    assert_ne!(code, 0);
+    assert!(stderr.contains("strict_kinds"), "got: {stderr}");
+
+    let line = serde_json::json!({
+        "metabox": "1",
+        "id": "",
+        "subject": "api.rs",
+        "issuer": "mailto:test@test.com",
+        "created_at": "2026-01-01T00:00:00Z",
+        "body": {"kind": "flaky", "score": 10, "summary": "flaky test"}
+    });
+    let output = std::process::Command::new(qualifier_bin())
+        .args(["attest", "--stdin"])
+        .current_dir(dir.path())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            if let Some(ref mut stdin) = child.stdin {
+                writeln!(stdin, "{line}").ok();
+            }
+            child.wait_with_output()
+        })
+        .expect("failed to run batch mode");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("requires a negative score"),
+        "batch mode should apply declared kinds"
+    );
+}
+
+// --- trust ---
+
+#[test]
+fn test_trust_weights_issuers_and_allowlists() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "[trust.issuer_types]\nai = 25\n\n[[trust.subjects]]\npattern = \"crypto/**\"\nallow = [\"mailto:lead@test.com\"]\n",
+    )
+    .unwrap();
+
+    for (subject, issuer, issuer_type, kind, score) in [
+        ("api.rs", "mailto:bot@test.com", "ai", "praise", "40"),
+        ("api.rs", "mailto:lead@test.com", "human", "concern", "-20"),
+        (
+            "crypto/aes.rs",
+            "mailto:bot@test.com",
+            "human",
+            "praise",
+            "30",
+        ),
+    ] {
+        let (_, stderr, code) = run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                subject,
+                "--kind",
+                kind,
+                "--score",
+                score,
+                "--summary",
+                "signal",
+                "--issuer",
+                issuer,
+                "--issuer-type",
+                issuer_type,
+            ],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["show", "api.rs", "--format", "json"]);
+    assert_eq!(code, 0, "show failed: {stderr}");
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["raw_score"], -10);
+    let praise = parsed["contributions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["nominal"] == 40)
+        .unwrap();
+    assert_eq!(praise["decayed"], 10);
+    assert_eq!(praise["weight"], 25);
+    assert_eq!(praise["weight_reasons"][0], "issuer_type ai: 25%");
+
+    let (stdout, _, _) = run_qualifier(dir.path(), &["show", "crypto/aes.rs"]);
+    assert!(stdout.contains("Raw score:       0"), "got: {stdout}");
+    assert!(
+        stdout.contains("(weighted 0% to 0: crypto/**: issuer not allowed)"),
+        "got: {stdout}"
+    );
+
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "[trust]\ndefault = 5000\n",
+    )
+    .unwrap();
+    let (_, stderr, code) = run_qualifier(dir.path(), &["score"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("trust weight 5000"), "got: {stderr}");
+}
+
+#[test]
+fn test_snapshot_keeps_untrusted_issuers_weighted() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "[[trust.subjects]]\npattern = \"src/crypto/**\"\nallow = [\"mailto:maint@test.com\"]\n",
+    )
+    .unwrap();
+
+    for (issuer, kind, score) in [
+        ("mailto:bot@test.com", "praise", "50"),
+        ("mailto:maint@test.com", "concern", "-10"),
+    ] {
+        let (_, stderr, code) = run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                "src/crypto/aes.rs",
+                "--kind",
+                kind,
+                "--score",
+                score,
+                "--summary",
+                "signal",
+                "--issuer",
+                issuer,
+            ],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+
+    let score = |dir: &Path| {
+        let (stdout, stderr, code) =
+            run_qualifier(dir, &["show", "src/crypto/aes.rs", "--format", "json"]);
+        assert_eq!(code, 0, "show failed: {stderr}");
+        let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        parsed["raw_score"].clone()
+    };
+    assert_eq!(score(dir.path()), -10);
+
+    let (_, stderr, code) =
+        run_qualifier(dir.path(), &["compact", "src/crypto/aes.rs", "--snapshot"]);
+    assert_eq!(code, 0, "compact failed: {stderr}");
+    assert_eq!(score(dir.path()), -10);
+
+    // The bot's praise keeps its own line, still weighted to zero.
+    let contents = std::fs::read_to_string(dir.path().join("src/crypto/.qual")).unwrap();
+    assert!(contents.contains("mailto:bot@test.com"), "got: {contents}");
+}
+
 // --- qualifier show nonexistent artifact ---
 
 #[test]
@@ -1016,6 +1432,116 @@ fn test_attest_batch_validates() {
     );
 }
 
+#[test]
+fn test_concurrent_batch_attests_and_compaction_keep_every_record() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    let (stdout, _, _) = run_qualifier(
+        dir.path(),
+        &["attest", "old.rs", "--kind", "pass", "--summary", "first"],
+    );
+    let id = stdout.split("id: ").nth(1).unwrap().trim().to_string();
+    run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "old.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "second",
+            "--supersedes",
+            &id,
+        ],
+    );
+
+    let batches: Vec<_> = (0..6)
+        .map(|t| {
+            let input: String = (0..20)
+                .map(|i| {
+                    let line = serde_json::json!({
+                        "metabox": "1",
+                        "id": "",
+                        "subject": format!("a{t}.rs"),
+                        "issuer": "mailto:agent@test.com",
+                        "created_at": "2026-01-01T00:00:00Z",
+                        "body": {"kind": "pass", "score": 1, "summary": format!("batch {t} #{i}")}
+                    });
+                    format!("{line}\n")
+                })
+                .collect();
+            let mut child = std::process::Command::new(qualifier_bin())
+                .args(["attest", "--stdin"])
+                .current_dir(dir.path())
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped())
+                .spawn()
+                .unwrap();
+            let mut stdin = child.stdin.take().unwrap();
+            std::thread::spawn(move || {
+                use std::io::Write;
+                stdin.write_all(input.as_bytes()).unwrap();
+            });
+            child
+        })
+        .collect();
+    let (_, stderr, code) = run_qualifier(dir.path(), &["compact", "--all"]);
+    assert_eq!(code, 0, "compact failed: {stderr}");
+    for child in batches {
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success());
+    }
+
+    // 120 batch records plus the superseding one; only the superseded
+    // record may be gone.
+    let content = std::fs::read_to_string(dir.path().join(".qual")).unwrap();
+    assert_eq!(content.lines().count(), 121, "got:\n{content}");
+    assert!(!content.contains("\"first\""));
+}
+
+#[test]
+fn test_compact_backup_keeps_previous_file() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    let (stdout, _, _) = run_qualifier(
+        dir.path(),
+        &["attest", "a.rs", "--kind", "pass", "--summary", "first"],
+    );
+    let id = stdout.split("id: ").nth(1).unwrap().trim().to_string();
+    run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "a.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "second",
+            "--supersedes",
+            &id,
+        ],
+    );
+    let qual_path = dir.path().join(".qual");
+    let original = std::fs::read_to_string(&qual_path).unwrap();
+
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["compact", "a.rs", "--backup"]);
+    assert_eq!(code, 0, "compact failed: {stderr}");
+    assert!(stdout.contains(".qual.bak"), "got: {stdout}");
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join(".qual.bak")).unwrap(),
+        original
+    );
+    assert_eq!(
+        std::fs::read_to_string(&qual_path).unwrap().lines().count(),
+        1
+    );
+
+    // The backup is not a .qual file, so it does not count toward scores.
+    let (stdout, _, _) = run_qualifier(dir.path(), &["ls"]);
+    assert_eq!(stdout.matches("a.rs").count(), 1, "got: {stdout}");
+}
+
 // --- metabox format tests ---
 
 #[test]
@@ -1219,3 +1745,2069 @@ fn test_attest_with_span_and_columns() {
         "span should contain end col: {content}"
     );
 }
+
+#[test]
+fn test_score_spans_reports_regions() {
+    let dir = tempfile::tempdir().unwrap();
+
+    for (score, span, summary) in [
+        ("--score=-20", Some("50:60"), "unwrap on input"),
+        ("--score=-30", Some("40:52"), "unbounded recursion"),
+        ("--score=40", None, "well tested"),
+    ] {
+        let mut args = vec![
+            "attest",
+            "parser.rs",
+            "--kind",
+            "concern",
+            score,
+            "--summary",
+            summary,
+            "--issuer",
+            "mailto:test@test.com",
+        ];
+        if let Some(span) = span {
+            args.extend(["--span", span]);
+        }
+        let (_, stderr, code) = run_qualifier(dir.path(), &args);
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+
+    let (stdout, _, code) = run_qualifier(
+        dir.path(),
+        &["score", "parser.rs", "--spans", "--format", "json"],
+    );
+    assert_eq!(code, 0);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed[0]["raw_score"], -10);
+    let spans = parsed[0]["spans"].as_array().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0]["start_line"], 40);
+    assert_eq!(spans[0]["end_line"], 60);
+    assert_eq!(spans[0]["raw_score"], -50);
+    assert_eq!(spans[0]["records"].as_array().unwrap().len(), 2);
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["score", "parser.rs", "--spans"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("L40\u{2013}60"), "got: {stdout}");
+
+    // Without --spans the JSON shape is unchanged.
+    let (stdout, _, _) = run_qualifier(dir.path(), &["score", "--format", "json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(parsed[0].get("spans").is_none());
+}
+
+// --- dependency records in .qual files ---
+
+#[test]
+fn test_score_uses_dependency_records_from_qual_files() {
+    let dir = tempfile::tempdir().unwrap();
+
+    for (subject, kind, score) in [("app", "praise", "80"), ("lib", "blocker", "-50")] {
+        run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                subject,
+                "--kind",
+                kind,
+                "--score",
+                score,
+                "--summary",
+                "x",
+                "--issuer",
+                "mailto:test@test.com",
+            ],
+        );
+    }
+
+    // Declare app -> lib as a dependency record instead of in the graph file.
+    let dep = serde_json::json!({
+        "metabox": "1",
+        "type": "dependency",
+        "subject": "app",
+        "issuer": "https://build.example.com",
+        "created_at": "2026-02-25T10:00:00Z",
+        "id": "",
+        "body": {"depends_on": ["lib"]}
+    });
+    let qual_path = dir.path().join(".qual");
+    let mut content = std::fs::read_to_string(&qual_path).unwrap();
+    content.push_str(&format!("{dep}\n"));
+    std::fs::write(&qual_path, content).unwrap();
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["score", "--format", "json"]);
+    assert_eq!(code, 0);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let app = parsed
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["subject"] == "app")
+        .unwrap();
+    assert_eq!(app["raw_score"], 80);
+    assert_eq!(app["effective_score"], -50);
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["graph", "--format", "json"]);
+    assert_eq!(code, 0, "graph should work from dependency records alone");
+    assert!(stdout.contains("\"depends_on\":[\"lib\"]"));
+
+    let (_, _, code) = run_qualifier(dir.path(), &["check", "--min-score", "0"]);
+    assert_ne!(code, 0, "check should see the limiting dependency");
+}
+
+#[test]
+fn test_score_rejects_cycle_across_graph_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("qualifier.graph.jsonl"),
+        "{\"subject\":\"a\",\"depends_on\":[\"b\"]}\n",
+    )
+    .unwrap();
+    let dep = serde_json::json!({
+        "metabox": "1",
+        "type": "dependency",
+        "subject": "b",
+        "issuer": "https://build.example.com",
+        "created_at": "2026-02-25T10:00:00Z",
+        "id": "",
+        "body": {"depends_on": ["a"]}
+    });
+    std::fs::write(dir.path().join(".qual"), format!("{dep}\n")).unwrap();
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["score"]);
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("cycle"),
+        "should report the cycle: {stderr}"
+    );
+}
+
+// --- qualifier dep ---
+
+#[test]
+fn test_dep_add_ls_rm() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "dep",
+            "add",
+            "bin/server",
+            "--on",
+            "lib/auth",
+            "--on",
+            "lib/http",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "dep add should succeed: {stderr}");
+    assert!(stdout.contains("bin/server -> lib/auth, lib/http"));
+
+    // Written with the same layout resolution as attest, with a valid id.
+    let content = std::fs::read_to_string(dir.path().join("bin/.qual")).unwrap();
+    let record: qualifier::attestation::Record =
+        serde_json::from_str(content.lines().next().unwrap()).unwrap();
+    assert_eq!(
+        record.id(),
+        qualifier::attestation::generate_record_id(&record)
+    );
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["dep", "ls", "--format", "json"]);
+    assert_eq!(code, 0);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed[0]["subject"], "bin/server");
+    assert_eq!(parsed[0]["depends_on"].as_array().unwrap().len(), 2);
+
+    let (_, _, code) = run_qualifier(dir.path(), &["dep", "rm", "bin/server", "--on", "lib/http"]);
+    assert_eq!(code, 0);
+    let (stdout, _, _) = run_qualifier(dir.path(), &["dep", "ls", "bin/server"]);
+    assert!(stdout.contains("bin/server -> lib/auth"));
+    assert!(!stdout.contains("lib/http"));
+
+    // The original record is revoked, not rewritten, so its ID still resolves.
+    let after = std::fs::read_to_string(dir.path().join("bin/.qual")).unwrap();
+    assert!(after.starts_with(&content));
+    let revocation: qualifier::attestation::Record =
+        serde_json::from_str(after.lines().last().unwrap()).unwrap();
+    assert_eq!(revocation.revokes(), [record.id().to_string()]);
+
+    let (_, _, code) = run_qualifier(dir.path(), &["dep", "rm", "bin/server"]);
+    assert_eq!(code, 0);
+    let (stdout, _, _) = run_qualifier(dir.path(), &["dep", "ls"]);
+    assert!(stdout.contains("No dependency records"));
+}
+
+#[test]
+fn test_dep_add_refuses_cycle() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("qualifier.graph.jsonl"),
+        "{\"subject\":\"lib\",\"depends_on\":[\"app\"]}\n",
+    )
+    .unwrap();
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "dep",
+            "add",
+            "app",
+            "--on",
+            "lib",
+            "--issuer",
+            "mailto:t@t.com",
+        ],
+    );
+    assert_ne!(code, 0, "cyclic edge should be refused");
+    assert!(stderr.contains("cycle"), "should mention cycle: {stderr}");
+    assert!(
+        !dir.path().join(".qual").exists(),
+        "nothing should be written"
+    );
+}
+
+// --- layered configuration ---
+
+#[test]
+fn test_check_uses_project_config_min_score() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(dir.path().join(".qualifier.toml"), "min_score = 50\n").unwrap();
+
+    run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "a.rs",
+            "--kind",
+            "pass",
+            "--score",
+            "20",
+            "--summary",
+            "ok",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["check"]);
+    assert_ne!(code, 0, "20 is below the configured 50: {stderr}");
+
+    // CLI flag overrides the project config.
+    let (_, _, code) = run_qualifier(dir.path(), &["check", "--min-score", "10"]);
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn test_env_and_project_config_apply_to_format_and_issuer() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "issuer = \"mailto:config@example.com\"\n",
+    )
+    .unwrap();
+
+    run_qualifier(
+        dir.path(),
+        &["attest", "a.rs", "--kind", "pass", "--summary", "ok"],
+    );
+    let content = std::fs::read_to_string(dir.path().join(".qual")).unwrap();
+    assert!(content.contains("mailto:config@example.com"));
+
+    let output = Command::new(qualifier_bin())
+        .args(["score"])
+        .env("QUALIFIER_FORMAT", "json")
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed[0]["subject"], "a.rs");
+}
+
+#[test]
+fn test_config_show_reports_layers() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(dir.path().join(".qualifier.toml"), "min_score = 30\n").unwrap();
+
+    let output = Command::new(qualifier_bin())
+        .args(["config", "show", "--format", "json"])
+        .env("QUALIFIER_ISSUER", "mailto:env@example.com")
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let parsed: serde_json::Value =
+        serde_json::from_str(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    let entry = |key: &str| {
+        parsed
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["key"] == key)
+            .unwrap()
+            .clone()
+    };
+
+    assert_eq!(entry("min_score")["value"], 30);
+    assert!(
+        entry("min_score")["source"]
+            .as_str()
+            .unwrap()
+            .starts_with("project")
+    );
+    assert_eq!(entry("issuer")["value"], "mailto:env@example.com");
+    assert_eq!(entry("issuer")["source"], "env");
+    assert_eq!(entry("format")["source"], "cli");
+    assert_eq!(entry("graph")["source"], "default");
+}
+
+// --- policy records ---
+
+#[test]
+fn test_check_enforces_policy_records() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "src/a.rs",
+            "--kind",
+            "blocker",
+            "--score",
+            "10",
+            "--summary",
+            "still broken",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+
+    let (_, _, code) = run_qualifier(dir.path(), &["check"]);
+    assert_eq!(code, 0, "no policy yet, score is above 0");
+
+    let policy = serde_json::json!({
+        "type": "policy",
+        "subject": "src/",
+        "issuer": "mailto:lead@test.com",
+        "created_at": "2026-02-25T10:00:00Z",
+        "id": "",
+        "body": {
+            "rules": [{"subjects": "src/**", "forbid": ["blocker"]}],
+            "summary": "no active blockers in src"
+        }
+    });
+    let output = Command::new(qualifier_bin())
+        .args(["attest", "--stdin"])
+        .current_dir(dir.path())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            if let Some(ref mut stdin) = child.stdin {
+                writeln!(stdin, "{}", policy).ok();
+            }
+            child.wait_with_output()
+        })
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["check"]);
+    assert_ne!(code, 0, "policy should fail the gate");
+    assert!(stderr.contains("POLICY: src/a.rs: forbidden kind 'blocker'"));
+    assert!(stderr.contains("1 policy violation(s)"));
+}
+
+#[test]
+fn test_check_policy_covers_files_without_records() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/a.rs"), "fn a() {}\n").unwrap();
+    std::fs::write(dir.path().join("src/b.rs"), "fn b() {}\n").unwrap();
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "src/a.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "reviewed",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "attest failed: {stderr}");
+
+    let policy = serde_json::json!({
+        "type": "policy",
+        "subject": "src/",
+        "issuer": "mailto:lead@test.com",
+        "created_at": "2026-02-25T10:00:00Z",
+        "id": "",
+        "body": {
+            "rules": [{"subjects": "src/**", "require": [{"kind": "pass"}]}],
+            "summary": "every source file is reviewed"
+        }
+    });
+    let output = Command::new(qualifier_bin())
+        .args(["attest", "--stdin"])
+        .current_dir(dir.path())
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            if let Some(ref mut stdin) = child.stdin {
+                writeln!(stdin, "{}", policy).ok();
+            }
+            child.wait_with_output()
+        })
+        .unwrap();
+    assert!(output.status.success());
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["check"]);
+    assert_ne!(code, 0, "the unreviewed file should fail the gate");
+    assert!(stderr.contains("POLICY: src/b.rs: requires 1 active 'pass'"));
+    assert!(stderr.contains("1 policy violation(s)"), "got: {stderr}");
+
+    // Files qualifier writes itself, like compaction backups, are not
+    // artifacts the policy covers.
+    let mut supersedes: Option<String> = None;
+    for summary in ["reviewed", "reviewed again"] {
+        let mut args = vec![
+            "attest",
+            "src/b.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            summary,
+            "--issuer",
+            "mailto:test@test.com",
+        ];
+        if let Some(id) = &supersedes {
+            args.extend(["--supersedes", id.as_str()]);
+        }
+        let (stdout, stderr, code) = run_qualifier(dir.path(), &args);
+        assert_eq!(code, 0, "attest failed: {stderr}");
+        supersedes = Some(stdout.split("id: ").nth(1).unwrap().trim().to_string());
+    }
+    let (_, stderr, code) = run_qualifier(dir.path(), &["compact", "src/b.rs", "--backup"]);
+    assert_eq!(code, 0, "compact failed: {stderr}");
+    assert!(dir.path().join("src/.qual.bak").exists());
+    let (_, stderr, code) = run_qualifier(dir.path(), &["check"]);
+    assert_eq!(code, 0, "check failed: {stderr}");
+}
+
+// --- qualifier keygen / sign / verify ---
+
+#[test]
+fn test_sign_and_verify_records() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    let key = dir.path().join("alice.key");
+    let key = key.to_str().unwrap();
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "keygen",
+            "--output",
+            key,
+            "--issuer",
+            "mailto:alice@example.com",
+            "--register",
+        ],
+    );
+    assert_eq!(code, 0, "keygen failed: {stderr}");
+    let keyring = std::fs::read_to_string(dir.path().join("qualifier.keys.jsonl")).unwrap();
+    assert!(keyring.contains("mailto:alice@example.com"));
+
+    // A second keygen refuses to clobber the key.
+    let (_, _, code) = run_qualifier(
+        dir.path(),
+        &[
+            "keygen",
+            "--output",
+            key,
+            "--issuer",
+            "mailto:alice@example.com",
+        ],
+    );
+    assert_ne!(code, 0);
+
+    for (issuer, summary) in [
+        ("mailto:alice@example.com", "genuine"),
+        ("mailto:mallory@example.com", "unsigned"),
+    ] {
+        run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                "a.rs",
+                "--kind",
+                "pass",
+                "--summary",
+                summary,
+                "--issuer",
+                issuer,
+            ],
+        );
+    }
+
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &["sign", "--key", key, "--issuer", "mailto:alice@example.com"],
+    );
+    assert_eq!(code, 0, "sign failed: {stderr}");
+    assert!(stdout.contains("Signed 1 record(s)"));
+    assert!(dir.path().join(".qual.sig").exists());
+
+    // Re-signing is idempotent.
+    let (stdout, _, _) = run_qualifier(
+        dir.path(),
+        &["sign", "--key", key, "--issuer", "mailto:alice@example.com"],
+    );
+    assert!(stdout.contains("Signed 0 record(s)"));
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["verify"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("1 verified, 1 unsigned"));
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["verify", "--strict"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("UNSIGNED"));
+
+    // Tampering with a signed record breaks verification.
+    let qual = dir.path().join(".qual");
+    let content = std::fs::read_to_string(&qual).unwrap();
+    std::fs::write(&qual, content.replace("genuine", "forged")).unwrap();
+    let (_, stderr, code) = run_qualifier(dir.path(), &["verify"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("INVALID"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_keygen_force_makes_existing_key_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let key = dir.path().join("alice.key");
+    std::fs::write(&key, "old\n").unwrap();
+    std::fs::set_permissions(&key, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "keygen",
+            "--output",
+            key.to_str().unwrap(),
+            "--issuer",
+            "mailto:alice@example.com",
+            "--force",
+        ],
+    );
+    assert_eq!(code, 0, "keygen failed: {stderr}");
+    let mode = std::fs::metadata(&key).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+// --- qualifier export ---
+
+#[test]
+fn test_export_in_toto() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+    run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "a.rs",
+            "--kind",
+            "concern",
+            "--score",
+            "-10",
+            "--summary",
+            "slow",
+            "--issuer",
+            "mailto:alice@example.com",
+        ],
+    );
+    run_qualifier(dir.path(), &["dep", "add", "a.rs", "--on", "b.rs"]);
+    // A directory has no content digest, so its records are not exported.
+    std::fs::create_dir_all(dir.path().join("lib")).unwrap();
+    run_qualifier(dir.path(), &["dep", "add", "lib", "--on", "a.rs"]);
+
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["export", "--format", "in-toto"]);
+    assert_eq!(code, 0, "export failed: {stderr}");
+    assert!(stderr.contains("skipping"), "got: {stderr}");
+    assert!(stderr.contains("lib"), "got: {stderr}");
+
+    let statements: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(statements.len(), 2);
+    for statement in &statements {
+        for subject in statement["subject"].as_array().unwrap() {
+            let digest = subject["digest"].as_object().unwrap();
+            assert!(
+                digest
+                    .values()
+                    .any(|d| d.as_str().is_some_and(|d| !d.is_empty())),
+                "every exported subject has a digest: {statement}"
+            );
+        }
+    }
+
+    let att = statements
+        .iter()
+        .find(|s| s["predicateType"] == "https://qualifier.dev/attestation/v1")
+        .unwrap();
+    assert_eq!(att["_type"], "https://in-toto.io/Statement/v1");
+    assert_eq!(att["subject"][0]["name"], "a.rs");
+    assert_eq!(
+        att["subject"][0]["digest"]["blake3"],
+        blake3::hash(b"fn a() {}\n").to_hex().to_string()
+    );
+    assert_eq!(att["predicate"]["score"], -10);
+    assert_eq!(att["predicate"]["issuer"], "mailto:alice@example.com");
+    assert!(
+        statements
+            .iter()
+            .any(|s| s["predicateType"] == "https://qualifier.dev/dependency/v1")
+    );
+
+    let (_, _, code) = run_qualifier(dir.path(), &["export", "--format", "yaml"]);
+    assert_ne!(code, 0);
+}
+
+// --- qualifier import ---
+
+#[test]
+fn test_import_in_toto_roundtrip() {
+    let upstream = tempfile::tempdir().unwrap();
+    run_qualifier(upstream.path(), &["init"]);
+    std::fs::write(upstream.path().join("lib.rs"), "pub fn lib() {}\n").unwrap();
+    for summary in ["first", "second"] {
+        run_qualifier(
+            upstream.path(),
+            &[
+                "attest",
+                "lib.rs",
+                "--kind",
+                "pass",
+                "--summary",
+                summary,
+                "--issuer",
+                "mailto:up@example.com",
+            ],
+        );
+    }
+    let (statements, _, code) = run_qualifier(upstream.path(), &["export", "--format", "in-toto"]);
+    assert_eq!(code, 0);
+    let upstream_content = std::fs::read_to_string(upstream.path().join(".qual")).unwrap();
+
+    let downstream = tempfile::tempdir().unwrap();
+    run_qualifier(downstream.path(), &["init"]);
+    let input = downstream.path().join("upstream.intoto.jsonl");
+    std::fs::write(&input, &statements).unwrap();
+
+    let (stdout, stderr, code) =
+        run_qualifier(downstream.path(), &["import", "upstream.intoto.jsonl"]);
+    assert_eq!(code, 0, "import failed: {stderr}");
+    assert!(stdout.contains("Imported 2 record(s)"));
+    assert_eq!(
+        std::fs::read_to_string(downstream.path().join(".qual")).unwrap(),
+        upstream_content
+    );
+
+    // Importing again adds nothing.
+    let (stdout, _, _) = run_qualifier(downstream.path(), &["import", "upstream.intoto.jsonl"]);
+    assert!(stdout.contains("Imported 0 record(s) (2 already present"));
+
+    // A tampered predicate is rejected before anything is written.
+    std::fs::write(&input, statements.replace("\"second\"", "\"forged\"")).unwrap();
+    let (_, stderr, code) = run_qualifier(downstream.path(), &["import", "upstream.intoto.jsonl"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("does not match recomputed id"));
+}
+
+#[test]
+fn test_import_validates_whole_batch_before_writing() {
+    let downstream = tempfile::tempdir().unwrap();
+    run_qualifier(downstream.path(), &["init"]);
+    let (stdout, stderr, code) = run_qualifier(
+        downstream.path(),
+        &[
+            "attest",
+            "other.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "local",
+            "--issuer",
+            "mailto:down@example.com",
+        ],
+    );
+    assert_eq!(code, 0, "attest failed: {stderr}");
+    let local_id = stdout.split("id: ").nth(1).unwrap().trim().to_string();
+
+    // Upstream cannot see that the superseded id is about another subject.
+    let upstream = tempfile::tempdir().unwrap();
+    run_qualifier(upstream.path(), &["init"]);
+    std::fs::write(upstream.path().join("lib.rs"), "pub fn lib() {}\n").unwrap();
+    for extra in [vec![], vec!["--supersedes", local_id.as_str()]] {
+        let mut args = vec![
+            "attest",
+            "lib.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "upstream",
+            "--issuer",
+            "mailto:up@example.com",
+        ];
+        args.extend(extra);
+        let (_, stderr, code) = run_qualifier(upstream.path(), &args);
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+    let (statements, _, code) = run_qualifier(upstream.path(), &["export", "--format", "in-toto"]);
+    assert_eq!(code, 0);
+    std::fs::write(downstream.path().join("upstream.intoto.jsonl"), statements).unwrap();
+
+    let before = std::fs::read_to_string(downstream.path().join(".qual")).unwrap();
+    let (_, stderr, code) = run_qualifier(downstream.path(), &["import", "upstream.intoto.jsonl"]);
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("cross-subject supersession"),
+        "got: {stderr}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(downstream.path().join(".qual")).unwrap(),
+        before,
+        "a rejected batch must not be partly imported"
+    );
+}
+
+#[test]
+fn test_import_rejects_subjects_outside_the_project() {
+    let upstream = tempfile::tempdir().unwrap();
+    let up = upstream.path().join("up");
+    std::fs::create_dir_all(&up).unwrap();
+    run_qualifier(&up, &["init"]);
+    std::fs::create_dir_all(upstream.path().join("escaped")).unwrap();
+    std::fs::write(upstream.path().join("escaped/pwn.rs"), "").unwrap();
+    let (_, stderr, code) = run_qualifier(
+        &up,
+        &[
+            "attest",
+            "../escaped/pwn.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "x",
+            "--file",
+            ".qual",
+            "--issuer",
+            "mailto:up@example.com",
+        ],
+    );
+    assert_eq!(code, 0, "attest failed: {stderr}");
+    let (statements, _, _) = run_qualifier(&up, &["export", "--format", "in-toto"]);
+
+    let downstream = tempfile::tempdir().unwrap();
+    let down = downstream.path().join("down");
+    std::fs::create_dir_all(&down).unwrap();
+    run_qualifier(&down, &["init"]);
+    std::fs::write(down.join("upstream.intoto.jsonl"), statements).unwrap();
+    let (_, stderr, code) = run_qualifier(&down, &["import", "upstream.intoto.jsonl"]);
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("not a path inside the project"),
+        "got: {stderr}"
+    );
+    assert!(!downstream.path().join("escaped").exists());
+}
+
+#[test]
+fn test_import_rejects_dependency_cycles() {
+    let upstream = tempfile::tempdir().unwrap();
+    run_qualifier(upstream.path(), &["init"]);
+    std::fs::create_dir_all(upstream.path().join("src")).unwrap();
+    std::fs::write(upstream.path().join("src/a"), "").unwrap();
+    run_qualifier(
+        upstream.path(),
+        &[
+            "dep",
+            "add",
+            "src/a",
+            "--on",
+            "src/b",
+            "--issuer",
+            "mailto:up@example.com",
+        ],
+    );
+    let (statements, _, _) = run_qualifier(upstream.path(), &["export", "--format", "in-toto"]);
+
+    let downstream = tempfile::tempdir().unwrap();
+    run_qualifier(downstream.path(), &["init"]);
+    run_qualifier(
+        downstream.path(),
+        &[
+            "dep",
+            "add",
+            "src/b",
+            "--on",
+            "src/a",
+            "--issuer",
+            "mailto:down@example.com",
+        ],
+    );
+    std::fs::write(downstream.path().join("upstream.intoto.jsonl"), statements).unwrap();
+    let before = std::fs::read_to_string(downstream.path().join("src/.qual")).unwrap();
+
+    for extra in [&["--dry-run"][..], &[]] {
+        let mut args = vec!["import", "upstream.intoto.jsonl"];
+        args.extend(extra);
+        let (_, stderr, code) = run_qualifier(downstream.path(), &args);
+        assert_ne!(code, 0);
+        assert!(stderr.contains("cycle"), "got: {stderr}");
+    }
+    assert_eq!(
+        std::fs::read_to_string(downstream.path().join("src/.qual")).unwrap(),
+        before
+    );
+    let (_, stderr, code) = run_qualifier(downstream.path(), &["check"]);
+    assert_eq!(code, 0, "check failed: {stderr}");
+}
+
+#[test]
+fn test_import_dry_run_creates_nothing() {
+    let upstream = tempfile::tempdir().unwrap();
+    run_qualifier(upstream.path(), &["init"]);
+    std::fs::create_dir_all(upstream.path().join("lib/deep")).unwrap();
+    std::fs::write(upstream.path().join("lib/deep/x.rs"), "").unwrap();
+    run_qualifier(
+        upstream.path(),
+        &[
+            "attest",
+            "lib/deep/x.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "x",
+        ],
+    );
+    let (statements, _, _) = run_qualifier(upstream.path(), &["export", "--format", "in-toto"]);
+
+    let downstream = tempfile::tempdir().unwrap();
+    run_qualifier(downstream.path(), &["init"]);
+    std::fs::write(downstream.path().join("upstream.intoto.jsonl"), statements).unwrap();
+    let (stdout, stderr, code) = run_qualifier(
+        downstream.path(),
+        &["import", "upstream.intoto.jsonl", "--dry-run"],
+    );
+    assert_eq!(code, 0, "import failed: {stderr}");
+    assert!(stdout.contains("would import"), "got: {stdout}");
+    assert!(!downstream.path().join("lib").exists());
+}
+
+// --- qualifier import-sarif ---
+
+const CLIPPY_SARIF: &str = r#"{
+  "version": "2.1.0",
+  "runs": [{
+    "tool": {"driver": {"name": "clippy"}},
+    "results": [{
+      "ruleId": "clippy::unwrap_used",
+      "level": "warning",
+      "message": {"text": "used `unwrap()` on a `Result` value"},
+      "locations": [{"physicalLocation": {
+        "artifactLocation": {"uri": "src/parser.rs"},
+        "region": {"startLine": 42}
+      }}]
+    }]
+  }]
+}"#;
+
+#[test]
+fn test_import_sarif_supersedes_on_reimport() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(
+        dir.path().join(".qualifier.toml"),
+        "[sarif.levels]\nwarning = -7\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("clippy.sarif"), CLIPPY_SARIF).unwrap();
+
+    for _ in 0..2 {
+        let (_, stderr, code) = run_qualifier(dir.path(), &["import-sarif", "clippy.sarif"]);
+        assert_eq!(code, 0, "import-sarif failed: {stderr}");
+    }
+
+    let content = std::fs::read_to_string(dir.path().join("src/.qual")).unwrap();
+    let records: Vec<serde_json::Value> = content
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["issuer"], "urn:tool:clippy");
+    assert_eq!(records[0]["issuer_type"], "tool");
+    assert_eq!(records[0]["body"]["kind"], "clippy::unwrap_used");
+    assert_eq!(records[0]["body"]["score"], -7);
+    assert_eq!(records[0]["body"]["span"]["start"]["line"], 42);
+    assert_eq!(records[1]["body"]["supersedes"], records[0]["id"]);
+
+    // Only the latest finding counts.
+    let (stdout, _, _) = run_qualifier(dir.path(), &["score", "--format", "json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed[0]["raw_score"], -7);
+}
+
+#[test]
+fn test_import_sarif_resolves_fixed_findings() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    let both = CLIPPY_SARIF.replace(
+        "\"results\": [{",
+        r#""results": [{
+      "ruleId": "clippy::todo",
+      "level": "error",
+      "message": {"text": "`todo` should not be present"},
+      "locations": [{"physicalLocation": {
+        "artifactLocation": {"uri": "src/lexer.rs"},
+        "region": {"startLine": 7}
+      }}]
+    }, {"#,
+    );
+    std::fs::write(dir.path().join("both.sarif"), both).unwrap();
+    std::fs::write(dir.path().join("partial.sarif"), CLIPPY_SARIF).unwrap();
+    let fixed = CLIPPY_SARIF.replace(
+        "\"results\": [{",
+        r#""artifacts": [{"location": {"uri": "src/lexer.rs"}}],
+    "results": [{"#,
+    );
+    std::fs::write(dir.path().join("fixed.sarif"), fixed).unwrap();
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["import-sarif", "both.sarif"]);
+    assert_eq!(code, 0, "import-sarif failed: {stderr}");
+
+    let score = |subject: &str| {
+        let (stdout, _, _) = run_qualifier(dir.path(), &["show", subject, "--format", "json"]);
+        serde_json::from_str::<serde_json::Value>(&stdout).unwrap()["raw_score"].clone()
+    };
+
+    // A scan that never looked at lexer.rs says nothing about it.
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["import-sarif", "partial.sarif"]);
+    assert_eq!(code, 0, "import-sarif failed: {stderr}");
+    assert!(stdout.contains("0 resolved"), "got: {stdout}");
+    assert_eq!(score("src/lexer.rs"), -20);
+
+    // One that analyzed it and reported nothing has seen it fixed.
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["import-sarif", "fixed.sarif"]);
+    assert_eq!(code, 0, "import-sarif failed: {stderr}");
+    assert!(
+        stdout.contains("1 superseding earlier findings, 1 resolved"),
+        "got: {stdout}"
+    );
+    assert_eq!(score("src/lexer.rs"), 0);
+    assert_eq!(score("src/parser.rs"), -10);
+
+    // Re-importing the same run resolves nothing further.
+    let (stdout, _, _) = run_qualifier(dir.path(), &["import-sarif", "fixed.sarif"]);
+    assert!(stdout.contains("0 resolved"), "got: {stdout}");
+}
+
+#[test]
+fn test_import_sarif_dry_run_creates_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(dir.path().join("clippy.sarif"), CLIPPY_SARIF).unwrap();
+
+    let (stdout, stderr, code) =
+        run_qualifier(dir.path(), &["import-sarif", "clippy.sarif", "--dry-run"]);
+    assert_eq!(code, 0, "import-sarif failed: {stderr}");
+    assert!(stdout.contains("would import"), "got: {stdout}");
+    assert!(!dir.path().join("src").exists());
+}
+
+#[test]
+fn test_export_sarif() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "src/a.rs",
+            "--kind",
+            "concern",
+            "--score",
+            "-10",
+            "--summary",
+            "slow",
+            "--span",
+            "3:5",
+            "--suggested-fix",
+            "cache it",
+            "--issuer",
+            "mailto:alice@example.com",
+        ],
+    );
+    run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "src/a.rs",
+            "--kind",
+            "praise",
+            "--summary",
+            "tidy",
+            "--issuer",
+            "mailto:alice@example.com",
+        ],
+    );
+
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["export", "--format", "sarif"]);
+    assert_eq!(code, 0, "export failed: {stderr}");
+    let log: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["runs"].as_array().unwrap().len(), 1);
+
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "mailto:alice@example.com");
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "concern");
+    assert_eq!(results[0]["level"], "warning");
+    assert_eq!(results[0]["message"]["text"], "slow");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/a.rs");
+    assert_eq!(location["region"]["startLine"], 3);
+    assert_eq!(location["region"]["endLine"], 5);
+    assert_eq!(results[0]["fixes"][0]["description"]["text"], "cache it");
+}
+
+// --- qualifier rename ---
+
+#[test]
+fn test_rename_moves_history_and_dependencies() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("qualifier.graph.jsonl"),
+        "{\"subject\":\"bin/server\",\"depends_on\":[\"src/old.rs\"]}\n",
+    )
+    .unwrap();
+
+    for (subject, kind, score) in [
+        ("src/old.rs", "concern", "--score=-30"),
+        ("src/old.rs", "praise", "--score=50"),
+        ("src/other.rs", "pass", "--score=20"),
+    ] {
+        let (_, stderr, code) = run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                subject,
+                "--kind",
+                kind,
+                score,
+                "--summary",
+                "x",
+                "--issuer",
+                "mailto:test@test.com",
+            ],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "dep",
+            "add",
+            "app.rs",
+            "--on",
+            "src/old.rs",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "dep add failed: {stderr}");
+
+    let before = std::fs::read_to_string(dir.path().join("src/.qual")).unwrap();
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &["rename", "src/old.rs", "src/new.rs", "--dry-run"],
+    );
+    assert_eq!(code, 0, "dry run failed: {stderr}");
+    assert!(stdout.contains("Would rename"), "got: {stdout}");
+    assert!(stdout.contains("folded 2 record(s)"), "got: {stdout}");
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("src/.qual")).unwrap(),
+        before,
+        "dry run must not write"
+    );
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["rename", "src/old.rs", "src/new.rs"]);
+    assert_eq!(code, 0, "rename failed: {stderr}");
+
+    let (stdout, _, _) = run_qualifier(dir.path(), &["score", "--format", "json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let subjects: Vec<&str> = parsed
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["subject"].as_str().unwrap())
+        .collect();
+    assert!(!subjects.contains(&"src/old.rs"), "got: {subjects:?}");
+    let renamed = parsed
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["subject"] == "src/new.rs")
+        .expect("renamed subject should be scored");
+    assert_eq!(renamed["raw_score"], 20);
+
+    // The epoch points back at the old record ids.
+    let (stdout, _, _) = run_qualifier(dir.path(), &["show", "src/new.rs", "--format", "json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["records"][0]["type"], "epoch");
+    assert_eq!(
+        parsed["records"][0]["body"]["refs"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+
+    let (stdout, _, _) = run_qualifier(dir.path(), &["dep", "ls", "--format", "json"]);
+    assert!(
+        stdout.contains("src/new.rs") && !stdout.contains("src/old.rs"),
+        "got: {stdout}"
+    );
+    let graph = std::fs::read_to_string(dir.path().join("qualifier.graph.jsonl")).unwrap();
+    assert!(
+        graph.contains("src/new.rs") && !graph.contains("src/old.rs"),
+        "got: {graph}"
+    );
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["rename", "src/old.rs", "src/x.rs"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("src/old.rs"), "got: {stderr}");
+}
+
+#[test]
+fn test_rename_within_one_file_drops_spent_retractions() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+
+    let mut ids = Vec::new();
+    for (kind, score) in [("concern", "--score=-30"), ("pass", "--score=20")] {
+        let (stdout, stderr, code) = run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                "src/old.rs",
+                "--kind",
+                kind,
+                score,
+                "--summary",
+                "x",
+                "--issuer",
+                "mailto:test@test.com",
+            ],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+        ids.push(
+            stdout
+                .lines()
+                .find_map(|l| l.trim().strip_prefix("id: "))
+                .unwrap()
+                .to_string(),
+        );
+    }
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "retract",
+            &ids[0],
+            "--summary",
+            "wrong file",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "retract failed: {stderr}");
+
+    // The new subject's records go to the same file, so it is rewritten,
+    // not removed.
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &["rename", "src/old.rs", "src/new.rs", "--dry-run"],
+    );
+    assert_eq!(code, 0, "dry run failed: {stderr}");
+    assert!(stdout.contains("rewrite ./src/.qual"), "got: {stdout}");
+    assert!(!stdout.contains("remove"), "got: {stdout}");
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["rename", "src/old.rs", "src/new.rs"]);
+    assert_eq!(code, 0, "rename failed: {stderr}");
+    let content = std::fs::read_to_string(dir.path().join("src/.qual")).unwrap();
+    assert_eq!(content.lines().count(), 1, "got: {content}");
+    assert!(content.contains("\"type\":\"epoch\""), "got: {content}");
+
+    let (stdout, _, _) = run_qualifier(dir.path(), &["show", "src/new.rs", "--format", "json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["raw_score"], 20);
+}
+
+#[test]
+fn test_rename_keeps_pending_waivers_expiring() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+
+    for (kind, score, expires) in [
+        ("waiver", "--score=40", Some("2099-11-01")),
+        ("concern", "--score=-30", None),
+    ] {
+        let mut args = vec![
+            "attest",
+            "src/old.rs",
+            "--kind",
+            kind,
+            score,
+            "--summary",
+            "x",
+            "--issuer",
+            "mailto:test@test.com",
+        ];
+        if let Some(expires) = expires {
+            args.extend(["--expires-at", expires]);
+        }
+        let (_, stderr, code) = run_qualifier(dir.path(), &args);
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+
+    let score_at = |subject: &str, at: &str| -> serde_json::Value {
+        let (stdout, stderr, code) = run_qualifier(
+            dir.path(),
+            &["show", subject, "--format", "json", "--at", at],
+        );
+        assert_eq!(code, 0, "show failed: {stderr}");
+        serde_json::from_str::<serde_json::Value>(&stdout).unwrap()["raw_score"].clone()
+    };
+    assert_eq!(score_at("src/old.rs", "2099-10-01T00:00:00Z"), 10);
+    assert_eq!(score_at("src/old.rs", "2099-12-01T00:00:00Z"), -30);
+
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["rename", "src/old.rs", "src/new.rs"]);
+    assert_eq!(code, 0, "rename failed: {stderr}");
+    assert!(stdout.contains("folded 1 record(s)"), "got: {stdout}");
+
+    assert_eq!(score_at("src/new.rs", "2099-10-01T00:00:00Z"), 10);
+    assert_eq!(score_at("src/new.rs", "2099-12-01T00:00:00Z"), -30);
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &["check", "--fail-on-expired", "--at", "2099-12-01T00:00:00Z"],
+    );
+    assert_ne!(code, 0, "the carried waiver should still expire");
+    assert!(stderr.contains("1 expired attestation(s)"), "got: {stderr}");
+}
+
+#[test]
+fn test_rename_drops_signatures_of_removed_records() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    let key = dir.path().join("alice.key");
+    let key = key.to_str().unwrap();
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "keygen",
+            "--output",
+            key,
+            "--issuer",
+            "mailto:alice@example.com",
+            "--register",
+        ],
+    );
+    assert_eq!(code, 0, "keygen failed: {stderr}");
+    for subject in ["lib/old.rs", "lib/keep.rs"] {
+        let (_, stderr, code) = run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                subject,
+                "--kind",
+                "pass",
+                "--summary",
+                "x",
+                "--issuer",
+                "mailto:alice@example.com",
+            ],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &["sign", "--key", key, "--issuer", "mailto:alice@example.com"],
+    );
+    assert_eq!(code, 0, "sign failed: {stderr}");
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["rename", "lib/old.rs", "src/new.rs"]);
+    assert_eq!(code, 0, "rename failed: {stderr}");
+    let sidecar = dir.path().join("lib/.qual.sig");
+    assert_eq!(
+        std::fs::read_to_string(&sidecar).unwrap().lines().count(),
+        1
+    );
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["verify"]);
+    assert_eq!(code, 0, "verify failed: {stderr}");
+    assert!(stdout.contains("1 verified, 1 unsigned"), "got: {stdout}");
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["rename", "lib/keep.rs", "src/keep.rs"]);
+    assert_eq!(code, 0, "rename failed: {stderr}");
+    assert!(!dir.path().join("lib/.qual").exists());
+    assert!(!sidecar.exists(), "the emptied file's sidecar must go too");
+}
+
+// --- qualifier layout migrate ---
+
+#[test]
+fn test_layout_migrate_round_trips_scores() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    for (subject, kind) in [
+        ("main.rs", "pass"),
+        ("src/a.rs", "concern"),
+        ("src/b.rs", "praise"),
+    ] {
+        let (_, stderr, code) = run_qualifier(
+            dir.path(),
+            &["attest", subject, "--kind", kind, "--summary", "x"],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+    let scores = |dir: &Path| run_qualifier(dir, &["score", "--format", "json"]).0;
+    let before = scores(dir.path());
+
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &["layout", "migrate", "--to", "file", "--dry-run"],
+    );
+    assert_eq!(code, 0, "dry run failed: {stderr}");
+    assert!(
+        stdout.contains("Would migrate to the file layout"),
+        "got: {stdout}"
+    );
+    assert!(
+        stdout.contains("create ./src/a.rs.qual (1 record(s))"),
+        "got: {stdout}"
+    );
+    assert!(stdout.contains("remove ./src/.qual"), "got: {stdout}");
+    assert!(
+        dir.path().join("src/.qual").exists(),
+        "dry run must not write"
+    );
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["layout", "migrate", "--to", "file"]);
+    assert_eq!(code, 0, "migrate failed: {stderr}");
+    for path in ["main.rs.qual", "src/a.rs.qual", "src/b.rs.qual"] {
+        assert!(dir.path().join(path).exists(), "{path} missing");
+    }
+    assert!(!dir.path().join("src/.qual").exists());
+    assert_eq!(scores(dir.path()), before);
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["layout", "migrate", "--to", "project"]);
+    assert_eq!(code, 0, "migrate failed: {stderr}");
+    let content = std::fs::read_to_string(dir.path().join(".qual")).unwrap();
+    assert_eq!(content.lines().count(), 3);
+    assert!(!dir.path().join("src/a.rs.qual").exists());
+    assert_eq!(scores(dir.path()), before);
+
+    let (_, _, code) = run_qualifier(dir.path(), &["layout", "migrate", "--to", "directory"]);
+    assert_eq!(code, 0);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("src/.qual"))
+            .unwrap()
+            .lines()
+            .count(),
+        2
+    );
+    assert_eq!(scores(dir.path()), before);
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["layout", "migrate", "--to", "directory"]);
+    assert_eq!(code, 0);
+    assert!(
+        stdout.contains("Already in the directory layout"),
+        "got: {stdout}"
+    );
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["layout", "migrate", "--to", "tree"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("unknown layout 'tree'"), "got: {stderr}");
+}
+
+#[test]
+fn test_layout_migrate_moves_signatures_with_records() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    let key = dir.path().join("alice.key");
+    let key = key.to_str().unwrap();
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "keygen",
+            "--output",
+            key,
+            "--issuer",
+            "mailto:alice@example.com",
+            "--register",
+        ],
+    );
+    assert_eq!(code, 0, "keygen failed: {stderr}");
+    for subject in ["src/a.rs", "src/b.rs"] {
+        let (_, stderr, code) = run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                subject,
+                "--kind",
+                "pass",
+                "--summary",
+                "x",
+                "--issuer",
+                "mailto:alice@example.com",
+            ],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &["sign", "--key", key, "--issuer", "mailto:alice@example.com"],
+    );
+    assert_eq!(code, 0, "sign failed: {stderr}");
+
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &["layout", "migrate", "--to", "file", "--dry-run"],
+    );
+    assert_eq!(code, 0, "dry run failed: {stderr}");
+    assert!(
+        stdout.contains("create ./src/a.rs.qual.sig (1 signature(s))"),
+        "got: {stdout}"
+    );
+    assert!(stdout.contains("remove ./src/.qual.sig"), "got: {stdout}");
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["layout", "migrate", "--to", "file"]);
+    assert_eq!(code, 0, "migrate failed: {stderr}");
+    assert!(!dir.path().join("src/.qual.sig").exists());
+    assert!(dir.path().join("src/a.rs.qual.sig").exists());
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["verify", "--strict"]);
+    assert_eq!(code, 0, "verify failed: {stderr}");
+    assert!(stdout.contains("2 verified, 0 unsigned"), "got: {stdout}");
+}
+
+// --- qualifier watch ---
+
+#[test]
+fn test_watch_emits_json_score_events() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+    use std::time::Duration;
+
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    for (subject, score) in [("lib/crypto", "--score=40"), ("lib/auth", "--score=60")] {
+        run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                subject,
+                "--kind",
+                "pass",
+                score,
+                "--summary",
+                "x",
+                "--issuer",
+                "mailto:test@test.com",
+            ],
+        );
+    }
+    run_qualifier(
+        dir.path(),
+        &[
+            "dep",
+            "add",
+            "lib/auth",
+            "--on",
+            "lib/crypto",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+
+    let mut child = Command::new(qualifier_bin())
+        .args(["watch", "--format", "json"])
+        .current_dir(dir.path())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start watch");
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if tx.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    let next = || {
+        let line = rx.recv_timeout(Duration::from_secs(10));
+        serde_json::from_str::<serde_json::Value>(&line.expect("watch produced no event")).unwrap()
+    };
+
+    let ready = next();
+    assert_eq!(ready["event"], "ready");
+    assert_eq!(ready["subjects"], 2);
+
+    run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "lib/crypto",
+            "--kind",
+            "blocker",
+            "--score=-60",
+            "--summary",
+            "timing leak",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+
+    let auth = next();
+    let crypto = next();
+    child.kill().ok();
+    child.wait().ok();
+
+    assert_eq!(auth["event"], "score");
+    assert_eq!(auth["subject"], "lib/auth");
+    assert_eq!(auth["before"]["effective_score"], 40);
+    assert_eq!(auth["after"]["effective_score"], -20);
+    assert_eq!(auth["after"]["limiting_path"][0], "lib/crypto");
+    assert_eq!(crypto["subject"], "lib/crypto");
+    assert_eq!(crypto["after"]["raw_score"], -20);
+}
+
+// --- retract ---
+
+#[test]
+fn test_retract_withdraws_record_until_compaction() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+
+    let mut ids = Vec::new();
+    for (kind, score) in [("pass", "--score=20"), ("concern", "--score=-40")] {
+        let (stdout, stderr, code) = run_qualifier(
+            dir.path(),
+            &[
+                "attest",
+                "api.rs",
+                "--kind",
+                kind,
+                score,
+                "--summary",
+                "x",
+                "--issuer",
+                "mailto:test@test.com",
+            ],
+        );
+        assert_eq!(code, 0, "attest failed: {stderr}");
+        let id = stdout
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("id: "))
+            .expect("attest prints the record id")
+            .to_string();
+        ids.push(id);
+    }
+
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "retract",
+            &ids[1][..12],
+            "--summary",
+            "false positive",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "retract failed: {stderr}");
+    assert!(
+        stdout.contains("Retracted 1 record(s) from api.rs"),
+        "got: {stdout}"
+    );
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["show", "api.rs", "--format", "json"]);
+    assert_eq!(code, 0);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(parsed["raw_score"], 20);
+    assert!(parsed.get("revoked").is_none());
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["show", "api.rs"]);
+    assert_eq!(code, 0);
+    assert!(!stdout.contains("concern"), "got: {stdout}");
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["show", "api.rs", "--revoked"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("concern"), "got: {stdout}");
+    assert!(stdout.contains("[revoked by"), "got: {stdout}");
+    assert!(stdout.contains("false positive"), "got: {stdout}");
+
+    let (stdout, _, code) = run_qualifier(
+        dir.path(),
+        &["praise", "api.rs", "--revoked", "--format", "json"],
+    );
+    assert_eq!(code, 0);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let revoked = parsed["records"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["id"] == ids[1].as_str())
+        .expect("revoked record is listed");
+    assert_eq!(revoked["revoked_by"]["summary"], "false positive");
+
+    // Revoking a revocation is rejected
+    let content = std::fs::read_to_string(dir.path().join(".qual")).unwrap();
+    let revocation_id = content
+        .lines()
+        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
+        .find(|r| r["type"] == "revocation")
+        .map(|r| r["id"].as_str().unwrap().to_string())
+        .unwrap();
+    let (_, stderr, code) = run_qualifier(dir.path(), &["retract", &revocation_id]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("revocation"), "got: {stderr}");
+
+    let (_, stderr, code) = run_qualifier(dir.path(), &["compact", "api.rs"]);
+    assert_eq!(code, 0, "compact failed: {stderr}");
+    let content = std::fs::read_to_string(dir.path().join(".qual")).unwrap();
+    assert!(content.contains(&ids[0]));
+    assert!(
+        !content.contains(&ids[1]),
+        "revoked record should be pruned"
+    );
+    assert!(
+        !content.contains("revocation"),
+        "spent revocation should be pruned"
+    );
+
+    let (_, _, code) = run_qualifier(dir.path(), &["retract", "ffffffffffff"]);
+    assert_ne!(code, 0);
+}
+
+// --- expiry ---
+
+#[test]
+fn test_expired_attestations_stop_counting() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+
+    for (subject, kind, score, expires) in [
+        ("api.rs", "waiver", "--score=30", Some("2099-12-01")),
+        ("api.rs", "pass", "--score=10", None),
+        ("db.rs", "pass", "--score=10", Some("2100-06-01T00:00:00Z")),
+    ] {
+        let mut args = vec![
+            "attest",
+            subject,
+            "--kind",
+            kind,
+            score,
+            "--summary",
+            "x",
+            "--issuer",
+            "mailto:test@test.com",
+        ];
+        if let Some(expires) = expires {
+            args.extend(["--expires-at", expires]);
+        }
+        let (_, stderr, code) = run_qualifier(dir.path(), &args);
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+
+    let score_at = |at: &str| -> serde_json::Value {
+        let (stdout, _, code) = run_qualifier(
+            dir.path(),
+            &["show", "api.rs", "--format", "json", "--at", at],
+        );
+        assert_eq!(code, 0);
+        serde_json::from_str(&stdout).unwrap()
+    };
+    assert_eq!(score_at("2099-11-30T00:00:00Z")["raw_score"], 40);
+    assert_eq!(score_at("2099-12-01T00:00:00Z")["raw_score"], 10);
+    assert_eq!(score_at("2099-12-01")["raw_score"], 10, "--at takes dates");
+
+    let (stdout, _, code) = run_qualifier(
+        dir.path(),
+        &[
+            "ls",
+            "--expiring-within",
+            "30d",
+            "--at",
+            "2099-11-15T00:00:00Z",
+        ],
+    );
+    assert_eq!(code, 0);
+    assert!(stdout.contains("api.rs"), "got: {stdout}");
+    assert!(stdout.contains("expires 2099-12-01"), "got: {stdout}");
+    assert!(!stdout.contains("db.rs"), "got: {stdout}");
+
+    for window in ["-5d", "30\u{e9}", "d", "", "99999999999999999w"] {
+        let arg = format!("--expiring-within={window}");
+        let (_, stderr, code) = run_qualifier(dir.path(), &["ls", &arg]);
+        assert_ne!(code, 0, "{window:?} should be rejected");
+        assert!(stderr.contains("--expiring-within"), "got: {stderr}");
+    }
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &["check", "--fail-on-expired", "--at", "2099-12-02T00:00:00Z"],
+    );
+    assert_ne!(code, 0, "expired waiver should fail the gate");
+    assert!(stderr.contains("EXPIRED:"), "got: {stderr}");
+    assert!(stderr.contains("1 expired attestation(s)"), "got: {stderr}");
+
+    let (_, _, code) = run_qualifier(dir.path(), &["check", "--at", "2099-12-02T00:00:00Z"]);
+    assert_eq!(code, 0, "expiry only fails the gate when asked");
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "x",
+            "--expires-at",
+            "2001-01-01",
+        ],
+    );
+    assert_ne!(code, 0);
+    assert!(stderr.contains("expires_at"), "got: {stderr}");
+}
+
+// --- stale ---
+
+#[test]
+fn test_stale_detects_pinned_content_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(dir.path().join("api.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+
+    for (kind, score, span) in [
+        ("pass", "--score=30", None),
+        ("praise", "--score=20", Some("1")),
+    ] {
+        let mut args = vec![
+            "attest",
+            "api.rs",
+            "--kind",
+            kind,
+            score,
+            "--summary",
+            "reviewed",
+            "--issuer",
+            "mailto:test@test.com",
+            "--pin",
+        ];
+        if let Some(span) = span {
+            args.extend(["--span", span]);
+        }
+        let (_, stderr, code) = run_qualifier(dir.path(), &args);
+        assert_eq!(code, 0, "attest failed: {stderr}");
+    }
+    let content = std::fs::read_to_string(dir.path().join(".qual")).unwrap();
+    assert_eq!(content.matches("\"digest\":").count(), 2);
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["stale"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("No stale attestations."), "got: {stdout}");
+
+    // Rewrite line 2 only: the span pin on line 1 still matches.
+    std::fs::write(dir.path().join("api.rs"), "fn a() {}\nfn b() { todo!() }\n").unwrap();
+    let (stdout, _, code) = run_qualifier(dir.path(), &["stale", "--format", "json"]);
+    assert_eq!(code, 0);
+    let stale: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let stale = stale.as_array().unwrap();
+    assert_eq!(stale.len(), 1, "got: {stdout}");
+    assert_eq!(stale[0]["kind"], "pass");
+    assert_eq!(stale[0]["reason"], "changed");
+
+    let score = |dir: &Path| -> serde_json::Value {
+        let (stdout, _, code) = run_qualifier(dir, &["show", "api.rs", "--format", "json"]);
+        assert_eq!(code, 0);
+        serde_json::from_str::<serde_json::Value>(&stdout).unwrap()["raw_score"].clone()
+    };
+    assert_eq!(
+        score(dir.path()),
+        50,
+        "stale records count fully by default"
+    );
+    std::fs::write(dir.path().join(".qualifier.toml"), "[stale]\nweight = 50\n").unwrap();
+    assert_eq!(score(dir.path()), 35);
+    std::fs::write(dir.path().join(".qualifier.toml"), "[stale]\nweight = 0\n").unwrap();
+    assert_eq!(score(dir.path()), 20);
+
+    std::fs::remove_file(dir.path().join("api.rs")).unwrap();
+    let (stdout, _, _) = run_qualifier(dir.path(), &["stale"]);
+    assert_eq!(
+        stdout.matches("subject missing").count(),
+        2,
+        "got: {stdout}"
+    );
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "x",
+            "--pin",
+        ],
+    );
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--pin"), "got: {stderr}");
+}
+
+// --- reanchor ---
+
+#[test]
+fn test_reanchor_follows_moved_spans() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(
+        dir.path().join("api.rs"),
+        "use std::io;\n\nfn parse() {\n    input.unwrap()\n}\n",
+    )
+    .unwrap();
+
+    let (stdout, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--kind",
+            "concern",
+            "--summary",
+            "panics",
+            "--span",
+            "3:5",
+            "--fingerprint",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "attest failed: {stderr}");
+    let original_id = stdout
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("id: "))
+        .unwrap()
+        .to_string();
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["reanchor"]);
+    assert_eq!(code, 0);
+    assert!(
+        stdout.contains("Re-anchored 0 attestation(s)"),
+        "got: {stdout}"
+    );
+
+    std::fs::write(
+        dir.path().join("api.rs"),
+        "//! API\n\nuse std::io;\n\nfn parse() {\n    input.unwrap()\n}\n",
+    )
+    .unwrap();
+    let before = std::fs::read_to_string(dir.path().join(".qual")).unwrap();
+    let (stdout, _, code) = run_qualifier(dir.path(), &["reanchor", "--dry-run"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("L3\u{2013}5 "), "got: {stdout}");
+    assert!(stdout.contains("-> L5\u{2013}7"), "got: {stdout}");
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join(".qual")).unwrap(),
+        before,
+        "dry run must not write"
+    );
+
+    let (stdout, stderr, code) = run_qualifier(dir.path(), &["reanchor"]);
+    assert_eq!(code, 0, "reanchor failed: {stderr}");
+    assert!(
+        stdout.contains("Re-anchored 1 attestation(s)"),
+        "got: {stdout}"
+    );
+
+    let (stdout, _, _) = run_qualifier(dir.path(), &["show", "api.rs", "--format", "json"]);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let records = parsed["records"].as_array().unwrap();
+    let tip = records
+        .iter()
+        .find(|r| r["body"]["supersedes"] == original_id.as_str())
+        .expect("a superseding record was written");
+    assert_eq!(tip["body"]["span"]["start"]["line"], 5);
+    assert_eq!(tip["body"]["span"]["end"]["line"], 7);
+    let original = records.iter().find(|r| r["id"] == original_id.as_str());
+    let original = original.expect("the original is kept");
+    assert_eq!(tip["issuer"], "mailto:test@test.com");
+    assert_eq!(tip["created_at"], original["created_at"]);
+    assert_eq!(parsed["raw_score"], -10, "only the re-anchored tip counts");
+
+    // The code is gone: report it rather than guessing.
+    std::fs::write(
+        dir.path().join("api.rs"),
+        "fn parse() -> Result<()> { Ok(()) }\n",
+    )
+    .unwrap();
+    let (stdout, _, code) = run_qualifier(dir.path(), &["reanchor"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("lost"), "got: {stdout}");
+    assert!(stdout.contains("1 unresolved"), "got: {stdout}");
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "x",
+            "--fingerprint",
+        ],
+    );
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--span"), "got: {stderr}");
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--kind",
+            "pass",
+            "--summary",
+            "x",
+            "--span",
+            "40:50",
+            "--fingerprint",
+        ],
+    );
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("span L40\u{2013}50 runs past the end of 'api.rs'"),
+        "got: {stderr}"
+    );
+}
+
+#[test]
+fn test_attest_symbol_follows_item() {
+    let dir = tempfile::tempdir().unwrap();
+    run_qualifier(dir.path(), &["init"]);
+    std::fs::write(
+        dir.path().join("api.rs"),
+        "struct Parser;\n\nimpl Parser {\n    fn parse(&self) {\n        input.unwrap()\n    }\n}\n",
+    )
+    .unwrap();
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--summary",
+            "no such item",
+            "--symbol",
+            "Parser::missing",
+        ],
+    );
+    assert_ne!(code, 0);
+    assert!(stderr.contains("not found"), "got: {stderr}");
+
+    let (_, stderr, code) = run_qualifier(
+        dir.path(),
+        &[
+            "attest",
+            "api.rs",
+            "--kind",
+            "concern",
+            "--summary",
+            "panics",
+            "--symbol",
+            "impl Parser::parse",
+            "--fingerprint",
+            "--issuer",
+            "mailto:test@test.com",
+        ],
+    );
+    assert_eq!(code, 0, "attest failed: {stderr}");
+    let qual = std::fs::read_to_string(dir.path().join(".qual")).unwrap();
+    assert!(
+        qual.contains("\"symbol\":\"impl Parser::parse\""),
+        "got: {qual}"
+    );
+    assert!(qual.contains("\"fingerprint\":"), "got: {qual}");
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["show", "api.rs"]);
+    assert_eq!(code, 0);
+    assert!(
+        stdout.contains("(symbol impl Parser::parse at L4\u{2013}6)"),
+        "got: {stdout}"
+    );
+
+    std::fs::write(
+        dir.path().join("api.rs"),
+        "//! API\n\nstruct Parser;\n\nimpl Parser {\n    fn parse(&self) {\n        input.unwrap()\n    }\n}\n",
+    )
+    .unwrap();
+    let (stdout, _, code) = run_qualifier(dir.path(), &["show", "api.rs"]);
+    assert_eq!(code, 0);
+    assert!(
+        stdout.contains("(symbol impl Parser::parse at L6\u{2013}8)"),
+        "got: {stdout}"
+    );
+
+    let (stdout, _, code) = run_qualifier(dir.path(), &["score", "--spans", "api.rs"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("L6"), "got: {stdout}");
+
+    std::fs::write(dir.path().join("api.rs"), "struct Parser;\n").unwrap();
+    let (stdout, _, code) = run_qualifier(dir.path(), &["praise", "api.rs"]);
+    assert_eq!(code, 0);
+    assert!(
+        stdout.contains("symbol: impl Parser::parse (unresolved)"),
+        "got: {stdout}"
+    );
+}
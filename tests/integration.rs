@@ -7,6 +7,15 @@ use qualifier::scoring;
 use chrono::Utc;
 use std::path::PathBuf;
 
+/// Evaluation without decay, at a fixed instant.
+fn nominal() -> scoring::Evaluation {
+    scoring::Evaluation::at(
+        chrono::DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc),
+    )
+}
+
 fn make_att(subject: &str, kind: Kind, score: i32, summary: &str) -> Attestation {
     attestation::finalize(Attestation {
         metabox: "1".into(),
@@ -20,6 +29,9 @@ fn make_att(subject: &str, kind: Kind, score: i32, summary: &str) -> Attestation
         id: String::new(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind,
             r#ref: None,
             score,
@@ -27,6 +39,7 @@ fn make_att(subject: &str, kind: Kind, score: i32, summary: &str) -> Attestation
             suggested_fix: None,
             summary: summary.into(),
             supersedes: None,
+            symbol: None,
             tags: vec![],
         },
     })
@@ -52,6 +65,9 @@ fn test_golden_attestation_id() {
         id: String::new(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind: Kind::Concern,
             r#ref: None,
             score: -30,
@@ -59,6 +75,7 @@ fn test_golden_attestation_id() {
             suggested_fix: None,
             summary: "Panics on malformed input".into(),
             supersedes: None,
+            symbol: None,
             tags: vec![],
         },
     });
@@ -122,6 +139,43 @@ fn test_golden_dependency_id() {
     );
 }
 
+#[test]
+fn test_golden_policy_id() {
+    use qualifier::attestation::IssuerType;
+    use qualifier::attestation::{self, PolicyBody, PolicyRecord, PolicyRule, Requirement};
+
+    let policy = attestation::finalize_record(Record::Policy(PolicyRecord {
+        metabox: "1".into(),
+        record_type: "policy".into(),
+        subject: "src/".into(),
+        issuer: "mailto:lead@example.com".into(),
+        issuer_type: None,
+        created_at: chrono::DateTime::parse_from_rfc3339("2026-02-25T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc),
+        id: String::new(),
+        body: PolicyBody {
+            rules: vec![PolicyRule {
+                forbid: vec![Kind::Blocker],
+                min_score: Some(0),
+                require: vec![Requirement {
+                    issuer_type: Some(IssuerType::Human),
+                    kind: Kind::Pass,
+                    min_count: None,
+                }],
+                subjects: "src/**".into(),
+            }],
+            summary: "Release gate".into(),
+            supersedes: None,
+        },
+    }));
+    assert_eq!(
+        policy.id(),
+        "6c280a4d0bc4d2cfdb0df6da56459379c177a52a60d6a6a14de3d631e378063e",
+        "Golden policy ID changed! Canonical form or hashing is broken."
+    );
+}
+
 // --- Full attestation lifecycle ---
 
 #[test]
@@ -194,7 +248,7 @@ fn test_scoring_with_dependency_graph() {
         },
     ];
 
-    let scores = scoring::effective_scores(&g, &qfs);
+    let scores = scoring::effective_scores(&g, &qfs, &nominal());
 
     // lib/crypto is the poison
     assert_eq!(scores["lib/crypto"].raw, -40);
@@ -228,7 +282,7 @@ fn test_artifacts_in_qual_but_not_in_graph() {
         records: vec![make_record("standalone", Kind::Praise, 50, "fine")],
     }];
 
-    let scores = scoring::effective_scores(&g, &qfs);
+    let scores = scoring::effective_scores(&g, &qfs, &nominal());
 
     // standalone should appear with effective = raw
     assert_eq!(scores["standalone"].raw, 50);
@@ -257,6 +311,9 @@ fn test_compaction_roundtrip_preserves_scores() {
         id: String::new(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind: Kind::Pass,
             r#ref: None,
             score: 20,
@@ -264,6 +321,7 @@ fn test_compaction_roundtrip_preserves_scores() {
             suggested_fix: None,
             summary: "fixed".into(),
             supersedes: Some(original.id().to_string()),
+            symbol: None,
             tags: vec![],
         },
     })));
@@ -275,15 +333,21 @@ fn test_compaction_roundtrip_preserves_scores() {
         records: vec![original, fix, extra],
     };
 
-    let score_before = scoring::raw_score(&qf.records);
+    let score_before = scoring::raw_score(&qf.records, &nominal());
 
     // Prune
     let (pruned, _) = compact::prune(&qf);
-    assert_eq!(scoring::raw_score(&pruned.records), score_before);
+    assert_eq!(
+        scoring::raw_score(&pruned.records, &nominal()),
+        score_before
+    );
 
     // Snapshot
-    let (snapped, _) = compact::snapshot(&qf);
-    assert_eq!(scoring::raw_score(&snapped.records), score_before);
+    let (snapped, _) = compact::snapshot(&qf, &nominal());
+    assert_eq!(
+        scoring::raw_score(&snapped.records, &nominal()),
+        score_before
+    );
     assert_eq!(snapped.records.len(), 1);
     assert!(snapped.records[0].as_epoch().is_some());
 }
@@ -335,6 +399,9 @@ fn test_supersession_cycle_detected() {
         id: "aaa".into(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind: Kind::Pass,
             r#ref: None,
             score: 10,
@@ -342,6 +409,7 @@ fn test_supersession_cycle_detected() {
             suggested_fix: None,
             summary: "a".into(),
             supersedes: Some("bbb".into()),
+            symbol: None,
             tags: vec![],
         },
     }));
@@ -355,6 +423,9 @@ fn test_supersession_cycle_detected() {
         id: "bbb".into(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind: Kind::Pass,
             r#ref: None,
             score: 10,
@@ -362,6 +433,7 @@ fn test_supersession_cycle_detected() {
             suggested_fix: None,
             summary: "b".into(),
             supersedes: Some("aaa".into()),
+            symbol: None,
             tags: vec![],
         },
     }));
@@ -399,6 +471,9 @@ fn test_cross_artifact_supersession_rejected() {
         id: String::new(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind: Kind::Pass,
             r#ref: None,
             score: 20,
@@ -406,6 +481,7 @@ fn test_cross_artifact_supersession_rejected() {
             suggested_fix: None,
             summary: "fix in bar".into(),
             supersedes: Some(a.id().to_string()),
+            symbol: None,
             tags: vec![],
         },
     })));
@@ -429,6 +505,9 @@ fn test_kind_typo_detected_in_validation() {
         id: String::new(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind: Kind::Custom("pss".into()),
             r#ref: None,
             score: 10,
@@ -436,6 +515,7 @@ fn test_kind_typo_detected_in_validation() {
             suggested_fix: None,
             summary: "oops".into(),
             supersedes: None,
+            symbol: None,
             tags: vec![],
         },
     });
@@ -476,6 +556,9 @@ fn test_metabox_roundtrip() {
         id: String::new(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind: Kind::Praise,
             r#ref: Some("git:3aba500".into()),
             score: 30,
@@ -483,6 +566,7 @@ fn test_metabox_roundtrip() {
             suggested_fix: None,
             summary: "Great code".into(),
             supersedes: None,
+            symbol: None,
             tags: vec!["quality".into()],
         },
     });
@@ -513,7 +597,7 @@ fn test_compact_snapshot_produces_epoch() {
         records,
     };
 
-    let (snapped, _) = compact::snapshot(&qf);
+    let (snapped, _) = compact::snapshot(&qf, &nominal());
     assert_eq!(snapped.records.len(), 1);
 
     let epoch = snapped.records[0].as_epoch().unwrap();
@@ -537,6 +621,9 @@ fn test_supersession_with_new_fields() {
         id: String::new(),
         body: AttestationBody {
             detail: None,
+            digest: None,
+            expires_at: None,
+            fingerprint: None,
             kind: Kind::Pass,
             r#ref: Some("git:abc123".into()),
             score: 20,
@@ -544,6 +631,7 @@ fn test_supersession_with_new_fields() {
             suggested_fix: None,
             summary: "fixed it".into(),
             supersedes: Some(original.id().to_string()),
+            symbol: None,
             tags: vec![],
         },
     })));
@@ -556,5 +644,5 @@ fn test_supersession_with_new_fields() {
     assert_eq!(active[0].id(), replacement.id());
 
     // Raw score should be replacement's score only
-    assert_eq!(scoring::raw_score(&all), 20);
+    assert_eq!(scoring::raw_score(&all, &nominal()), 20);
 }